@@ -0,0 +1,23 @@
+/* Generated by `cargo xtask header` from aoc-core/src/ffi.rs; do not edit. */
+#ifndef AOC_H
+#define AOC_H
+
+/* What a call did; anything but `Ok` leaves the answer unwritten. */
+typedef enum {
+  /* The answer was written. */
+  AOC_OK = 0,
+  /* A pointer that must not be null was. */
+  AOC_NULL_POINTER = 1,
+  /* The input is not UTF-8. */
+  AOC_INVALID_UTF8 = 2,
+  /* The input is not a puzzle input for this day. */
+  AOC_PARSE = 3,
+  /* The input outgrows the day's fixed capacities. */
+  AOC_CAPACITY_EXCEEDED = 4,
+  /* A scratch buffer is shorter than the day's sizing function asked for. */
+  AOC_SCRATCH_TOO_SMALL = 5,
+  /* A saved checkpoint cannot be resumed. */
+  AOC_INVALID_CHECKPOINT = 6,
+} aoc_status;
+
+#endif
@@ -0,0 +1,135 @@
+//! One answer type for every day, so a harness can print, compare and
+//! report any of them alike.
+
+use core::fmt;
+
+use crate::Error;
+
+/// Longest text answer, in bytes.
+pub const TEXT_BYTES: usize = 64;
+
+/// A part's answer: a number of whichever width the day needs, or text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+  U64(u64),
+  U128(u128),
+  I64(i64),
+  Text(Text),
+}
+
+/// Text held inline, so an answer needs no allocator.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Text {
+  bytes: [u8; TEXT_BYTES],
+  len: u8,
+}
+
+impl Text {
+  /// `CapacityExceeded` past `TEXT_BYTES`.
+  pub fn new(text: &str) -> Result<Self, Error> {
+    Error::check_capacity("answer bytes", TEXT_BYTES, text.len())?;
+
+    let mut bytes = [0; TEXT_BYTES];
+    bytes[..text.len()].copy_from_slice(text.as_bytes());
+    Ok(Text { bytes, len: text.len() as u8 })
+  }
+
+  pub fn as_str(&self) -> &str {
+    // only ever copied whole from a `&str`
+    core::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or("")
+  }
+}
+
+impl fmt::Debug for Text {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(self.as_str(), f)
+  }
+}
+
+impl fmt::Display for Answer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Answer::U64(n) => write!(f, "{n}"),
+      Answer::U128(n) => write!(f, "{n}"),
+      Answer::I64(n) => write!(f, "{n}"),
+      Answer::Text(text) => f.write_str(text.as_str()),
+    }
+  }
+}
+
+impl From<u64> for Answer {
+  fn from(n: u64) -> Self {
+    Answer::U64(n)
+  }
+}
+
+/// Counts are widened to `u64`, which holds any `usize` this repo targets.
+impl From<usize> for Answer {
+  fn from(n: usize) -> Self {
+    Answer::U64(n as u64)
+  }
+}
+
+impl From<u128> for Answer {
+  fn from(n: u128) -> Self {
+    Answer::U128(n)
+  }
+}
+
+impl From<i64> for Answer {
+  fn from(n: i64) -> Self {
+    Answer::I64(n)
+  }
+}
+
+impl From<Text> for Answer {
+  fn from(text: Text) -> Self {
+    Answer::Text(text)
+  }
+}
+
+/// For callers, such as the C ABI, that hand answers on as a `u64`; gives
+/// back a number out of its range, or text.
+impl TryFrom<Answer> for u64 {
+  type Error = Answer;
+
+  fn try_from(answer: Answer) -> Result<u64, Answer> {
+    match answer {
+      Answer::U64(n) => Ok(n),
+      Answer::U128(n) => u64::try_from(n).map_err(|_| answer),
+      Answer::I64(n) => u64::try_from(n).map_err(|_| answer),
+      Answer::Text(_) => Err(answer),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  extern crate std;
+  use std::string::ToString;
+
+  #[test]
+  fn test_display() {
+    assert_eq!(Answer::from(42u64).to_string(), "42");
+    assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    assert_eq!(Answer::from(-7i64).to_string(), "-7");
+
+    let text = Text::new("EHZRUBZJ").unwrap();
+    assert_eq!(Answer::from(text).to_string(), "EHZRUBZJ");
+    assert!(matches!(
+      Text::new(&"x".repeat(TEXT_BYTES + 1)),
+      Err(Error::CapacityExceeded { limit: TEXT_BYTES, .. })
+    ));
+  }
+
+  #[test]
+  fn test_to_u64() {
+    assert_eq!(u64::try_from(Answer::from(7usize)), Ok(7));
+    assert_eq!(u64::try_from(Answer::from(7u128)), Ok(7));
+    assert!(u64::try_from(Answer::from(1u128 << 64)).is_err());
+    assert!(u64::try_from(Answer::from(-1i64)).is_err());
+    assert!(u64::try_from(Answer::Text(Text::new("1").unwrap())).is_err());
+  }
+}
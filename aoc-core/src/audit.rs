@@ -0,0 +1,106 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::fmt;
+use std::alloc::System;
+
+// --------------------------
+// Data Model
+// --------------------------
+
+/// Heap traffic seen on one thread while `measure` ran.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+  /// Calls to `alloc`, `alloc_zeroed` and `realloc`.
+  pub count: usize,
+  /// Bytes those calls asked for.
+  pub bytes: usize,
+}
+
+impl fmt::Display for Allocations {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} allocations, {} bytes", self.count, self.bytes)
+  }
+}
+
+std::thread_local! {
+  // const-initialised, so reading it never allocates
+  static SEEN: Cell<Allocations> = const {
+    Cell::new(Allocations { count: 0, bytes: 0 })
+  };
+}
+
+// --------------------------
+// Allocator
+// --------------------------
+
+/// The system allocator, counting every request per thread.
+///
+/// Install it in a test binary with `#[global_allocator]`; counting per
+/// thread keeps the test harness and parallel tests out of the numbers.
+pub struct CountingAlloc;
+
+fn record(bytes: usize) {
+  // `try_with` fails only while the thread is being torn down
+  let _ = SEEN.try_with(|seen| {
+    let Allocations { count, bytes: total } = seen.get();
+    seen.set(Allocations { count: count + 1, bytes: total + bytes });
+  });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    record(layout.size());
+    unsafe { System.alloc(layout) }
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    record(layout.size());
+    unsafe { System.alloc_zeroed(layout) }
+  }
+
+  unsafe fn realloc(
+    &self,
+    ptr: *mut u8,
+    layout: Layout,
+    new: usize,
+  ) -> *mut u8 {
+    record(new);
+    unsafe { System.realloc(ptr, layout, new) }
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) }
+  }
+}
+
+/// Runs `f` and returns what it allocated on this thread.
+///
+/// Only meaningful when `CountingAlloc` is the global allocator.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Allocations) {
+  let before = SEEN.with(Cell::get);
+  let out = f();
+  let after = SEEN.with(Cell::get);
+
+  let seen = Allocations {
+    count: after.count - before.count,
+    bytes: after.bytes - before.bytes,
+  };
+  (out, seen)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[global_allocator]
+  static ALLOC: CountingAlloc = CountingAlloc;
+
+  #[test]
+  fn test_measure() {
+    let (buf, seen) = measure(|| std::vec![0u8; 64]);
+    assert_eq!(seen, Allocations { count: 1, bytes: 64 });
+
+    let ((), seen) = measure(|| drop(buf));
+    assert_eq!(seen, Allocations::default());
+  }
+}
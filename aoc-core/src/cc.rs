@@ -0,0 +1,76 @@
+//! Runs a C test program against a day's C ABI, inside `cargo test`.
+//!
+//! The day is built as a staticlib with its `ffi` and `std` features into
+//! `target/ffi`, so the outer test build is left alone. The program is then
+//! compiled with the system `cc` against the generated headers and run.
+
+use std::format;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::string::String;
+use std::vec::Vec;
+
+// what a Rust staticlib built with std links against
+#[cfg(target_os = "macos")]
+const NATIVE_LIBS: &[&str] = &["-lSystem", "-lc", "-lm"];
+#[cfg(not(target_os = "macos"))]
+const NATIVE_LIBS: &[&str] = &["-lpthread", "-ldl", "-lm", "-lc"];
+
+/// Builds `package`, whose manifest is in `manifest_dir`, compiles the C
+/// file `program` from that directory against it and runs it with `args`.
+/// Panics with the failing step's output.
+pub fn run(manifest_dir: &str, package: &str, program: &str, args: &[&str]) {
+  let day = Path::new(manifest_dir);
+  let core = Path::new(env!("CARGO_MANIFEST_DIR"));
+  let root = core.parent().expect("aoc-core lives inside the workspace");
+  let target = root.join("target/ffi");
+  let lib = package.replace('-', "_");
+
+  let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+  let mut build = Command::new(cargo);
+  build
+    .args([
+      "rustc",
+      "-q",
+      "-p",
+      package,
+      "--lib",
+      "--features",
+      "ffi,std",
+    ])
+    .args(["--crate-type", "staticlib", "--target-dir"])
+    .arg(&target)
+    .current_dir(root);
+  check("building the staticlib", build.output());
+
+  let exe = target.join(format!("{lib}-c-test"));
+  let mut compile = Command::new("cc");
+  compile
+    .args(["-std=c11", "-Wall", "-Wextra", "-Werror"])
+    .arg("-I")
+    .arg(day.join("include"))
+    .arg("-I")
+    .arg(core.join("include"))
+    .arg("-I")
+    .arg(core.join("tests/c"))
+    .arg(day.join(program))
+    .arg(target.join(format!("debug/lib{lib}.a")))
+    .args(NATIVE_LIBS)
+    .arg("-o")
+    .arg(&exe);
+  check("compiling the C test", compile.output());
+
+  let mut test = Command::new(&exe);
+  test.args(args).current_dir(day);
+  check("running the C test", test.output());
+}
+
+fn check(step: &str, output: std::io::Result<Output>) {
+  let output = output.unwrap_or_else(|e| panic!("{step}: {e}"));
+  if !output.status.success() {
+    let text: Vec<String> = [&output.stdout, &output.stderr]
+      .map(|s| String::from_utf8_lossy(s).into_owned())
+      .into();
+    panic!("{step} failed with {}:\n{}", output.status, text.join(""));
+  }
+}
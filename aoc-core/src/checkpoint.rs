@@ -0,0 +1,261 @@
+//! Progress saved to a caller's byte buffer, to survive a power loss.
+//!
+//! A state writes its fields with a [`Writer`] and reads them back with a
+//! [`Reader`]; the provided `save` and `restore` frame them as
+//!
+//! ```text
+//! "AOC" 1 | tag: 4 bytes | fields | FNV-1a of all before: u64 LE
+//! ```
+//!
+//! so a checkpoint torn by the power going out mid-write, or saved from some
+//! other state, is refused rather than resumed. Integers are little-endian.
+
+use crate::hash::fnv1a;
+use crate::{Error, Part};
+
+const MAGIC: [u8; 4] = *b"AOC\x01";
+
+/// Bytes `save` adds around a state's fields.
+pub const FRAME: usize = MAGIC.len() + 4 + 8;
+
+/// State that can be written to bytes and picked up again from them.
+pub trait Checkpoint: Sized {
+  /// What the state is restored against: `()` if it holds everything
+  /// itself, or the problem it reads from.
+  type Context;
+
+  /// Tells checkpoints of different states apart.
+  const TAG: [u8; 4];
+
+  /// Most bytes `write` writes.
+  const FIELDS: usize;
+
+  /// A buffer this long holds any checkpoint of the state.
+  const BYTES: usize = FRAME + Self::FIELDS;
+
+  fn write(&self, w: &mut Writer<'_>) -> Result<(), Error>;
+
+  fn read(context: Self::Context, r: &mut Reader<'_>) -> Result<Self, Error>;
+
+  /// Saves the state to the front of `buf`, returning the bytes used;
+  /// `CapacityExceeded` if `buf` is too short.
+  fn save(&self, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut w = Writer::new(buf);
+    w.bytes(&MAGIC)?;
+    w.bytes(&Self::TAG)?;
+    self.write(&mut w)?;
+
+    let sum = fnv1a(w.written());
+    w.u64(sum)?;
+    Ok(w.len)
+  }
+
+  /// The state `save` left at the front of `buf`.
+  fn restore(context: Self::Context, buf: &[u8]) -> Result<Self, Error> {
+    let mut r = Reader::new(buf);
+    if r.bytes(MAGIC.len())? != MAGIC {
+      return Err(Error::Checkpoint("not a checkpoint"));
+    }
+    if r.bytes(4)? != Self::TAG {
+      return Err(Error::Checkpoint("saved from another state"));
+    }
+    let state = Self::read(context, &mut r)?;
+
+    let sum = fnv1a(r.read());
+    if r.u64()? != sum {
+      return Err(Error::Checkpoint("checksum mismatch"));
+    }
+    Ok(state)
+  }
+}
+
+/// Appends fields to a byte buffer.
+pub struct Writer<'a> {
+  buf: &'a mut [u8],
+  len: usize,
+}
+
+impl<'a> Writer<'a> {
+  pub fn new(buf: &'a mut [u8]) -> Self {
+    Writer { buf, len: 0 }
+  }
+
+  /// The bytes written so far.
+  pub fn written(&self) -> &[u8] {
+    &self.buf[..self.len]
+  }
+
+  pub fn bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+    let end = self.len + bytes.len();
+    Error::check_capacity("checkpoint bytes", self.buf.len(), end)?;
+
+    self.buf[self.len..end].copy_from_slice(bytes);
+    self.len = end;
+    Ok(())
+  }
+
+  pub fn u8(&mut self, value: u8) -> Result<(), Error> {
+    self.bytes(&[value])
+  }
+
+  pub fn bool(&mut self, value: bool) -> Result<(), Error> {
+    self.u8(value.into())
+  }
+
+  pub fn u32(&mut self, value: u32) -> Result<(), Error> {
+    self.bytes(&value.to_le_bytes())
+  }
+
+  pub fn u64(&mut self, value: u64) -> Result<(), Error> {
+    self.bytes(&value.to_le_bytes())
+  }
+
+  pub fn i64(&mut self, value: i64) -> Result<(), Error> {
+    self.bytes(&value.to_le_bytes())
+  }
+
+  /// Written as a `u64`, so checkpoints move between targets.
+  pub fn usize(&mut self, value: usize) -> Result<(), Error> {
+    self.u64(value as u64)
+  }
+
+  pub fn part(&mut self, part: Part) -> Result<(), Error> {
+    self.u8(match part {
+      Part::One => 1,
+      Part::Two => 2,
+    })
+  }
+}
+
+/// Takes fields off the front of a byte buffer.
+pub struct Reader<'a> {
+  buf: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  pub fn new(buf: &'a [u8]) -> Self {
+    Reader { buf, pos: 0 }
+  }
+
+  /// The bytes read so far.
+  pub fn read(&self) -> &'a [u8] {
+    &self.buf[..self.pos]
+  }
+
+  pub fn bytes(&mut self, n: usize) -> Result<&'a [u8], Error> {
+    let end = self.pos.saturating_add(n);
+    let bytes = self
+      .buf
+      .get(self.pos..end)
+      .ok_or(Error::Checkpoint("truncated"))?;
+    self.pos = end;
+    Ok(bytes)
+  }
+
+  fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+    let mut out = [0; N];
+    out.copy_from_slice(self.bytes(N)?);
+    Ok(out)
+  }
+
+  pub fn u8(&mut self) -> Result<u8, Error> {
+    Ok(self.array::<1>()?[0])
+  }
+
+  pub fn bool(&mut self) -> Result<bool, Error> {
+    match self.u8()? {
+      0 => Ok(false),
+      1 => Ok(true),
+      _ => Err(Error::Checkpoint("invalid flag")),
+    }
+  }
+
+  pub fn u32(&mut self) -> Result<u32, Error> {
+    Ok(u32::from_le_bytes(self.array()?))
+  }
+
+  pub fn u64(&mut self) -> Result<u64, Error> {
+    Ok(u64::from_le_bytes(self.array()?))
+  }
+
+  pub fn i64(&mut self) -> Result<i64, Error> {
+    Ok(i64::from_le_bytes(self.array()?))
+  }
+
+  pub fn usize(&mut self) -> Result<usize, Error> {
+    usize::try_from(self.u64()?).map_err(|_| Error::Checkpoint("too large"))
+  }
+
+  pub fn part(&mut self) -> Result<Part, Error> {
+    match self.u8()? {
+      1 => Ok(Part::One),
+      2 => Ok(Part::Two),
+      _ => Err(Error::Checkpoint("invalid part")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, PartialEq)]
+  struct Tally {
+    seen: usize,
+    total: u64,
+  }
+
+  impl Checkpoint for Tally {
+    type Context = ();
+    const TAG: [u8; 4] = *b"test";
+    const FIELDS: usize = 16;
+
+    fn write(&self, w: &mut Writer<'_>) -> Result<(), Error> {
+      w.usize(self.seen)?;
+      w.u64(self.total)
+    }
+
+    fn read(_: (), r: &mut Reader<'_>) -> Result<Self, Error> {
+      Ok(Tally { seen: r.usize()?, total: r.u64()? })
+    }
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let tally = Tally { seen: 3, total: 1 << 40 };
+    let mut buf = [0u8; Tally::BYTES];
+
+    assert_eq!(tally.save(&mut buf), Ok(Tally::BYTES));
+    assert_eq!(Tally::restore((), &buf), Ok(tally));
+
+    let mut short = [0u8; Tally::BYTES - 1];
+    assert!(matches!(
+      Tally { seen: 0, total: 0 }.save(&mut short),
+      Err(Error::CapacityExceeded { needed: Tally::BYTES, .. })
+    ));
+  }
+
+  #[test]
+  fn test_refuses_damage() {
+    let mut buf = [0u8; Tally::BYTES];
+    Tally { seen: 3, total: 7 }.save(&mut buf).unwrap();
+
+    // every single flipped bit is caught
+    for i in 0..buf.len() * 8 {
+      let mut torn = buf;
+      torn[i / 8] ^= 1 << (i % 8);
+      assert!(
+        matches!(Tally::restore((), &torn), Err(Error::Checkpoint(_))),
+        "bit {i}"
+      );
+    }
+
+    let err = Tally::restore((), &buf[..10]);
+    assert_eq!(err, Err(Error::Checkpoint("truncated")));
+
+    buf[4..8].copy_from_slice(b"tset");
+    let err = Tally::restore((), &buf);
+    assert_eq!(err, Err(Error::Checkpoint("saved from another state")));
+  }
+}
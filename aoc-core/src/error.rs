@@ -0,0 +1,52 @@
+use core::fmt;
+
+use crate::ParseError;
+
+/// Everything a day can fail with: bad text, text too big for the
+/// fixed-capacity buffers the day was built with, or saved progress that
+/// cannot be resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+  Parse(ParseError),
+  /// `needed` items of `what` were found, but only `limit` fit.
+  CapacityExceeded {
+    what: &'static str,
+    limit: usize,
+    needed: usize,
+  },
+  /// A saved checkpoint is damaged, or was saved from another state or
+  /// input.
+  Checkpoint(&'static str),
+}
+
+impl Error {
+  /// Fails with `CapacityExceeded` when `needed` is over `limit`.
+  pub fn check_capacity(
+    what: &'static str,
+    limit: usize,
+    needed: usize,
+  ) -> Result<(), Error> {
+    if needed > limit {
+      return Err(Error::CapacityExceeded { what, limit, needed });
+    }
+    Ok(())
+  }
+}
+
+impl From<ParseError> for Error {
+  fn from(e: ParseError) -> Self {
+    Error::Parse(e)
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Parse(e) => write!(f, "{e}"),
+      Error::CapacityExceeded { what, limit, needed } => {
+        write!(f, "{needed} {what} found, but only {limit} fit")
+      }
+      Error::Checkpoint(why) => write!(f, "cannot resume checkpoint: {why}"),
+    }
+  }
+}
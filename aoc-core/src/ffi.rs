@@ -0,0 +1,174 @@
+//! The C ABI shared by every day's `ffi` module.
+//!
+//! Each exported function takes the puzzle text as `(const uint8_t *,
+//! size_t)`, borrows any working memory from the caller, writes its answer
+//! through a `uint64_t *` and returns a [`Status`]. Nothing is allocated and
+//! nothing is kept between calls. `cargo xtask header` turns these
+//! signatures into the headers under each crate's `include/`.
+
+use core::slice;
+
+use crate::{Error, ParseError, Part, Solver};
+
+/// What a call did; anything but `Ok` leaves the answer unwritten.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+  /// The answer was written.
+  Ok = 0,
+  /// A pointer that must not be null was.
+  NullPointer = 1,
+  /// The input is not UTF-8.
+  InvalidUtf8 = 2,
+  /// The input is not a puzzle input for this day.
+  Parse = 3,
+  /// The input outgrows the day's fixed capacities.
+  CapacityExceeded = 4,
+  /// A scratch buffer is shorter than the day's sizing function asked for.
+  ScratchTooSmall = 5,
+  /// A saved checkpoint cannot be resumed.
+  InvalidCheckpoint = 6,
+}
+
+impl From<ParseError> for Status {
+  fn from(_: ParseError) -> Self {
+    Status::Parse
+  }
+}
+
+impl From<Error> for Status {
+  fn from(e: Error) -> Self {
+    match e {
+      Error::Parse(_) => Status::Parse,
+      Error::CapacityExceeded { .. } => Status::CapacityExceeded,
+      Error::Checkpoint(_) => Status::InvalidCheckpoint,
+    }
+  }
+}
+
+/// The `len` bytes at `ptr` as puzzle text.
+///
+/// # Safety
+/// Unless `len` is zero, `ptr` must point to `len` readable bytes that
+/// outlive `'a`.
+pub unsafe fn input<'a>(ptr: *const u8, len: usize) -> Result<&'a str, Status> {
+  if len == 0 {
+    return Ok("");
+  }
+  if ptr.is_null() {
+    return Err(Status::NullPointer);
+  }
+
+  let bytes = unsafe { slice::from_raw_parts(ptr, len) };
+  core::str::from_utf8(bytes).map_err(|_| Status::InvalidUtf8)
+}
+
+/// The caller's `len`-value buffer at `ptr`, every value set to `fill` first
+/// so its contents on entry never matter.
+///
+/// # Safety
+/// Unless `len` is zero, `ptr` must point to `len` writable, aligned values
+/// that nothing else touches during `'a`.
+pub unsafe fn buffer<'a, T: Copy>(
+  ptr: *mut T,
+  len: usize,
+  fill: T,
+) -> Result<&'a mut [T], Status> {
+  if len == 0 {
+    return Ok(&mut []);
+  }
+  if ptr.is_null() {
+    return Err(Status::NullPointer);
+  }
+
+  for i in 0..len {
+    unsafe { ptr.add(i).write(fill) };
+  }
+  Ok(unsafe { slice::from_raw_parts_mut(ptr, len) })
+}
+
+/// Fails with `ScratchTooSmall` unless the caller gave `needed` values.
+pub fn check_scratch(given: usize, needed: usize) -> Result<(), Status> {
+  if given < needed {
+    return Err(Status::ScratchTooSmall);
+  }
+  Ok(())
+}
+
+/// Writes `value` through `out`, returning how the call went.
+///
+/// # Safety
+/// `out` must be null or point to a writable, aligned `T`.
+pub unsafe fn write<T>(out: *mut T, value: Result<T, Status>) -> Status {
+  if out.is_null() {
+    return Status::NullPointer;
+  }
+
+  match value {
+    Ok(value) => {
+      unsafe { out.write(value) };
+      Status::Ok
+    }
+    Err(status) => status,
+  }
+}
+
+/// Solves `part` of the text at `ptr` with a day that needs no scratch.
+///
+/// # Safety
+/// As for [`input`] and [`write`].
+pub unsafe fn solve<S>(
+  part: Part,
+  ptr: *const u8,
+  len: usize,
+  out: *mut u64,
+) -> Status
+where
+  S: for<'a> Solver<Scratch<'a> = ()>,
+  S::Error: Into<Status>,
+{
+  let answer = (|| {
+    let text = unsafe { input(ptr, len)? };
+    let problem = S::parse(text).map_err(Into::into)?;
+    let answer = S::solve(part, &problem, ()).map_err(Into::into)?;
+    u64::try_from(answer).map_err(|_| Status::CapacityExceeded)
+  })();
+
+  unsafe { write(out, answer) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_input() {
+    let text = b"L68\n";
+    assert_eq!(unsafe { input(text.as_ptr(), 4) }, Ok("L68\n"));
+    assert_eq!(unsafe { input(core::ptr::null(), 0) }, Ok(""));
+    assert_eq!(
+      unsafe { input(core::ptr::null(), 1) },
+      Err(Status::NullPointer)
+    );
+    assert_eq!(
+      unsafe { input([0xff].as_ptr(), 1) },
+      Err(Status::InvalidUtf8)
+    );
+  }
+
+  #[test]
+  fn test_write() {
+    let mut out = 0u64;
+    assert_eq!(unsafe { write(&mut out, Ok(42)) }, Status::Ok);
+    assert_eq!(out, 42);
+    assert_eq!(
+      unsafe { write(&mut out, Err(Status::Parse)) },
+      Status::Parse
+    );
+    assert_eq!(out, 42);
+    assert_eq!(
+      unsafe { write(core::ptr::null_mut(), Ok(1u64)) },
+      Status::NullPointer
+    );
+  }
+}
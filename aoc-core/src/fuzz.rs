@@ -0,0 +1,389 @@
+//! A small fuzzer that runs on stable Rust inside `cargo test`.
+//!
+//! Inputs come from a corpus directory: every file is replayed first, then
+//! mutated by an `arbitrary::Unstructured` over random bytes. A panicking
+//! input is minimized and saved back into the corpus as `crash-<hash>`, so
+//! it is replayed by every later run until fixed.
+
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::format;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::{env, fs};
+
+use arbitrary::{Result, Unstructured};
+
+use crate::hash::fnv1a;
+
+// --------------------------
+// Configuration
+// --------------------------
+
+/// Mutated inputs per run, unless `AOC_FUZZ_RUNS` says otherwise.
+pub const RUNS: usize = 2_000;
+
+/// Inputs are cut to this many bytes, which keeps the solvers' work bounded.
+pub const MAX_LEN: usize = 4 << 10;
+
+// bytes of randomness behind each mutated input
+const ENTROPY: usize = 256;
+
+// tokens the puzzle formats are built from
+const BYTES: &[u8] = b"0123456789 \n\t\r,-:.#@^S()[]{}xLR";
+const NUMBERS: &[&str] = &[
+  "-9223372036854775808",
+  "-2147483648",
+  "-32768",
+  "-1",
+  "0",
+  "1",
+  "9",
+  "10",
+  "99",
+  "100",
+  "255",
+  "32767",
+  "32768",
+  "65535",
+  "65536",
+  "2147483647",
+  "2147483648",
+  "4294967296",
+  "9223372036854775807",
+  "18446744073709551615",
+  "18446744073709551616",
+  "99999999999999999999999",
+];
+
+// --------------------------
+// Harness
+// --------------------------
+
+/// Replays every file in `corpus`, then feeds `target` mutations of them,
+/// failing on the first input that panics.
+///
+/// `AOC_FUZZ_RUNS` sets how many mutations to try and `AOC_FUZZ_SEED` where
+/// the random stream starts; both default to values that make a plain
+/// `cargo test` repeatable.
+pub fn fuzz(corpus: impl AsRef<Path>, target: impl Fn(&str)) {
+  let corpus = corpus.as_ref();
+  let seeds = read_corpus(corpus);
+  assert!(!seeds.is_empty(), "no seed inputs in {}", corpus.display());
+
+  // the hook can't be swapped back while unwinding, so report afterwards
+  let quiet = QuietPanics::install();
+  let found = run(corpus, &seeds, &target);
+  drop(quiet);
+
+  if let Err(failure) = found {
+    panic!("{failure}");
+  }
+}
+
+fn run(
+  corpus: &Path,
+  seeds: &[(PathBuf, String)],
+  target: &impl Fn(&str),
+) -> core::result::Result<(), String> {
+  let runs = env_or("AOC_FUZZ_RUNS", RUNS as u64) as usize;
+  let seed = env_or("AOC_FUZZ_SEED", 0x5eed);
+
+  for (path, input) in seeds {
+    if let Some(message) = panics(target, input) {
+      return Err(format!("{} panics: {message}", path.display()));
+    }
+  }
+
+  let inputs: Vec<&[u8]> = seeds.iter().map(|(_, s)| s.as_bytes()).collect();
+  let mut rng = fastrand::Rng::with_seed(seed);
+  let mut entropy = [0u8; ENTROPY];
+
+  for run in 0..runs {
+    rng.fill(&mut entropy);
+    let input = mutate(&mut Unstructured::new(&entropy), &inputs);
+
+    if panics(target, &input).is_some() {
+      let small = minimize(&input, |i| panics(target, i).is_some());
+      let message = panics(target, &small).unwrap_or_default();
+      let path = save(corpus, &small);
+      return Err(format!(
+        "run {run} (seed {seed:#x}) panics: {message}\n\
+         minimized to {small:?}, saved as {}",
+        path.display()
+      ));
+    }
+  }
+
+  Ok(())
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+  let Ok(value) = env::var(name) else {
+    return default;
+  };
+  let parsed = match value.strip_prefix("0x") {
+    Some(hex) => u64::from_str_radix(hex, 16),
+    None => value.parse(),
+  };
+  parsed.unwrap_or_else(|_| panic!("{name}={value:?} is not a number"))
+}
+
+/// Every regular file in `dir`, in name order, as lossy UTF-8.
+fn read_corpus(dir: &Path) -> Vec<(PathBuf, String)> {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return Vec::new();
+  };
+  let mut paths: Vec<PathBuf> = entries
+    .filter_map(|e| e.ok().map(|e| e.path()))
+    .filter(|p| p.is_file())
+    .collect();
+  paths.sort();
+
+  paths
+    .into_iter()
+    .filter_map(|p| {
+      let bytes = fs::read(&p).ok()?;
+      Some((p, String::from_utf8_lossy(&bytes).into_owned()))
+    })
+    .collect()
+}
+
+/// Writes `input` into `dir` under a name derived from its bytes.
+fn save(dir: &Path, input: &str) -> PathBuf {
+  let path = dir.join(format!("crash-{:016x}", fnv1a(input.as_bytes())));
+  let _ = fs::create_dir_all(dir);
+  if let Err(e) = fs::write(&path, input) {
+    std::eprintln!("could not save {}: {e}", path.display());
+  }
+  path
+}
+
+// --------------------------
+// Panics
+// --------------------------
+
+std::thread_local! {
+  static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+type Hook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send>;
+
+/// Records panics on this thread instead of printing them, until dropped.
+/// Panics on other threads still reach the previous hook.
+struct QuietPanics {
+  previous: Option<std::sync::Arc<Hook>>,
+}
+
+impl QuietPanics {
+  fn install() -> Self {
+    let previous = std::sync::Arc::new(panic::take_hook());
+    let chained = previous.clone();
+    let this_thread = std::thread::current().id();
+
+    panic::set_hook(Box::new(move |info| {
+      if std::thread::current().id() != this_thread {
+        return chained(info);
+      }
+      let at = info.location().map(ToString::to_string).unwrap_or_default();
+      let message = match info.payload().downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => info
+          .payload()
+          .downcast_ref::<String>()
+          .cloned()
+          .unwrap_or_default(),
+      };
+      LAST_PANIC.with(|p| *p.borrow_mut() = Some(format!("{message} at {at}")));
+    }));
+
+    QuietPanics { previous: Some(previous) }
+  }
+}
+
+impl Drop for QuietPanics {
+  fn drop(&mut self) {
+    let _ = panic::take_hook();
+    if let Some(previous) = self.previous.take() {
+      panic::set_hook(Box::new(move |info| previous(info)));
+    }
+  }
+}
+
+/// The panic message and location if `target` panics on `input`.
+fn panics(target: &impl Fn(&str), input: &str) -> Option<String> {
+  let result = panic::catch_unwind(AssertUnwindSafe(|| target(input)));
+  result.is_err().then(|| {
+    LAST_PANIC
+      .with(|p| p.borrow_mut().take())
+      .unwrap_or_default()
+  })
+}
+
+// --------------------------
+// Mutation
+// --------------------------
+
+/// Either fresh arbitrary text, or a corpus input put through a few edits.
+fn mutate(u: &mut Unstructured, corpus: &[&[u8]]) -> String {
+  let mut bytes = if u.ratio(1, 8).unwrap_or(false) {
+    u.arbitrary::<&str>().unwrap_or("").as_bytes().to_vec()
+  } else {
+    u.choose(corpus).map(|c| c.to_vec()).unwrap_or_default()
+  };
+
+  let edits = u.int_in_range(1..=8).unwrap_or(1);
+  for _ in 0..edits {
+    if edit(u, &mut bytes, corpus).is_err() {
+      break;
+    }
+  }
+
+  bytes.truncate(MAX_LEN);
+  String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn edit(
+  u: &mut Unstructured,
+  bytes: &mut Vec<u8>,
+  corpus: &[&[u8]],
+) -> Result<()> {
+  let len = bytes.len();
+  let at = u.int_in_range(0..=len)?;
+
+  match u.int_in_range(0..=6)? {
+    // drop a run of bytes
+    0 if len > 0 => {
+      let end = u.int_in_range(at..=len.min(at + 16))?;
+      bytes.drain(at..end);
+    }
+    // overwrite one byte with a token byte
+    1 if at < len => bytes[at] = *u.choose(BYTES)?,
+    // insert a token byte
+    2 => bytes.insert(at, *u.choose(BYTES)?),
+    // swap the next number for one near some type's limit
+    3 => {
+      let number = u.choose(NUMBERS)?.as_bytes();
+      let digit = |b: &u8| b.is_ascii_digit() || *b == b'-';
+      let start = bytes[at..].iter().position(digit).map_or(len, |i| at + i);
+      let end = bytes[start..]
+        .iter()
+        .position(|b| !digit(b))
+        .map_or(len, |i| start + i);
+      bytes.splice(start..end, number.iter().copied());
+    }
+    // repeat a run of bytes
+    4 if len > 0 => {
+      let start = u.int_in_range(0..=len - 1)?;
+      let end = u.int_in_range(start..=len.min(start + 64))?;
+      let run = bytes[start..end].to_vec();
+      let times = u.int_in_range(1..=4)?;
+      for _ in 0..times {
+        bytes.splice(at..at, run.iter().copied());
+      }
+    }
+    // splice in part of another corpus input
+    5 => {
+      let other = u.choose(corpus)?;
+      if !other.is_empty() {
+        let start = u.int_in_range(0..=other.len() - 1)?;
+        let end = u.int_in_range(start..=other.len())?;
+        bytes.splice(at..at, other[start..end].iter().copied());
+      }
+    }
+    // insert raw arbitrary bytes
+    _ => {
+      let n = u.int_in_range(1..=8)?;
+      let raw = u.bytes(n)?;
+      bytes.splice(at..at, raw.iter().copied());
+    }
+  }
+
+  Ok(())
+}
+
+// --------------------------
+// Minimization
+// --------------------------
+
+/// Shrinks `input` while `fails` holds: first by dropping ever smaller runs
+/// of characters, then by turning single characters into `0` or a space.
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+  let mut chars: Vec<char> = input.chars().collect();
+  let text = |chars: &[char]| chars.iter().collect::<String>();
+
+  let mut chunk = chars.len().div_ceil(2).max(1);
+  loop {
+    let mut at = 0;
+    while at < chars.len() {
+      let end = (at + chunk).min(chars.len());
+      let mut shorter = chars[..at].to_vec();
+      shorter.extend_from_slice(&chars[end..]);
+      if fails(&text(&shorter)) {
+        chars = shorter;
+      } else {
+        at += chunk;
+      }
+    }
+    if chunk == 1 {
+      break;
+    }
+    chunk /= 2;
+  }
+
+  for i in 0..chars.len() {
+    for simpler in ['0', ' '] {
+      if chars[i] == simpler || chars[i] == '\n' {
+        continue;
+      }
+      let before = core::mem::replace(&mut chars[i], simpler);
+      if fails(&text(&chars)) {
+        break;
+      }
+      chars[i] = before;
+    }
+  }
+
+  text(&chars)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_minimize() {
+    let fails = |s: &str| s.contains("9,x");
+    assert_eq!(minimize("12\n49,x3\n5", fails), "9,x");
+
+    let fails = |s: &str| s.lines().count() >= 3;
+    assert_eq!(minimize("ab\ncd\nef\ngh", fails), "\n\n0");
+  }
+
+  #[test]
+  fn test_fuzz_finds_and_saves_crash() {
+    let dir = env::temp_dir().join(format!("aoc-fuzz-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("seed"), "1,2\n3,4\n").unwrap();
+
+    let target = |s: &str| {
+      if s.contains("99") {
+        panic!("too big");
+      }
+    };
+    let found = panic::catch_unwind(AssertUnwindSafe(|| fuzz(&dir, target)));
+    assert!(found.is_err());
+
+    let saved = read_corpus(&dir);
+    assert_eq!(saved.len(), 2);
+    assert!(saved[0].0.ends_with(format!("crash-{:016x}", fnv1a(b"99"))));
+    assert_eq!(saved[0].1, "99");
+
+    // the saved crash is now replayed first
+    let replayed = panic::catch_unwind(AssertUnwindSafe(|| fuzz(&dir, target)));
+    assert!(replayed.is_err());
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
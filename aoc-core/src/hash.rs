@@ -0,0 +1,31 @@
+/// A stable fingerprint of a puzzle input, for telling inputs apart without
+/// keeping them: 64-bit FNV-1a over the text, trailing whitespace ignored so
+/// a missing final newline hashes the same.
+pub fn input_hash(input: &str) -> u64 {
+  fnv1a(input.trim_end().as_bytes())
+}
+
+/// 64-bit FNV-1a over `bytes`.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+  const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+  const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+  bytes
+    .iter()
+    .fold(OFFSET, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_input_hash() {
+    // FNV-1a's published test vectors
+    assert_eq!(input_hash(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(input_hash("a"), 0xaf63_dc4c_8601_ec8c);
+
+    assert_eq!(input_hash("L68\nR48\n"), input_hash("L68\nR48"));
+    assert_ne!(input_hash("L68\nR48"), input_hash("L68\nR49"));
+  }
+}
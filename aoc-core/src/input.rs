@@ -0,0 +1,101 @@
+//! Puzzle input read at runtime by each day's binary and benches, so nothing
+//! has to exist at build time. The libraries still take a borrowed `&str`.
+//!
+//! The input comes from the first of: a path argument, the `AOC_INPUT`
+//! environment variable, or stdin when it is piped. A path of `-` also reads
+//! stdin.
+
+use std::fmt;
+use std::io::{IsTerminal, Read};
+use std::string::String;
+
+/// Names the input file when no path argument is given.
+pub const VAR: &str = "AOC_INPUT";
+
+/// Why no input could be read.
+#[derive(Debug)]
+pub enum InputError {
+  /// No path, no `AOC_INPUT`, and nothing piped on stdin.
+  Missing,
+  Read {
+    path: String,
+    error: std::io::Error,
+  },
+}
+
+impl fmt::Display for InputError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      InputError::Missing => write!(
+        f,
+        "no puzzle input: pass its path, set {VAR}, or pipe it on stdin"
+      ),
+      InputError::Read { path, error } => {
+        write!(f, "cannot read `{path}`: {error}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for InputError {}
+
+/// The input named by `path`, else by `AOC_INPUT`, else piped on stdin.
+pub fn load(path: Option<String>) -> Result<String, InputError> {
+  let path = path.or_else(|| std::env::var(VAR).ok());
+
+  match path.as_deref() {
+    Some("-") => stdin("-"),
+    Some(path) => std::fs::read_to_string(path)
+      .map_err(|error| InputError::Read { path: path.into(), error }),
+    None if std::io::stdin().is_terminal() => Err(InputError::Missing),
+    None => match stdin("stdin")? {
+      // a closed stdin, as under a harness, gave nothing to solve
+      text if text.is_empty() => Err(InputError::Missing),
+      text => Ok(text),
+    },
+  }
+}
+
+fn stdin(path: &str) -> Result<String, InputError> {
+  let mut text = String::new();
+  std::io::stdin()
+    .read_to_string(&mut text)
+    .map_err(|error| InputError::Read { path: path.into(), error })?;
+  Ok(text)
+}
+
+/// For a binary: the input its argument `n` names, or as `load` finds it;
+/// prints why there is none and exits with status 2 otherwise.
+pub fn from_arg(n: usize) -> String {
+  load(std::env::args().nth(n)).unwrap_or_else(|e| {
+    std::eprintln!("error: {e}");
+    std::process::exit(2)
+  })
+}
+
+/// For a bench, whose arguments belong to the bench harness: the input
+/// `AOC_INPUT` names or stdin holds. Panics with why there is none.
+pub fn for_bench() -> String {
+  load(None).unwrap_or_else(|e| panic!("{e}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::string::ToString;
+
+  use super::*;
+
+  #[test]
+  fn test_load() {
+    let path = std::env::temp_dir().join("aoc-core-input-test.txt");
+    std::fs::write(&path, "L68\n").unwrap();
+
+    let named = load(Some(path.display().to_string()));
+    assert_eq!(named.unwrap(), "L68\n");
+    std::fs::remove_file(&path).unwrap();
+
+    let missing = load(Some(path.display().to_string())).unwrap_err();
+    assert!(matches!(missing, InputError::Read { .. }), "{missing}");
+    assert!(missing.to_string().starts_with("cannot read `"));
+  }
+}
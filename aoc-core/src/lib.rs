@@ -0,0 +1,80 @@
+#![no_std]
+
+#[cfg(any(
+  feature = "audit",
+  feature = "cc",
+  feature = "fuzz",
+  feature = "input",
+  feature = "parallel",
+  feature = "trace"
+))]
+extern crate std;
+
+mod answer;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "cc")]
+pub mod cc;
+pub mod checkpoint;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+mod hash;
+#[cfg(feature = "input")]
+pub mod input;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod parse;
+pub mod resume;
+#[cfg(feature = "audit")]
+pub mod stack;
+pub mod stream;
+pub mod trace;
+pub mod work;
+pub mod workspace;
+
+pub use answer::{Answer, TEXT_BYTES, Text};
+pub use error::Error;
+pub use hash::input_hash;
+pub use parse::{ParseError, Reason};
+pub use workspace::Workspace;
+
+pub mod prelude {
+  pub use crate::{Answer, Error, ParseError, Part, Reason, Solver};
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+/// Which half of a day's puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+  One,
+  Two,
+}
+
+/// The surface every `day-*` crate exposes, so one harness can drive any day.
+///
+/// `Input` is what `parse` builds from the puzzle text. `Scratch` is working
+/// memory owned by the caller (`()` for days that need none), so the solver
+/// itself never allocates. Buffers only one part uses may be left empty when
+/// solving the other. Every day answers with an [`Answer`], whatever type its
+/// own `part1` and `part2` return.
+pub trait Solver {
+  const DAY: u8;
+
+  type Input<'a>;
+  type Scratch<'a>;
+  type Error;
+
+  fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error>;
+
+  fn solve<'a>(
+    part: Part,
+    input: &Self::Input<'a>,
+    scratch: Self::Scratch<'a>,
+  ) -> Result<Answer, Self::Error>;
+}
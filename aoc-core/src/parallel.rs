@@ -0,0 +1,109 @@
+//! Independent records split across scoped threads, for hosts with cores to
+//! spare.
+//!
+//! Each thread takes one contiguous run of the records, and the runs'
+//! results are added up in record order, so the answer is the same whatever
+//! the thread count or the order threads finish in.
+
+use core::iter::Sum;
+use std::thread;
+
+/// One thread per available core, the usual count to split across.
+pub fn threads() -> usize {
+  thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// `f` of every run `0..runs`, each on its own scoped thread, summed in run
+/// order.
+pub fn sum<R: Send + Sum>(runs: usize, f: impl Fn(usize) -> R + Sync) -> R {
+  let f = &f;
+  thread::scope(|s| {
+    let handles: std::vec::Vec<_> =
+      (0..runs).map(|run| s.spawn(move || f(run))).collect();
+
+    handles
+      .into_iter()
+      .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+      .sum()
+  })
+}
+
+/// `f` of `items` split into near-equal slices, one per thread.
+pub fn sum_slices<T: Sync, R: Send + Sum>(
+  items: &[T],
+  threads: usize,
+  f: impl Fn(&[T]) -> R + Sync,
+) -> R {
+  let runs = threads.min(items.len()).max(1);
+  sum(runs, |run| f(share(items, run, runs)))
+}
+
+/// `f` of `input` split into runs of whole lines, one per thread, each about
+/// as many bytes as the others.
+pub fn sum_lines<R: Send + Sum>(
+  input: &str,
+  threads: usize,
+  f: impl Fn(&str) -> R + Sync,
+) -> R {
+  let runs = threads.min(input.len()).max(1);
+  sum(runs, |run| f(lines(input, run, runs)))
+}
+
+/// Slice `run` of `items` cut into `runs` of near-equal length.
+pub fn share<T>(items: &[T], run: usize, runs: usize) -> &[T] {
+  let at = |run: usize| items.len() * run / runs;
+  &items[at(run)..at(run + 1)]
+}
+
+/// Run `run` of `input` cut into `runs` of whole lines, each cut moved on
+/// from an even byte split to the next line's start.
+pub fn lines(input: &str, run: usize, runs: usize) -> &str {
+  let at = |run: usize| {
+    let even = input.len() * run / runs;
+    if even == 0 {
+      return 0;
+    }
+    input.as_bytes()[even - 1..]
+      .iter()
+      .position(|&b| b == b'\n')
+      .map_or(input.len(), |i| even + i)
+  };
+  &input[at(run)..at(run + 1)]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_share() {
+    let items = [1, 2, 3, 4, 5, 6, 7];
+    let runs: std::vec::Vec<_> = (0..3).map(|r| share(&items, r, 3)).collect();
+    assert_eq!(runs, [&[1, 2][..], &[3, 4], &[5, 6, 7]]);
+
+    assert_eq!(sum_slices(&items, 3, |run| run.iter().sum::<u64>()), 28);
+  }
+
+  #[test]
+  fn test_lines() {
+    let input = "ab\ncdef\ng\n\nhij";
+    for runs in 1..=input.len() {
+      let cut: std::string::String =
+        (0..runs).map(|run| lines(input, run, runs)).collect();
+      assert_eq!(cut, input, "{runs} runs");
+
+      // every run that has any lines starts with one
+      for run in 0..runs {
+        let cut = lines(input, run, runs);
+        if cut.is_empty() {
+          continue;
+        }
+        let at = cut.as_ptr() as usize - input.as_ptr() as usize;
+        assert!(at == 0 || input.as_bytes()[at - 1] == b'\n', "{runs} runs");
+      }
+    }
+
+    let total = sum_lines(input, 4, |run| run.lines().count());
+    assert_eq!(total, input.lines().count());
+  }
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use core::str::FromStr;
+
+// --------------------------
+// Data Model
+// --------------------------
+
+/// A rejected puzzle input, pointing at the offending byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+  /// 1-based line number.
+  pub line: usize,
+  /// 1-based byte column within `line`.
+  pub column: usize,
+  pub reason: Reason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+  /// A number was expected but the field holds something else.
+  ExpectedNumber,
+  /// The number does not fit the type it is stored in.
+  OutOfRange,
+  /// A specific byte was expected here.
+  Expected(char),
+  /// A byte the format does not allow.
+  Unexpected(char),
+  /// The line or the input stopped early.
+  UnexpectedEnd,
+  /// A day-specific structural rule was broken.
+  Malformed(&'static str),
+}
+
+impl ParseError {
+  /// Locates `at`, which must be a sub-slice of `input`.
+  pub fn at(input: &str, at: &str, reason: Reason) -> Self {
+    let offset = (at.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    Self::at_offset(input, offset, reason)
+  }
+
+  /// Locates byte `offset` of `input`; offsets past the end point just after
+  /// the last byte.
+  pub fn at_offset(input: &str, offset: usize, reason: Reason) -> Self {
+    let before = &input.as_bytes()[..offset.min(input.len())];
+    let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let line_start = before
+      .iter()
+      .rposition(|&b| b == b'\n')
+      .map_or(0, |i| i + 1);
+
+    ParseError { line, column: before.len() - line_start + 1, reason }
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "line {}, column {}: {}",
+      self.line, self.column, self.reason
+    )
+  }
+}
+
+impl fmt::Display for Reason {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Reason::ExpectedNumber => write!(f, "expected a number"),
+      Reason::OutOfRange => write!(f, "number out of range"),
+      Reason::Expected(c) => write!(f, "expected `{c}`"),
+      Reason::Unexpected(c) => write!(f, "unexpected `{}`", c.escape_debug()),
+      Reason::UnexpectedEnd => write!(f, "unexpected end of input"),
+      Reason::Malformed(why) => write!(f, "{why}"),
+    }
+  }
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+/// Parses `field`, a sub-slice of `input`, as an integer.
+///
+/// On failure the error points at the first byte that is not part of a
+/// number, or at the field itself when it is all digits but out of range.
+/// A leading `+`, which `FromStr` takes, is not part of one: no puzzle
+/// writes it, and the days that re-read fields digit by digit stop at it.
+pub fn number<T: FromStr>(input: &str, field: &str) -> Result<T, ParseError> {
+  if !field.starts_with('+')
+    && let Ok(n) = field.parse::<T>()
+  {
+    return Ok(n);
+  }
+
+  let digits = field.strip_prefix('-').unwrap_or(field);
+  let bad = digits.char_indices().find(|(_, c)| !c.is_ascii_digit());
+
+  let (at, reason) = match bad {
+    Some((i, _)) => (&digits[i..], Reason::ExpectedNumber),
+    None if digits.is_empty() => (digits, Reason::ExpectedNumber),
+    None => (field, Reason::OutOfRange),
+  };
+
+  Err(ParseError::at(input, at, reason))
+}
+
+/// Splits `field` at the first `sep`, failing at the end of `field` if it
+/// has none.
+pub fn split_once<'a>(
+  input: &str,
+  field: &'a str,
+  sep: char,
+) -> Result<(&'a str, &'a str), ParseError> {
+  field.split_once(sep).ok_or_else(|| {
+    ParseError::at(input, &field[field.len()..], Reason::Expected(sep))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_locates_errors() {
+    let input = "1,2\n3,x\n";
+    let field = &input[6..7];
+
+    let err = number::<u32>(input, field).unwrap_err();
+    assert_eq!(
+      err,
+      ParseError { line: 2, column: 3, reason: Reason::ExpectedNumber }
+    );
+
+    let err = split_once(input, &input[4..7], ';').unwrap_err();
+    assert_eq!((err.line, err.column), (2, 4));
+
+    let input = "300";
+    let err = number::<u8>(input, input).unwrap_err();
+    assert_eq!(err.reason, Reason::OutOfRange);
+
+    let input = "+11";
+    let err = number::<u64>(input, input).unwrap_err();
+    assert_eq!((err.column, err.reason), (1, Reason::ExpectedNumber));
+  }
+}
@@ -0,0 +1,68 @@
+//! Solvers run a slice at a time, for superloops and RTOS tasks that cannot
+//! hand one call the CPU until the answer is found.
+//!
+//! A long solver keeps its loops' positions in a struct instead of on the
+//! call stack, so it can stop after any unit of work and carry on from there
+//! on the next call. Each day says what its unit is; every one is bounded by
+//! the day's fixed capacities.
+
+pub use core::task::Poll;
+
+/// A solver that does at most `budget` units of work per call.
+pub trait Resumable {
+  type Answer;
+
+  /// Works for up to `budget` units, at least one, then returns `Pending`,
+  /// or the answer once there is one. Later calls return it again.
+  fn step(&mut self, budget: u64) -> Poll<Self::Answer>;
+
+  /// Steps until the answer, `budget` units at a time.
+  fn run(mut self, budget: u64) -> Self::Answer
+  where
+    Self: Sized,
+  {
+    loop {
+      if let Poll::Ready(answer) = self.step(budget) {
+        return answer;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Sums `1..=n`, one term per unit.
+  struct Sum {
+    n: u64,
+    i: u64,
+    total: u64,
+  }
+
+  impl Resumable for Sum {
+    type Answer = u64;
+
+    fn step(&mut self, budget: u64) -> Poll<u64> {
+      for _ in 0..budget.max(1) {
+        if self.i == self.n {
+          return Poll::Ready(self.total);
+        }
+        self.i += 1;
+        self.total += self.i;
+      }
+      Poll::Pending
+    }
+  }
+
+  #[test]
+  fn test_run() {
+    let mut sum = Sum { n: 4, i: 0, total: 0 };
+    assert_eq!(sum.step(0), Poll::Pending);
+    assert_eq!(sum.step(2), Poll::Pending);
+    assert_eq!(sum.step(8), Poll::Ready(10));
+    assert_eq!(sum.step(1), Poll::Ready(10));
+
+    assert_eq!(Sum { n: 100, i: 0, total: 0 }.run(1), 5050);
+  }
+}
@@ -0,0 +1,98 @@
+use core::ptr;
+
+// --------------------------
+// Configuration
+// --------------------------
+
+/// Stack painted below the measuring thread's entry frame; a solver that
+/// needs more is reported as needing exactly this much.
+pub const PAINT: usize = 8 << 20;
+
+// slack left unpainted below `paint`'s own locals
+const MARGIN: usize = 1 << 10;
+const PATTERN: u64 = 0x57ac_57ac_57ac_57ac;
+
+// --------------------------
+// Painting
+// --------------------------
+
+/// Fills the stack below the caller with `PATTERN` and returns the address
+/// everything is measured from.
+///
+/// This writes memory the compiler believes is unused, so it is for
+/// measurement threads only.
+#[inline(never)]
+fn paint() -> usize {
+  let here = 0u8;
+  let top = core::hint::black_box(&here) as *const u8 as usize;
+
+  let mut addr = (top - PAINT) & !7;
+  while addr < top - MARGIN {
+    unsafe { ptr::write_volatile(addr as *mut u64, PATTERN) };
+    addr += 8;
+  }
+
+  top
+}
+
+/// Bytes between `top` and the deepest word no longer holding `PATTERN`.
+#[inline(never)]
+fn high_water(top: usize) -> usize {
+  let mut addr = (top - PAINT) & !7;
+  while addr < top - MARGIN {
+    if unsafe { ptr::read_volatile(addr as *const u64) } != PATTERN {
+      break;
+    }
+    addr += 8;
+  }
+
+  top - addr
+}
+
+/// Runs `f` on a fresh thread with a painted stack, returning its result
+/// and roughly how many bytes of stack it touched.
+///
+/// Anything under `MARGIN` (1 KiB) reads as `MARGIN`.
+pub fn measure_stack<R, F>(f: F) -> (R, usize)
+where
+  F: FnOnce() -> R + Send,
+  R: Send,
+{
+  std::thread::scope(|s| {
+    let thread = std::thread::Builder::new()
+      .stack_size(PAINT + (1 << 20))
+      .spawn_scoped(s, || {
+        let top = paint();
+        let out = f();
+        (out, high_water(top))
+      })
+      .expect("spawn measuring thread");
+
+    thread.join().expect("measured closure panicked")
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[inline(never)]
+  fn burn(depth: usize) -> u64 {
+    let buf = core::hint::black_box([depth as u64; 64]);
+    if depth == 0 {
+      buf[0]
+    } else {
+      buf[1] + burn(depth - 1)
+    }
+  }
+
+  #[test]
+  fn test_measure_stack() {
+    let (_, shallow) = measure_stack(|| burn(1));
+    let (_, deep) = measure_stack(|| burn(100));
+
+    // each level holds a 512 byte array
+    assert!(deep >= 100 * 512, "{deep}");
+    assert!(deep > shallow);
+  }
+}
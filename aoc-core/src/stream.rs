@@ -0,0 +1,244 @@
+//! Input fed a chunk at a time, for devices that receive the puzzle over a
+//! serial link and cannot hold all of it.
+//!
+//! A line-oriented day wraps a [`Lines`] buffer, which keeps only the line
+//! still being received, and folds each completed line into its answer.
+//! Chunks may end anywhere, even inside a multi-byte character.
+
+use crate::checkpoint::{Checkpoint, Reader, Writer};
+use crate::{Error, ParseError, Reason};
+
+/// A day solved from chunks of its input, without seeing all of it at once.
+///
+/// After `feed` fails the stream is left part-way through a line and should
+/// be dropped.
+pub trait Stream {
+  type Answer;
+
+  fn feed(&mut self, chunk: &[u8]) -> Result<(), Error>;
+
+  /// The answer, once the last chunk has been fed.
+  fn finish(self) -> Result<Self::Answer, Error>;
+}
+
+/// Reassembles lines from chunks, keeping at most `N` bytes of a line that
+/// runs past the end of a chunk. No line longer than that is handed over,
+/// even one that arrives whole, so handlers may hold `N` bytes of any line.
+///
+/// Lines are split as `str::lines` splits them. Each is handed over as if it
+/// were a whole input, so a `ParseError` from the handler says line 1; it is
+/// renumbered to the line's place in the stream on the way out.
+pub struct Lines<const N: usize> {
+  partial: [u8; N],
+  len: usize,
+  /// Lines handed over so far.
+  line: usize,
+}
+
+impl<const N: usize> Default for Lines<N> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<const N: usize> Lines<N> {
+  pub const fn new() -> Self {
+    Lines { partial: [0; N], len: 0, line: 0 }
+  }
+
+  /// Hands `each` every line `chunk` completes, then keeps what is left;
+  /// `CapacityExceeded` if any line outgrows `N` bytes.
+  pub fn feed(
+    &mut self,
+    chunk: &[u8],
+    mut each: impl FnMut(&str) -> Result<(), Error>,
+  ) -> Result<(), Error> {
+    let mut rest = chunk;
+
+    while let Some(end) = rest.iter().position(|&b| b == b'\n') {
+      let head = &rest[..end];
+      let head = head.strip_suffix(b"\r").unwrap_or(head);
+
+      if self.len == 0 {
+        // whole lines go straight from the chunk
+        Error::check_capacity("line bytes", N, end)?;
+        emit(&mut self.line, head, &mut each)?;
+      } else {
+        self.push(&rest[..end])?;
+        let len = core::mem::take(&mut self.len);
+        let line = &self.partial[..len];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        emit(&mut self.line, line, &mut each)?;
+      }
+      rest = &rest[end + 1..];
+    }
+
+    self.push(rest)
+  }
+
+  /// Hands `each` the last line, if the input did not end with a newline.
+  pub fn finish(
+    &mut self,
+    mut each: impl FnMut(&str) -> Result<(), Error>,
+  ) -> Result<(), Error> {
+    let len = core::mem::take(&mut self.len);
+    if len == 0 {
+      return Ok(());
+    }
+    emit(&mut self.line, &self.partial[..len], &mut each)
+  }
+
+  fn push(&mut self, bytes: &[u8]) -> Result<(), Error> {
+    let len = self.len + bytes.len();
+    Error::check_capacity("line bytes", N, len)?;
+
+    self.partial[self.len..len].copy_from_slice(bytes);
+    self.len = len;
+    Ok(())
+  }
+}
+
+/// The line being received, for a stream saving its own checkpoint.
+impl<const N: usize> Checkpoint for Lines<N> {
+  type Context = ();
+  const TAG: [u8; 4] = *b"line";
+  const FIELDS: usize = 16 + N;
+
+  fn write(&self, w: &mut Writer<'_>) -> Result<(), Error> {
+    w.usize(self.line)?;
+    w.usize(self.len)?;
+    w.bytes(&self.partial[..self.len])
+  }
+
+  fn read(_: (), r: &mut Reader<'_>) -> Result<Self, Error> {
+    let mut lines = Lines::new();
+    lines.line = r.usize()?;
+    lines.len = r.usize()?;
+    if lines.len > N {
+      return Err(Error::Checkpoint("partial line too long"));
+    }
+    lines.partial[..lines.len].copy_from_slice(r.bytes(lines.len)?);
+    Ok(lines)
+  }
+}
+
+/// Hands `bytes` to `each` as the line after `line`.
+fn emit(
+  line: &mut usize,
+  bytes: &[u8],
+  each: &mut impl FnMut(&str) -> Result<(), Error>,
+) -> Result<(), Error> {
+  *line += 1;
+  let text = core::str::from_utf8(bytes).map_err(|e| ParseError {
+    line: *line,
+    column: e.valid_up_to() + 1,
+    reason: Reason::Malformed("line is not UTF-8"),
+  })?;
+
+  each(text).map_err(|e| match e {
+    Error::Parse(e) => {
+      Error::Parse(ParseError { line: e.line + *line - 1, ..e })
+    }
+    e => e,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Every line `chunks` hold, joined with `|`, into `out`.
+  fn split<'a>(
+    chunks: &[&[u8]],
+    out: &'a mut [u8; 64],
+  ) -> Result<&'a str, Error> {
+    let mut lines = Lines::<8>::new();
+    let mut len = 0;
+    let mut each = |line: &str| {
+      out[len..len + line.len()].copy_from_slice(line.as_bytes());
+      out[len + line.len()] = b'|';
+      len += line.len() + 1;
+      Ok(())
+    };
+
+    for chunk in chunks {
+      lines.feed(chunk, &mut each)?;
+    }
+    lines.finish(&mut each)?;
+    Ok(core::str::from_utf8(&out[..len]).unwrap())
+  }
+
+  #[test]
+  fn test_lines() {
+    let mut out = [0; 64];
+    let joined = split(&[b"ab\r", b"\ncd\n\ne", b"f\r"], &mut out);
+    assert_eq!(joined, Ok("ab|cd||ef\r|"));
+
+    // a character split across chunks is whole by the time its line is
+    let mut out = [0; 64];
+    let joined = split(&[b"\xc3", b"\xa9\n"], &mut out);
+    assert_eq!(joined, Ok("\u{e9}|"));
+
+    let mut out = [0; 64];
+    assert!(matches!(
+      split(&[b"0123", b"45678\n"], &mut out),
+      Err(Error::CapacityExceeded { limit: 8, needed: 9, .. })
+    ));
+
+    // the same line whole in one chunk is just as long
+    let mut out = [0; 64];
+    assert!(matches!(
+      split(&[b"012345678\n"], &mut out),
+      Err(Error::CapacityExceeded { limit: 8, needed: 9, .. })
+    ));
+  }
+
+  #[test]
+  fn test_renumbers_errors() {
+    let mut lines = Lines::<8>::new();
+    let mut each = |line: &str| match line.find('x') {
+      Some(i) => {
+        let reason = Reason::Unexpected('x');
+        Err(ParseError::at(line, &line[i..], reason).into())
+      }
+      None => Ok(()),
+    };
+
+    assert_eq!(lines.feed(b"ab\ncd\n", &mut each), Ok(()));
+    let err = lines.feed(b"ex\n", &mut each).unwrap_err();
+    assert_eq!(
+      err,
+      Error::Parse(ParseError {
+        line: 3,
+        column: 2,
+        reason: Reason::Unexpected('x')
+      })
+    );
+
+    let mut lines = Lines::<8>::new();
+    lines.feed(b"\xff", |_| Ok(())).unwrap();
+    let err = lines.finish(|_| Ok(())).unwrap_err();
+    assert!(matches!(
+      err,
+      Error::Parse(ParseError { line: 1, column: 1, .. })
+    ));
+  }
+
+  #[test]
+  fn test_checkpoint() {
+    let mut lines = Lines::<8>::new();
+    lines.feed(b"ab\ncd", |_| Ok(())).unwrap();
+
+    let mut buf = [0u8; Lines::<8>::BYTES];
+    let used = lines.save(&mut buf).unwrap();
+    let mut resumed = Lines::<8>::restore((), &buf[..used]).unwrap();
+
+    // the resumed buffer finishes the second line where the first left off
+    let each = |line: &str| match line {
+      "cde" => Err(ParseError::at(line, line, Reason::ExpectedNumber).into()),
+      _ => Ok(()),
+    };
+    let err = resumed.feed(b"e\n", each).unwrap_err();
+    assert!(matches!(err, Error::Parse(ParseError { line: 2, .. })));
+  }
+}
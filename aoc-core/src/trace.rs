@@ -0,0 +1,139 @@
+//! Structured events a solver reports on its way to an answer.
+//!
+//! Solvers take a `&mut impl TraceSink` and emit into it; their plain entry
+//! points pass [`NoTrace`], whose empty `event` inlines away together with
+//! the arguments built for it. With the `trace` feature, [`Stderr`] prints
+//! events and [`Collect`] keeps them.
+
+use core::fmt;
+
+/// Something a solver saw, borrowed from its input or working memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<'a> {
+  /// A line of input, before it is solved.
+  Line(&'a str),
+  /// What one line of input contributed, such as day 3's joltage.
+  Value { line: &'a str, value: u64 },
+  /// A named piece of solver state after a step, such as day 1's dial.
+  State { name: &'static str, value: i64 },
+  /// A point the solver works on, such as day 9's red tiles.
+  Point { x: i64, y: i64 },
+  /// One problem's numbers and the operator folding them, as in day 6.
+  Group {
+    index: usize,
+    op: char,
+    values: &'a [u64],
+  },
+  /// Paths counted between two devices, as in day 11.
+  Segment {
+    from: &'a str,
+    to: &'a str,
+    count: u64,
+  },
+  /// Cells a region needs against the cells it has, as in day 12.
+  Fit { needed: u64, area: u64 },
+}
+
+impl fmt::Display for Event<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Event::Line(line) => write!(f, "line {line:?}"),
+      Event::Value { line, value } => write!(f, "{line:?} => {value}"),
+      Event::State { name, value } => write!(f, "{name} = {value}"),
+      Event::Point { x, y } => write!(f, "point ({x}, {y})"),
+      Event::Group { index, op, values } => {
+        write!(f, "group {index} ({op}): {values:?}")
+      }
+      Event::Segment { from, to, count } => {
+        write!(f, "{from} -> {to}: {count}")
+      }
+      Event::Fit { needed, area } => {
+        write!(f, "needs {needed} of {area} cells")
+      }
+    }
+  }
+}
+
+/// Where a solver's events go.
+pub trait TraceSink {
+  fn event(&mut self, event: Event<'_>);
+}
+
+impl<S: TraceSink + ?Sized> TraceSink for &mut S {
+  #[inline(always)]
+  fn event(&mut self, event: Event<'_>) {
+    (**self).event(event);
+  }
+}
+
+/// Drops every event; the default sink.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoTrace;
+
+impl TraceSink for NoTrace {
+  #[inline(always)]
+  fn event(&mut self, _: Event<'_>) {}
+}
+
+/// Prints every event to stderr, one per line.
+#[cfg(feature = "trace")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stderr;
+
+#[cfg(feature = "trace")]
+impl TraceSink for Stderr {
+  fn event(&mut self, event: Event<'_>) {
+    std::eprintln!("{event}");
+  }
+}
+
+/// Keeps every event, rendered, in the order they came.
+#[cfg(feature = "trace")]
+#[derive(Debug, Clone, Default)]
+pub struct Collect {
+  pub events: std::vec::Vec<std::string::String>,
+}
+
+#[cfg(feature = "trace")]
+impl TraceSink for Collect {
+  fn event(&mut self, event: Event<'_>) {
+    use std::string::ToString;
+
+    self.events.push(event.to_string());
+  }
+}
+
+#[cfg(all(test, feature = "trace"))]
+mod tests {
+  use super::*;
+
+  fn every_event(mut sink: impl TraceSink) {
+    sink.event(Event::Line("L68"));
+    sink.event(Event::Value { line: "987654321111111", value: 98 });
+    sink.event(Event::State { name: "dial", value: -18 });
+    sink.event(Event::Point { x: 7, y: 1 });
+    sink.event(Event::Group { index: 0, op: '*', values: &[1, 24, 356] });
+    sink.event(Event::Segment { from: "svr", to: "fft", count: 2 });
+    sink.event(Event::Fit { needed: 42, area: 48 });
+  }
+
+  #[test]
+  fn test_collect() {
+    let mut sink = Collect::default();
+    every_event(&mut sink);
+    every_event(NoTrace);
+
+    assert_eq!(
+      sink.events,
+      [
+        "line \"L68\"",
+        "\"987654321111111\" => 98",
+        "dial = -18",
+        "point (7, 1)",
+        "group 0 (*): [1, 24, 356]",
+        "svr -> fft: 2",
+        "needs 42 of 48 cells",
+      ]
+    );
+  }
+}
@@ -0,0 +1,57 @@
+//! Counting the work a solver does on its way to an answer.
+//!
+//! Solvers take a `&mut impl Tally<S>` for their own counters `S` and count
+//! through it; their plain entry points pass [`NoTally`], which never runs
+//! the counting closure, so the counters inline away. Counters only tally
+//! themselves with the `instrument` feature, which each day's own
+//! `instrument` feature turns on for its `_instrumented` entry points.
+
+/// A solver's work counters, such as day 10's search nodes.
+pub trait Counters {}
+
+/// Where a solver counts its work.
+pub trait Tally<C> {
+  fn tally(&mut self, count: impl FnOnce(&mut C));
+}
+
+#[cfg(feature = "instrument")]
+impl<C: Counters> Tally<C> for C {
+  #[inline(always)]
+  fn tally(&mut self, count: impl FnOnce(&mut C)) {
+    count(self);
+  }
+}
+
+/// Counts nothing; the default tally.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoTally;
+
+impl<C: Counters> Tally<C> for NoTally {
+  #[inline(always)]
+  fn tally(&mut self, _: impl FnOnce(&mut C)) {}
+}
+
+#[cfg(all(test, feature = "instrument"))]
+mod tests {
+  use super::*;
+
+  #[derive(Default)]
+  struct Nodes(u32);
+
+  impl Counters for Nodes {}
+
+  fn walk(tally: &mut impl Tally<Nodes>) {
+    for _ in 0..3 {
+      tally.tally(|n| n.0 += 1);
+    }
+  }
+
+  #[test]
+  fn test_tally() {
+    let mut nodes = Nodes::default();
+    walk(&mut nodes);
+    walk(&mut NoTally);
+
+    assert_eq!(nodes.0, 3);
+  }
+}
@@ -0,0 +1,148 @@
+//! One caller-owned block of bytes that a day's scratch slices are cut from.
+//!
+//! The Owner/View days each expose `requirements(&input)`, whose `bytes` is
+//! how big the block must be and whose `carve` cuts the day's `Scratch` out
+//! of it. Firmware can reserve the block statically; std callers allocate it
+//! once. Nothing is ever dropped: values written into the block are simply
+//! forgotten with it, so only [`Plain`] data and borrows belong there.
+
+use core::ptr::NonNull;
+use core::slice;
+
+use crate::{Error, Solver};
+
+/// Values `take` may lay into the block.
+///
+/// # Safety
+///
+/// Implementors have no drop glue, since the block forgets them, and no
+/// padding bytes, since the block's owner reads it back as `u8` once the
+/// slices are gone.
+pub unsafe trait Plain {}
+
+unsafe impl Plain for bool {}
+unsafe impl Plain for u8 {}
+unsafe impl Plain for u16 {}
+unsafe impl Plain for u32 {}
+unsafe impl Plain for u64 {}
+unsafe impl Plain for usize {}
+unsafe impl Plain for i64 {}
+unsafe impl Plain for (u64, u64) {}
+unsafe impl Plain for &str {}
+unsafe impl<T> Plain for &mut [T] {}
+
+/// A [`Solver`] whose `Scratch` is carved from one [`Workspace`], so a
+/// harness can size and cut it without knowing the day.
+pub trait Carve: Solver {
+  /// How big the block must be for `input`: the day's
+  /// `requirements(input).bytes`.
+  fn bytes(input: &Self::Input<'_>) -> usize;
+
+  /// Cuts the scratch for `input` out of `ws`.
+  fn carve<'a>(
+    input: &Self::Input<'_>,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Self::Scratch<'a>, Error>;
+}
+
+/// Typed slices cut in turn from one caller-provided byte buffer.
+pub struct Workspace<'a> {
+  rest: &'a mut [u8],
+}
+
+impl<'a> Workspace<'a> {
+  pub fn new(bytes: &'a mut [u8]) -> Self {
+    Workspace { rest: bytes }
+  }
+
+  /// Bytes `take` may use for `n` values of `T`, worst-case padding
+  /// included.
+  pub const fn size_of<T>(n: usize) -> usize {
+    size_of::<T>() * n + align_of::<T>() - 1
+  }
+
+  /// Bytes not yet taken.
+  pub fn remaining(&self) -> usize {
+    self.rest.len()
+  }
+
+  /// The next `n` values, each set by `fill`; `CapacityExceeded` once the
+  /// block runs out. Zero bytes' worth takes nothing, padding included.
+  pub fn take<T: Plain>(
+    &mut self,
+    n: usize,
+    fill: impl FnMut() -> T,
+  ) -> Result<&'a mut [T], Error> {
+    if n == 0 || size_of::<T>() == 0 {
+      return Ok(unsafe { init(NonNull::dangling().as_ptr(), n, fill) });
+    }
+
+    let rest = core::mem::take(&mut self.rest);
+    let pad = rest.as_ptr().align_offset(align_of::<T>());
+    let needed = size_of::<T>()
+      .checked_mul(n)
+      .and_then(|size| size.checked_add(pad))
+      .unwrap_or(usize::MAX);
+    if needed > rest.len() {
+      let limit = rest.len();
+      self.rest = rest;
+      return Err(Error::CapacityExceeded {
+        what: "workspace bytes",
+        limit,
+        needed,
+      });
+    }
+
+    let (head, tail) = rest[pad..].split_at_mut(needed - pad);
+    self.rest = tail;
+
+    // aligned above, and `head`, which this workspace gives up, holds `n`
+    Ok(unsafe { init(head.as_mut_ptr().cast(), n, fill) })
+  }
+}
+
+/// Writes `n` values from `fill` at `ptr` and borrows them as a slice.
+///
+/// # Safety
+///
+/// `ptr` is aligned for `T` and valid for `n` writes for `'a`.
+unsafe fn init<'a, T>(
+  ptr: *mut T,
+  n: usize,
+  mut fill: impl FnMut() -> T,
+) -> &'a mut [T] {
+  for i in 0..n {
+    unsafe { ptr.add(i).write(fill()) };
+  }
+  unsafe { slice::from_raw_parts_mut(ptr, n) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_take() {
+    let mut bytes =
+      [0xffu8; Workspace::size_of::<u8>(3) + Workspace::size_of::<u64>(2)];
+    let mut ws = Workspace::new(&mut bytes);
+
+    let small = ws.take(3, || 7u8).unwrap();
+    let wide = ws.take(2, || 9u64).unwrap();
+    assert_eq!((&*small, &*wide), (&[7, 7, 7][..], &[9, 9][..]));
+    assert_eq!(wide.as_ptr().align_offset(align_of::<u64>()), 0);
+
+    let left = ws.remaining();
+    assert!(matches!(
+      ws.take(1, || 0u64),
+      Err(Error::CapacityExceeded { limit, needed, .. })
+        if limit == left && needed > left
+    ));
+    assert_eq!(ws.remaining(), left);
+    assert_eq!(ws.take(0, || 0u64).map(|s| s.len()), Ok(0));
+
+    // nothing to lay out needs no alignment either
+    let mut ws = Workspace::new(&mut []);
+    assert_eq!(ws.take(0, || 0u64).map(|s| s.len()), Ok(0));
+  }
+}
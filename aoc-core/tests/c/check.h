@@ -0,0 +1,43 @@
+/* Helpers for the per-day C tests run by `aoc_core::cc`. */
+#ifndef AOC_CHECK_H
+#define AOC_CHECK_H
+
+#include <stdio.h>
+#include <stdlib.h>
+
+/* Fails the test, naming the line, unless `cond` holds. */
+#define CHECK(cond)                                                   \
+  do {                                                                \
+    if (!(cond)) {                                                    \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+              #cond);                                                 \
+      exit(1);                                                        \
+    }                                                                 \
+  } while (0)
+
+/* The whole file at `path`, its length in `len`; exits if unreadable. */
+static char *read_input(const char *path, size_t *len) {
+  FILE *f = fopen(path, "rb");
+  if (!f) {
+    fprintf(stderr, "cannot open %s\n", path);
+    exit(1);
+  }
+
+  size_t cap = 4096;
+  char *text = malloc(cap);
+  *len = 0;
+  size_t n;
+  while (text && (n = fread(text + *len, 1, cap - *len, f)) > 0) {
+    *len += n;
+    if (*len == cap) {
+      cap *= 2;
+      text = realloc(text, cap);
+    }
+  }
+  fclose(f);
+
+  CHECK(text != NULL);
+  return text;
+}
+
+#endif
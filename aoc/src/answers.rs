@@ -0,0 +1,126 @@
+use std::fmt::Display;
+
+use aoc_core::Part;
+
+// --------------------------
+// Data Model
+// --------------------------
+
+/// Known answers for one day, keyed by `aoc_core::input_hash`.
+///
+/// The file holds one input per line: its hash in hex, then the part 1 and
+/// part 2 answers, with `-` for a part not yet known. Blank lines and `#`
+/// comments are skipped. Only hashes are kept, so the file can be committed
+/// where the inputs cannot.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+  known: Vec<(u64, [Option<String>; 2])>,
+}
+
+/// How an answer compares with the known one for its input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+  Match,
+  Mismatch { expected: String },
+  Unknown,
+}
+
+impl Display for Verdict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Verdict::Match => write!(f, "ok"),
+      Verdict::Mismatch { expected } => write!(f, "MISMATCH, want {expected}"),
+      Verdict::Unknown => write!(f, "unknown"),
+    }
+  }
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+impl Answers {
+  pub fn parse(text: &str) -> Result<Self, String> {
+    let mut known = Vec::new();
+
+    for (n, line) in text.lines().enumerate() {
+      let line = line.split('#').next().unwrap_or("").trim();
+      if line.is_empty() {
+        continue;
+      }
+
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      let [hash, part1, part2] = fields[..] else {
+        return Err(format!("line {}: expected `hash part1 part2`", n + 1));
+      };
+      let hash = u64::from_str_radix(hash, 16)
+        .map_err(|_| format!("line {}: invalid hash `{hash}`", n + 1))?;
+
+      let answer = |a: &str| (a != "-").then(|| a.to_string());
+      known.push((hash, [answer(part1), answer(part2)]));
+    }
+
+    Ok(Answers { known })
+  }
+
+  /// Compares `answer` with the one known for `hash`'s `part`.
+  pub fn check(&self, hash: u64, part: Part, answer: &str) -> Verdict {
+    let index = match part {
+      Part::One => 0,
+      Part::Two => 1,
+    };
+    let known = self.known.iter().find(|(h, _)| *h == hash);
+
+    match known.and_then(|(_, answers)| answers[index].as_deref()) {
+      Some(expected) if expected == answer => Verdict::Match,
+      Some(expected) => Verdict::Mismatch { expected: expected.into() },
+      None => Verdict::Unknown,
+    }
+  }
+
+  pub fn contains(&self, hash: u64) -> bool {
+    self.known.iter().any(|(h, _)| *h == hash)
+  }
+}
+
+/// A line recording `answers` for the input hashing to `hash`.
+pub fn line(hash: u64, answers: [&str; 2]) -> String {
+  format!("{hash:016x} {} {}\n", answers[0], answers[1])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_check() {
+    let text = "\
+# hash            part 1  part 2
+00000000000000ff  1034    6166
+0000000000000100  3       -
+";
+    let answers = Answers::parse(text).unwrap();
+
+    assert_eq!(answers.check(0xff, Part::One, "1034"), Verdict::Match);
+    assert_eq!(
+      answers.check(0xff, Part::Two, "6167"),
+      Verdict::Mismatch { expected: "6166".into() }
+    );
+    assert_eq!(answers.check(0x100, Part::Two, "6"), Verdict::Unknown);
+    assert_eq!(answers.check(0x101, Part::One, "3"), Verdict::Unknown);
+
+    // a recorded line reads back as the answers it recorded
+    let recorded = Answers::parse(&line(0x101, ["3", "6"])).unwrap();
+    assert_eq!(recorded.check(0x101, Part::Two, "6"), Verdict::Match);
+    assert!(recorded.contains(0x101));
+  }
+
+  #[test]
+  fn test_parse_error() {
+    let err = Answers::parse("ff 1\n").err().unwrap();
+    assert_eq!(err, "line 1: expected `hash part1 part2`");
+
+    let err = Answers::parse("\nxyz 1 2\n").err().unwrap();
+    assert_eq!(err, "line 2: invalid hash `xyz`");
+  }
+}
@@ -0,0 +1,270 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use aoc_core::workspace::Carve;
+use aoc_core::{Answer, Part, Solver, Workspace};
+
+// --------------------------
+// Data Model
+// --------------------------
+
+pub struct Outcome {
+  pub answer: Answer,
+  pub parse: Duration,
+  pub solve: Duration,
+}
+
+/// Puzzle parameters that are not in the input text, where a day's sample
+/// and real puzzle differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+  /// Pairs day 8's part 1 connects: `day_8::K_EDGES`, or 10 for its sample.
+  pub k_edges: usize,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Options { k_edges: day_8::K_EDGES }
+  }
+}
+
+#[derive(Debug)]
+pub enum RunError {
+  UnknownDay(u8),
+  Parse(String),
+  Solve(String),
+}
+
+impl Display for RunError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RunError::UnknownDay(day) => write!(f, "no solver for day {day}"),
+      RunError::Parse(e) => write!(f, "parse error: {e}"),
+      RunError::Solve(e) => write!(f, "solve error: {e}"),
+    }
+  }
+}
+
+// --------------------------
+// Dispatch
+// --------------------------
+
+pub fn run(
+  day: u8,
+  part: Part,
+  input: &str,
+  options: &Options,
+) -> Result<Outcome, RunError> {
+  match day {
+    1 => solve::<day_1::Day1>(part, input, ()),
+    2 => solve::<day_2::Day2>(part, input, ()),
+    3 => solve::<day_3::prelude::Day3>(part, input, ()),
+    4 => carved::<day_4::prelude::Day4>(part, input),
+    5 => carved::<day_5::prelude::Day5>(part, input),
+    6 => carved::<day_6::prelude::Day6>(part, input),
+    7 => carved::<day_7::prelude::Day7>(part, input),
+    8 => day8(part, input, options.k_edges),
+    9 => solve::<day_9::prelude::Day9>(part, input, ()),
+    10 => solve::<day_10::prelude::Day10>(part, input, ()),
+    11 => solve::<day_11::prelude::Day11>(part, input, ()),
+    12 => solve::<day_12::prelude::Day12>(part, input, ()),
+    _ => Err(RunError::UnknownDay(day)),
+  }
+}
+
+// a frame per day, so `run`'s own stays small and `aoc stack` charges each
+// day only for its solver
+#[inline(never)]
+fn solve<'a, S>(
+  part: Part,
+  input: &'a str,
+  scratch: S::Scratch<'a>,
+) -> Result<Outcome, RunError>
+where
+  S: Solver,
+  S::Error: Display,
+{
+  let start = Instant::now();
+  let problem = S::parse(input).map_err(|e| RunError::Parse(e.to_string()))?;
+  let parse = start.elapsed();
+
+  let start = Instant::now();
+  let answer = S::solve(part, &problem, scratch)
+    .map_err(|e| RunError::Solve(e.to_string()))?;
+  let solve = start.elapsed();
+
+  Ok(Outcome { answer, parse, solve })
+}
+
+// day 8 takes its number of pairs after parsing, so it times its own parse
+#[inline(never)]
+fn day8(part: Part, input: &str, k_edges: usize) -> Result<Outcome, RunError> {
+  use day_8::prelude::{Day8, parse};
+
+  let start = Instant::now();
+  let problem = parse(input)
+    .and_then(|p| p.with_k_edges(k_edges))
+    .map_err(|e| RunError::Parse(e.to_string()))?;
+  let parse = start.elapsed();
+
+  let start = Instant::now();
+  let answer = Day8::solve(part, &problem, ())
+    .map_err(|e| RunError::Solve(e.to_string()))?;
+  let solve = start.elapsed();
+
+  Ok(Outcome { answer, parse, solve })
+}
+
+// --------------------------
+// Scratch owners
+// --------------------------
+// The Owner/View days borrow their working memory from the caller: this
+// sizes one block by the day's `requirements` and carves it up before
+// handing it to the solver.
+
+#[inline(never)]
+fn carved<S>(part: Part, input: &str) -> Result<Outcome, RunError>
+where
+  S: Carve,
+  S::Error: Display,
+{
+  let mut block = Vec::new();
+
+  let start = Instant::now();
+  let problem = S::parse(input).map_err(|e| RunError::Parse(e.to_string()))?;
+  let parse = start.elapsed();
+
+  block.resize(S::bytes(&problem), 0);
+  let scratch = S::carve(&problem, &mut Workspace::new(&mut block))
+    .map_err(|e| RunError::Solve(e.to_string()))?;
+
+  let start = Instant::now();
+  let answer = S::solve(part, &problem, scratch)
+    .map_err(|e| RunError::Solve(e.to_string()))?;
+  let solve = start.elapsed();
+
+  Ok(Outcome { answer, parse, solve })
+}
+
+// --------------------------
+// Work counters
+// --------------------------
+
+/// The report of the work `part`'s solver counted, for the days that count
+/// it: day 4's part 2, part 1 of days 7 and 8, and both parts of day 10.
+/// `None` for the rest.
+pub fn work(
+  day: u8,
+  part: Part,
+  input: &str,
+  options: &Options,
+) -> Result<Option<String>, RunError> {
+  let stats = match (day, part) {
+    (4, Part::Two) => day4_work(input)?,
+    (7, Part::One) => day7_work(input)?,
+    (8, Part::One) => {
+      use day_8::prelude::{parse, part1_instrumented};
+
+      let problem = parse(input)
+        .and_then(|p| p.with_k_edges(options.k_edges))
+        .map_err(|e| RunError::Parse(e.to_string()))?;
+      part1_instrumented(&problem).1.to_string()
+    }
+    (10, _) => {
+      use day_10::prelude::{parse, part1_instrumented, part2_instrumented};
+
+      let problem = parse(input).map_err(|e| RunError::Parse(e.to_string()))?;
+      let (_, stats) = match part {
+        Part::One => part1_instrumented(&problem),
+        Part::Two => part2_instrumented(&problem),
+      };
+      stats.to_string()
+    }
+    _ => return Ok(None),
+  };
+
+  Ok(Some(stats))
+}
+
+fn day4_work(input: &str) -> Result<String, RunError> {
+  use day_4::prelude::{parse, part2_instrumented, requirements};
+  use day_4::tinysetqueue::prelude::{MembershipMode, TinySetQueue};
+
+  let problem = parse(input).map_err(|e| RunError::Parse(e.to_string()))?;
+  let needs = requirements(&problem);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs
+    .carve(&mut Workspace::new(&mut block))
+    .map_err(|e| RunError::Solve(e.to_string()))?;
+
+  let mut queue = TinySetQueue::new(
+    scratch.queue_buf,
+    scratch.in_queue,
+    MembershipMode::InQueue,
+  );
+  let grid = problem.grid();
+  let (_, stats) =
+    part2_instrumented(&grid, scratch.present, scratch.degree, &mut queue);
+  Ok(stats.to_string())
+}
+
+fn day7_work(input: &str) -> Result<String, RunError> {
+  use day_7::prelude::{parse, part1_instrumented, requirements};
+
+  let grid = parse(input).map_err(|e| RunError::Parse(e.to_string()))?;
+  let needs = requirements(&grid);
+  let mut block = vec![0u8; needs.bytes];
+  let (_, stats) = needs
+    .carve(&mut Workspace::new(&mut block))
+    .and_then(|scratch| grid.as_part1(scratch.part1()))
+    .and_then(|mut p| part1_instrumented(&mut p))
+    .map_err(|e| RunError::Solve(e.to_string()))?;
+  Ok(stats.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_carved_parse_error() {
+    let bad = [
+      (4, "..@\n.x@\n"),
+      (5, "3-5\n1x-4\n\n1\n"),
+      (6, "1 2\n3 x\n* +\n"),
+      (7, "..S..\n..x..\n"),
+    ];
+    for (day, input) in bad {
+      for part in [Part::One, Part::Two] {
+        let err = run(day, part, input, &Options::default()).err();
+        assert!(
+          matches!(&err, Some(RunError::Parse(e)) if e.starts_with("line 2,")),
+          "day {day} {part:?}: {err:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_work() {
+    let sample = |day| {
+      let dir = env!("CARGO_MANIFEST_DIR");
+      std::fs::read_to_string(format!("{dir}/../day-{day}/sample.txt"))
+        .unwrap()
+    };
+    let options = Options { k_edges: 10 };
+
+    let counted =
+      [(4, Part::Two), (7, Part::One), (8, Part::One), (10, Part::One)];
+    for (day, part) in counted.into_iter().chain([(10, Part::Two)]) {
+      let stats = work(day, part, &sample(day), &options).unwrap();
+      assert!(stats.is_some_and(|s| !s.is_empty()), "day {day} {part:?}");
+    }
+
+    assert_eq!(work(1, Part::One, &sample(1), &options).ok(), Some(None));
+    assert!(matches!(
+      work(4, Part::Two, "..@\n.x@\n", &options),
+      Err(RunError::Parse(_))
+    ));
+  }
+}
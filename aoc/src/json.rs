@@ -0,0 +1,83 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_core::{Answer, Part};
+
+/// One part's result as a JSON object on one line, for dashboards to ingest:
+///
+/// ```text
+/// {"day":1,"part":2,"answer":6,"input_hash":"a43ababbcae82b7d","elapsed_ns":9}
+/// ```
+///
+/// Numeric answers are JSON numbers, however wide, and text answers are
+/// strings. `elapsed_ns` covers parse and solve.
+pub fn line(
+  day: u8,
+  part: Part,
+  answer: &Answer,
+  hash: u64,
+  elapsed: Duration,
+) -> String {
+  let part = match part {
+    Part::One => 1,
+    Part::Two => 2,
+  };
+  let answer = match answer {
+    Answer::Text(text) => string(text.as_str()),
+    number => number.to_string(),
+  };
+
+  format!(
+    "{{\"day\":{day},\"part\":{part},\"answer\":{answer},\
+     \"input_hash\":\"{hash:016x}\",\"elapsed_ns\":{}}}",
+    elapsed.as_nanos()
+  )
+}
+
+/// `text` as a JSON string literal.
+fn string(text: &str) -> String {
+  let mut out = String::from("\"");
+  for c in text.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      c if c.is_control() => {
+        let _ = write!(out, "\\u{:04x}", c as u32);
+      }
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use aoc_core::Text;
+
+  use super::*;
+
+  #[test]
+  fn test_line() {
+    let elapsed = Duration::from_micros(41);
+    assert_eq!(
+      line(1, Part::Two, &Answer::from(6166u64), 0x6a61, elapsed),
+      "{\"day\":1,\"part\":2,\"answer\":6166,\
+       \"input_hash\":\"0000000000006a61\",\"elapsed_ns\":41000}"
+    );
+
+    let wide = line(9, Part::One, &Answer::from(u128::MAX), 0, elapsed);
+    assert!(
+      wide.contains(&format!("\"answer\":{},", u128::MAX)),
+      "{wide}"
+    );
+
+    let text = Answer::Text(Text::new("a\"b\\c\n\u{1}").unwrap());
+    let quoted = line(9, Part::One, &text, 0, elapsed);
+    assert!(
+      quoted.contains(r#""answer":"a\"b\\c\n\u0001","#),
+      "{quoted}"
+    );
+  }
+}
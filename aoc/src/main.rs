@@ -0,0 +1,451 @@
+use std::io::Read;
+use std::process::ExitCode;
+
+use aoc_core::stack::{PAINT, measure_stack};
+use aoc_core::{Part, input_hash};
+
+mod answers;
+mod days;
+mod json;
+
+use answers::{Answers, Verdict};
+use days::Options;
+
+
+const USAGE: &str = "\
+usage: aoc run --day <1-12> [--part <1|2>] --input <path|-> [--json]
+               [--part2-input <path|->] [--k-edges <n>] [--work]
+       aoc stack [--inputs <dir>]
+       aoc verify [--inputs <dir>] [--record]
+
+  --day     puzzle day to solve
+  --part    which part to solve; both when omitted
+  --input   puzzle input file, or `-` to read stdin
+  --json    print each part as a JSON line: day, part, answer, input hash
+            and elapsed nanoseconds
+  --part2-input
+            a separate input for part 2, as day 11's sample has
+  --k-edges pairs day 8's part 1 connects: 1000 for the puzzle, 10 for its
+            sample
+  --work    also print to stderr the work counted by the solvers that
+            count it: day 4's part 2, part 1 of days 7 and 8, and day 10
+  --inputs  directory holding `day-N/input.txt`; defaults to `.`
+  --record  add the answers of inputs not yet in `day-N/answers.txt`";
+
+// --------------------------
+// Args
+// --------------------------
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+  Run(RunArgs),
+  /// Measure every day's stack high-water mark on its input.
+  Stack {
+    inputs: String,
+  },
+  /// Check every day's answers against its known-answers file.
+  Verify {
+    inputs: String,
+    record: bool,
+  },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RunArgs {
+  day: u8,
+  parts: Vec<Part>,
+  input: String,
+  part2_input: Option<String>,
+  options: Options,
+  json: bool,
+  work: bool,
+}
+
+fn parse_args<I>(args: I) -> Result<Command, String>
+where
+  I: IntoIterator<Item = String>,
+{
+  let mut args = args.into_iter();
+
+  match args.next().as_deref() {
+    Some("run") => parse_run(args).map(Command::Run),
+    Some("stack") => parse_stack(args),
+    Some("verify") => parse_verify(args),
+    Some(other) => Err(format!("unknown command `{other}`")),
+    None => Err("missing command".into()),
+  }
+}
+
+fn parse_stack(
+  mut args: impl Iterator<Item = String>,
+) -> Result<Command, String> {
+  let mut inputs = String::from(".");
+
+  while let Some(flag) = args.next() {
+    let value = args
+      .next()
+      .ok_or_else(|| format!("missing value for `{flag}`"))?;
+
+    match flag.as_str() {
+      "--inputs" => inputs = value,
+      _ => return Err(format!("unknown flag `{flag}`")),
+    }
+  }
+
+  Ok(Command::Stack { inputs })
+}
+
+fn parse_verify(
+  mut args: impl Iterator<Item = String>,
+) -> Result<Command, String> {
+  let mut inputs = String::from(".");
+  let mut record = false;
+
+  while let Some(flag) = args.next() {
+    match flag.as_str() {
+      "--record" => record = true,
+      "--inputs" => {
+        inputs = args
+          .next()
+          .ok_or_else(|| format!("missing value for `{flag}`"))?;
+      }
+      _ => return Err(format!("unknown flag `{flag}`")),
+    }
+  }
+
+  Ok(Command::Verify { inputs, record })
+}
+
+fn parse_run(
+  mut args: impl Iterator<Item = String>,
+) -> Result<RunArgs, String> {
+  let mut day = None;
+  let mut parts = vec![Part::One, Part::Two];
+  let mut input = None;
+  let mut part2_input = None;
+  let mut options = Options::default();
+  let mut json = false;
+  let mut work = false;
+
+  while let Some(flag) = args.next() {
+    // switches take no value
+    let switch = match flag.as_str() {
+      "--json" => Some(&mut json),
+      "--work" => Some(&mut work),
+      _ => None,
+    };
+    if let Some(on) = switch {
+      *on = true;
+      continue;
+    }
+    let value = args
+      .next()
+      .ok_or_else(|| format!("missing value for `{flag}`"))?;
+
+    match flag.as_str() {
+      "--day" => {
+        let n = value
+          .parse::<u8>()
+          .ok()
+          .filter(|n| (1..=12).contains(n))
+          .ok_or_else(|| format!("invalid day `{value}`"))?;
+        day = Some(n);
+      }
+      "--part" => {
+        parts = match value.as_str() {
+          "1" => vec![Part::One],
+          "2" => vec![Part::Two],
+          _ => return Err(format!("invalid part `{value}`")),
+        };
+      }
+      "--input" => input = Some(value),
+      "--part2-input" => part2_input = Some(value),
+      "--k-edges" => {
+        options.k_edges = value
+          .parse()
+          .map_err(|_| format!("invalid number of pairs `{value}`"))?;
+      }
+      _ => return Err(format!("unknown flag `{flag}`")),
+    }
+  }
+
+  Ok(RunArgs {
+    day: day.ok_or("missing --day")?,
+    parts,
+    input: input.ok_or("missing --input")?,
+    part2_input,
+    options,
+    json,
+    work,
+  })
+}
+
+fn read_input(path: &str) -> std::io::Result<String> {
+  if path == "-" {
+    let mut text = String::new();
+    std::io::stdin().read_to_string(&mut text)?;
+    Ok(text)
+  } else {
+    std::fs::read_to_string(path)
+  }
+}
+
+// --------------------------
+// Main
+// --------------------------
+
+fn main() -> ExitCode {
+  match parse_args(std::env::args().skip(1)) {
+    Ok(Command::Run(args)) => run(args),
+    Ok(Command::Stack { inputs }) => stack(&inputs),
+    Ok(Command::Verify { inputs, record }) => verify(&inputs, record),
+    Err(e) => {
+      eprintln!("error: {e}\n\n{USAGE}");
+      ExitCode::from(2)
+    }
+  }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+  let read = |path: &str| {
+    read_input(path)
+      .map_err(|e| eprintln!("error: cannot read `{path}`: {e}"))
+      .ok()
+  };
+  let Some(input) = read(&args.input) else {
+    return ExitCode::FAILURE;
+  };
+  let part2_input = match args.part2_input.as_deref().map(read) {
+    Some(Some(text)) => Some(text),
+    Some(None) => return ExitCode::FAILURE,
+    None => None,
+  };
+
+  for part in args.parts {
+    let (n, input) = match part {
+      Part::One => (1, &input),
+      Part::Two => (2, part2_input.as_ref().unwrap_or(&input)),
+    };
+    let hash = input_hash(input);
+
+    match days::run(args.day, part, input, &args.options) {
+      Ok(outcome) if args.json => {
+        let elapsed = outcome.parse + outcome.solve;
+        println!(
+          "{}",
+          json::line(args.day, part, &outcome.answer, hash, elapsed)
+        );
+      }
+      Ok(outcome) => println!(
+        "Part {n}: {} (parse {:?}, solve {:?})",
+        outcome.answer, outcome.parse, outcome.solve
+      ),
+      Err(e) => {
+        eprintln!("error: day {} part {n}: {e}", args.day);
+        return ExitCode::FAILURE;
+      }
+    }
+
+    if args.work {
+      match days::work(args.day, part, input, &args.options) {
+        Ok(Some(stats)) => eprintln!("Part {n} work:\n{stats}"),
+        Ok(None) => eprintln!("Part {n} counts no work"),
+        Err(e) => {
+          eprintln!("error: day {} part {n}: {e}", args.day);
+          return ExitCode::FAILURE;
+        }
+      }
+    }
+  }
+
+  ExitCode::SUCCESS
+}
+
+/// Prints a markdown table of each part's stack high-water mark, parse
+/// included. Days without an input are skipped.
+fn stack(inputs: &str) -> ExitCode {
+  let mut status = ExitCode::SUCCESS;
+
+  println!("| day | part 1 | part 2 |");
+  println!("|----:|-------:|-------:|");
+
+  for day in 1..=12 {
+    let path = format!("{inputs}/day-{day}/input.txt");
+    let Ok(input) = std::fs::read_to_string(&path) else {
+      eprintln!("skipping day {day}: cannot read `{path}`");
+      continue;
+    };
+
+    let cells = [Part::One, Part::Two].map(|part| {
+      let (outcome, used) = measure_stack(|| {
+        days::run(day, part, &input, &Options::default())
+      });
+      match outcome {
+        Ok(_) if used >= PAINT => format!("> {} KiB", PAINT >> 10),
+        Ok(_) => format!("{:.1} KiB", used as f64 / 1024.0),
+        Err(e) => {
+          eprintln!("error: day {day} {part:?}: {e}");
+          status = ExitCode::FAILURE;
+          "error".into()
+        }
+      }
+    });
+
+    println!("| {day} | {} | {} |", cells[0], cells[1]);
+  }
+
+  status
+}
+
+/// Prints a markdown table of each part's answer against the day's
+/// `answers.txt`. Mismatches and errors fail the run; unknown answers do not.
+/// Days without an input are skipped.
+fn verify(inputs: &str, record: bool) -> ExitCode {
+  let mut status = ExitCode::SUCCESS;
+
+  println!("| day | input | part 1 | part 2 |");
+  println!("|----:|:------|:-------|:-------|");
+
+  for day in 1..=12 {
+    let path = format!("{inputs}/day-{day}/input.txt");
+    let Ok(input) = std::fs::read_to_string(&path) else {
+      eprintln!("skipping day {day}: cannot read `{path}`");
+      continue;
+    };
+
+    let answers_path = format!("{inputs}/day-{day}/answers.txt");
+    let text = match std::fs::read_to_string(&answers_path) {
+      Ok(text) => text,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+      Err(e) => {
+        eprintln!("error: cannot read `{answers_path}`: {e}");
+        status = ExitCode::FAILURE;
+        continue;
+      }
+    };
+    let answers = match Answers::parse(&text) {
+      Ok(answers) => answers,
+      Err(e) => {
+        eprintln!("error: `{answers_path}` {e}");
+        status = ExitCode::FAILURE;
+        continue;
+      }
+    };
+
+    let hash = input_hash(&input);
+    let got = [Part::One, Part::Two]
+      .map(|part| days::run(day, part, &input, &Options::default()));
+
+    let cells = [Part::One, Part::Two].map(|part| match &got[part as usize] {
+      Ok(outcome) => {
+        let verdict = answers.check(hash, part, &outcome.answer.to_string());
+        if let Verdict::Mismatch { .. } = verdict {
+          status = ExitCode::FAILURE;
+        }
+        format!("{} ({verdict})", outcome.answer)
+      }
+      Err(e) => {
+        eprintln!("error: day {day} {part:?}: {e}");
+        status = ExitCode::FAILURE;
+        "error".into()
+      }
+    });
+
+    println!("| {day} | {hash:016x} | {} | {} |", cells[0], cells[1]);
+
+    if record
+      && !answers.contains(hash)
+      && let [Ok(one), Ok(two)] = &got
+    {
+      let sep = if text.is_empty() || text.ends_with('\n') {
+        ""
+      } else {
+        "\n"
+      };
+      let [one, two] = [one, two].map(|o| o.answer.to_string());
+      let line = answers::line(hash, [&one, &two]);
+      match std::fs::write(&answers_path, format!("{text}{sep}{line}")) {
+        Ok(()) => eprintln!("recorded day {day} in `{answers_path}`"),
+        Err(e) => {
+          eprintln!("error: cannot write `{answers_path}`: {e}");
+          status = ExitCode::FAILURE;
+        }
+      }
+    }
+  }
+
+  status
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn args(line: &str) -> Result<RunArgs, String> {
+    match command(line)? {
+      Command::Run(args) => Ok(args),
+      other => Err(format!("not a run: {other:?}")),
+    }
+  }
+
+  fn command(line: &str) -> Result<Command, String> {
+    parse_args(line.split_whitespace().map(String::from))
+  }
+
+  #[test]
+  fn test_parse_args() {
+    let parsed = args("run --day 8 --part 2 --input -").unwrap();
+    assert_eq!(
+      parsed,
+      RunArgs {
+        day: 8,
+        parts: vec![Part::Two],
+        input: "-".into(),
+        part2_input: None,
+        options: Options::default(),
+        json: false,
+        work: false
+      }
+    );
+
+    let sample = args(
+      "run --day 11 --input sample.txt --part2-input sample.part2.txt \
+       --k-edges 10",
+    )
+    .unwrap();
+    assert_eq!(sample.part2_input.as_deref(), Some("sample.part2.txt"));
+    assert_eq!(sample.options, Options { k_edges: 10 });
+    assert!(args("run --day 8 --input - --k-edges ten").is_err());
+
+    let json = args("run --day 8 --json --input -").unwrap();
+    assert!(json.json);
+    assert_eq!(json.input, "-");
+    assert!(args("run --day 10 --work --input -").unwrap().work);
+
+    let both = args("run --input day-1/input.txt --day 1").unwrap();
+    assert_eq!(both.parts, vec![Part::One, Part::Two]);
+
+    assert!(args("run --day 1").is_err());
+    assert!(args("run --day 13 --input -").is_err());
+    assert!(args("run --day 1 --part 3 --input -").is_err());
+    assert!(args("bench --day 1").is_err());
+
+    let stack = command("stack --inputs inputs").unwrap();
+    assert_eq!(stack, Command::Stack { inputs: "inputs".into() });
+    assert_eq!(
+      command("stack").unwrap(),
+      Command::Stack { inputs: ".".into() }
+    );
+    assert!(command("stack --day 1").is_err());
+
+    assert_eq!(
+      command("verify --record --inputs inputs").unwrap(),
+      Command::Verify { inputs: "inputs".into(), record: true }
+    );
+    assert_eq!(
+      command("verify").unwrap(),
+      Command::Verify { inputs: ".".into(), record: false }
+    );
+    assert!(command("verify --inputs").is_err());
+  }
+}
@@ -1,27 +1,25 @@
+use std::sync::LazyLock;
+
 use day_1::parse;
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 use day_1::part1_impl::part1;
 #[cfg(feature = "part2")]
 use day_1::part2;
 use divan::black_box;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
-
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let p = parse(black_box(INPUT));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
   black_box(part1(&p));
 }
 
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let p = parse(black_box(INPUT));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
   black_box(part2(&p));
 }
 
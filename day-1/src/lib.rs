@@ -1,5 +1,18 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::checkpoint::{Checkpoint, Reader, Writer};
+use aoc_core::parse::number;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::stream::{Lines, Stream};
+use aoc_core::trace::{Event, NoTrace, TraceSink};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Error, Part, Solver};
+use aoc_core::{ParseError, Reason};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 // --------------------------
 // Data Model
 // --------------------------
@@ -22,55 +35,80 @@ pub struct Problem<'a> {
 // Parse
 // --------------------------
 
-fn parse_instruction(line: &str) -> Instruction {
-  let (dir, num_str) = line.split_at(1);
-
-  let dir = match dir.as_bytes()[0] {
-    b'L' => Turn::Left,
-    b'R' => Turn::Right,
-    _ => unimplemented!(
-      "Turns must either be counter-clockwise (L) or clockwise (R)"
-    ),
+fn parse_instruction(
+  input: &str,
+  line: &str,
+) -> Result<Instruction, ParseError> {
+  let mut chars = line.chars();
+
+  // turns are either counter-clockwise (L) or clockwise (R)
+  let dir = match chars.next() {
+    Some('L') => Turn::Left,
+    Some('R') => Turn::Right,
+    Some(c) => return Err(ParseError::at(input, line, Reason::Unexpected(c))),
+    None => return Err(ParseError::at(input, line, Reason::UnexpectedEnd)),
   };
 
-  let amount = u16::from_str_radix(num_str, 10).unwrap();
+  let amount = number::<u16>(input, chars.as_str())?;
 
-  Instruction { dir, amount }
+  Ok(Instruction { dir, amount })
 }
 
 
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+/// Checks every instruction up front; the solvers re-read them lazily.
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  for line in input.lines() {
+    let line = line.trim();
+    if !line.is_empty() {
+      parse_instruction(input, line)?;
+    }
+  }
+
+  Ok(Problem { input })
 }
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 pub mod part1_impl {
-  use super::{parse_instruction, Problem, Turn};
+  use super::{
+    Event, Instruction, NoTrace, Problem, TraceSink, Turn, parse_instruction,
+  };
+
+  /// The dial after `instruction`.
+  pub(crate) fn turn(pos: i16, instruction: &Instruction) -> i16 {
+    (pos
+      + match instruction.dir {
+        Turn::Left => -((instruction.amount % 100) as i16),
+        Turn::Right => (instruction.amount % 100) as i16,
+      })
+      % 100
+  }
 
   pub fn part1(p: &Problem) -> u64 {
+    part1_traced(p, &mut NoTrace)
+  }
+
+  /// `part1`, reporting each line and the dial after it to `sink`.
+  pub fn part1_traced(p: &Problem, sink: &mut impl TraceSink) -> u64 {
     let mut pos: i16 = 50;
     let mut zeros = 0;
 
     for raw in p.input.lines() {
-      #[cfg(feature = "std")]
-      eprintln!("Processing line: '{}'\n", raw);
+      sink.event(Event::Line(raw));
       let line = raw.trim();
       if line.is_empty() {
         continue;
       }
 
-      let instruction = parse_instruction(line);
+      let Ok(instruction) = parse_instruction(p.input, line) else {
+        continue; // unreachable: `parse` checked every line
+      };
 
-      pos = (pos
-        + match instruction.dir {
-          Turn::Left => -1 * (instruction.amount % 100) as i16,
-          Turn::Right => (instruction.amount % 100) as i16,
-        })
-        % 100;
+      pos = turn(pos, &instruction);
+      sink.event(Event::State { name: "dial", value: pos.into() });
 
       if pos == 0 {
         zeros += 1;
@@ -81,8 +119,8 @@ pub mod part1_impl {
   }
 }
 
-#[cfg(not(feature = "part2"))]
-pub use part1_impl::part1;
+#[cfg(feature = "part1")]
+pub use part1_impl::{part1, part1_traced};
 
 // --------------------------
 // Solver — Part 2
@@ -90,60 +128,195 @@ pub use part1_impl::part1;
 
 #[cfg(feature = "part2")]
 pub fn part2(p: &Problem) -> u64 {
+  part2_traced(p, &mut NoTrace)
+}
+
+/// `part2`, reporting each line, the zeros it passes and the dial after it
+/// to `sink`.
+#[cfg(feature = "part2")]
+pub fn part2_traced(p: &Problem, sink: &mut impl TraceSink) -> u64 {
   let mut pos: i16 = 50;
   let mut zeros = 0;
 
   for raw in p.input.lines() {
-    #[cfg(feature = "std")]
-    eprintln!("Processing line: '{}'\n", raw);
+    sink.event(Event::Line(raw));
     let line = raw.trim();
     if line.is_empty() {
       continue;
     }
 
-    let instruction = parse_instruction(line);
-
-    let dir = &instruction.dir;
-    let movement = instruction.amount as i16;
-
-    let mut first = match dir {
-      Turn::Right => 100 - pos,
-      Turn::Left => pos,
+    let Ok(instruction) = parse_instruction(p.input, line) else {
+      continue; // unreachable: `parse` checked every line
     };
 
-    // you only see 0 in passing after a full turn
-    if first == 0 {
-      first = 100;
+    let passes;
+    (pos, passes) = turn_counting(pos, &instruction);
+    zeros += passes;
+
+    if passes > 0 {
+      sink.event(Event::Value { line: raw, value: passes });
     }
+    sink.event(Event::State { name: "dial", value: pos.into() });
+  }
 
-    let passes = if movement < first {
-      0
-    } else {
-      1 + (movement - first) / 100
-    };
+  zeros
+}
 
-    zeros += passes as u64;
+/// The dial after `instruction`, and how many times it passed zero.
+#[cfg(feature = "part2")]
+fn turn_counting(pos: i16, instruction: &Instruction) -> (i16, u64) {
+  let dir = &instruction.dir;
+  // a `u16` amount runs past `i16::MAX`
+  let movement = i32::from(instruction.amount);
+
+  let mut first = i32::from(match dir {
+    Turn::Right => 100 - pos,
+    Turn::Left => pos,
+  });
+
+  // you only see 0 in passing after a full turn
+  if first == 0 {
+    first = 100;
+  }
 
-    #[cfg(feature = "std")]
-    let old_pos = pos;
+  let passes = if movement < first {
+    0
+  } else {
+    1 + (movement - first) / 100
+  };
 
-    pos = (pos
-      + match instruction.dir {
-        Turn::Left => -((instruction.amount % 100) as i16),
-        Turn::Right => (instruction.amount % 100) as i16,
-      })
-    .rem_euclid(100);
+  let pos = (pos
+    + match instruction.dir {
+      Turn::Left => -((instruction.amount % 100) as i16),
+      Turn::Right => (instruction.amount % 100) as i16,
+    })
+  .rem_euclid(100);
 
-    #[cfg(feature = "std")]
-    if passes > 0 {
-      eprintln!(
-        "Processing line: '{}' found {} passes ({} -> {})\n",
-        raw, passes, old_pos, pos
-      );
+  (pos, passes as u64)
+}
+
+// --------------------------
+// Stream
+// --------------------------
+
+// longest line a stream holds; rotations are a letter and a `u16`
+#[cfg(all(feature = "part1", feature = "part2"))]
+const LINE_BYTES: usize = 32;
+
+/// Either part fed a chunk at a time; only the dial is kept between lines.
+#[cfg(all(feature = "part1", feature = "part2"))]
+pub struct Day1Stream {
+  lines: Lines<LINE_BYTES>,
+  dial: Dial,
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+struct Dial {
+  part: Part,
+  pos: i16,
+  zeros: u64,
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Dial {
+  fn line(&mut self, raw: &str) -> Result<(), Error> {
+    let line = raw.trim();
+    if line.is_empty() {
+      return Ok(());
     }
+
+    let instruction = parse_instruction(raw, line)?;
+    match self.part {
+      Part::One => {
+        self.pos = part1_impl::turn(self.pos, &instruction);
+        self.zeros += (self.pos == 0) as u64;
+      }
+      Part::Two => {
+        let passes;
+        (self.pos, passes) = turn_counting(self.pos, &instruction);
+        self.zeros += passes;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Day1Stream {
+  pub const fn new(part: Part) -> Self {
+    Day1Stream { lines: Lines::new(), dial: Dial { part, pos: 50, zeros: 0 } }
   }
+}
 
-  zeros
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Stream for Day1Stream {
+  type Answer = u64;
+
+  fn feed(&mut self, chunk: &[u8]) -> Result<(), Error> {
+    self.lines.feed(chunk, |line| self.dial.line(line))
+  }
+
+  fn finish(mut self) -> Result<u64, Error> {
+    self.lines.finish(|line| self.dial.line(line))?;
+    Ok(self.dial.zeros)
+  }
+}
+
+/// The dial and any half-received line, so a stream outlives a power loss.
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Checkpoint for Day1Stream {
+  type Context = ();
+  const TAG: [u8; 4] = *b"1str";
+  const FIELDS: usize = 1 + 8 + 8 + Lines::<LINE_BYTES>::FIELDS;
+
+  fn write(&self, w: &mut Writer<'_>) -> Result<(), Error> {
+    w.part(self.dial.part)?;
+    w.i64(self.dial.pos.into())?;
+    w.u64(self.dial.zeros)?;
+    self.lines.write(w)
+  }
+
+  fn read(_: (), r: &mut Reader<'_>) -> Result<Self, Error> {
+    let part = r.part()?;
+    let pos = i16::try_from(r.i64()?)
+      .ok()
+      .filter(|pos| (-99..100).contains(pos))
+      .ok_or(Error::Checkpoint("dial out of range"))?;
+    let zeros = r.u64()?;
+
+    Ok(Day1Stream {
+      lines: Lines::read((), r)?,
+      dial: Dial { part, pos, zeros },
+    })
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day1;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day1 {
+  const DAY: u8 = 1;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, ParseError> {
+    let answer = match part {
+      Part::One => part1(p),
+      Part::Two => part2(p),
+    };
+
+    Ok(answer.into())
+  }
 }
 
 
@@ -152,11 +325,11 @@ mod tests {
   use super::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let sample = include_str!("../sample.txt");
 
-    let p = parse(sample);
+    let p = parse(sample).unwrap();
     let answer = part1(&p);
 
     assert_eq!(answer, 3);
@@ -166,8 +339,24 @@ mod tests {
   #[cfg(feature = "part2")]
   fn test_part2() {
     let sample = include_str!("../sample.txt");
-    let problem = parse(sample);
+    let problem = parse(sample).unwrap();
     let result = part2(&problem);
     assert_eq!(result, 6);
+
+    // amounts past `i16::MAX` still count every full turn
+    assert_eq!(part2(&parse("R40000\n").unwrap()), 400);
+    assert_eq!(part2(&parse("L65535\n").unwrap()), 655);
+  }
+
+  #[test]
+  fn test_parse_error() {
+    let err = parse("L68\nR3x\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 2, column: 3, reason: Reason::ExpectedNumber }
+    );
+
+    let err = parse("L68\nU30\n").err().unwrap();
+    assert_eq!(err.reason, Reason::Unexpected('U'));
   }
 }
@@ -1,25 +1,20 @@
+use aoc_core::ParseError;
+#[cfg(not(feature = "trace"))]
+use aoc_core::trace::NoTrace as Sink;
+#[cfg(feature = "trace")]
+use aoc_core::trace::Stderr as Sink;
 use day_1::*;
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?;
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+  let p1 = part1_traced(&problem, &mut Sink);
+  println!("Part 1: {p1}");
 
+  let p2 = part2_traced(&problem, &mut Sink);
+  println!("Part 2: {p2}");
 
-fn main() {
-  let problem = parse(INPUT);
-
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&problem);
-    println!("Part 1: {p1}");
-  }
-
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&problem);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
@@ -0,0 +1,13 @@
+use fastrand::Rng;
+
+/// `n` rotations of any `u16` number of clicks either way, seeded.
+pub fn rotations(seed: u64, n: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let lines: Vec<String> = (0..n)
+    .map(|_| {
+      let dir = if rng.bool() { 'L' } else { 'R' };
+      format!("{dir}{}", rng.u16(..))
+    })
+    .collect();
+  lines.join("\n")
+}
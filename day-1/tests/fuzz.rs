@@ -0,0 +1,22 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking. Crashing inputs are minimized and saved back
+// into `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver};
+use day_1::Day1;
+
+fn target(input: &str) {
+  let Ok(p) = Day1::parse(input) else {
+    return;
+  };
+  for part in [Part::One, Part::Two] {
+    let _ = Day1::solve(part, &p, ());
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
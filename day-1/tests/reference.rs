@@ -0,0 +1,78 @@
+// Differential tests: random rotations go through the `Solver` and through a
+// naive std reference that turns the dial one click at a time.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver};
+use day_1::Day1;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  /// Every click the dial makes, as the position it lands on.
+  fn clicks(input: &str) -> impl Iterator<Item = i64> + '_ {
+    let mut pos = 50i64;
+    input
+      .lines()
+      .filter(|l| !l.trim().is_empty())
+      .flat_map(move |line| {
+        let (dir, amount) = line.trim().split_at(1);
+        let step = if dir == "L" { -1 } else { 1 };
+        let amount: i64 = amount.parse().unwrap();
+        let start = pos;
+        pos = (pos + step * amount).rem_euclid(100);
+        (1..=amount).map(move |i| (start + step * i).rem_euclid(100))
+      })
+  }
+
+  pub fn part1(input: &str) -> u64 {
+    let mut pos = 50i64;
+    let mut zeros = 0;
+    for line in input.lines().filter(|l| !l.trim().is_empty()) {
+      let (dir, amount) = line.trim().split_at(1);
+      let amount: i64 = amount.parse().unwrap();
+      pos += if dir == "L" { -amount } else { amount };
+      if pos.rem_euclid(100) == 0 {
+        zeros += 1;
+      }
+    }
+    zeros
+  }
+
+  pub fn part2(input: &str) -> u64 {
+    clicks(input).filter(|&p| p == 0).count() as u64
+  }
+}
+
+/// One rotation per line; amounts cover all of `u16`, zero and hundreds of
+/// full turns included.
+fn rotations() -> impl Strategy<Value = String> {
+  let rotation = (prop::bool::ANY, any::<u16>())
+    .prop_map(|(left, n)| format!("{}{n}", if left { 'L' } else { 'R' }));
+  prop::collection::vec(rotation, 0..12).prop_map(|lines| lines.join("\n"))
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day1::parse(input).unwrap();
+  Day1::solve(part, &p, ()).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in rotations()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in rotations()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::rotations(seed, 10);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
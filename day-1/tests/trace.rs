@@ -0,0 +1,40 @@
+// Trace events tell the same story as the answers they lead to.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::trace::Collect;
+use day_1::*;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn count(events: &[String], event: &str) -> u64 {
+  events.iter().filter(|e| *e == event).count() as u64
+}
+
+#[test]
+fn test_part1_trace() {
+  let p = parse(INPUT).unwrap();
+  let mut sink = Collect::default();
+  let answer = part1_traced(&p, &mut sink);
+
+  assert_eq!(answer, part1(&p));
+  assert_eq!(sink.events[..2], ["line \"L68\"", "dial = -18"]);
+  assert_eq!(count(&sink.events, "dial = 0"), answer);
+}
+
+#[test]
+fn test_part2_trace() {
+  let p = parse(INPUT).unwrap();
+  let mut sink = Collect::default();
+  let answer = part2_traced(&p, &mut sink);
+
+  assert_eq!(answer, part2(&p));
+  assert_eq!(
+    sink.events[..3],
+    ["line \"L68\"", "\"L68\" => 1", "dial = 82"]
+  );
+  let passes = sink.events.iter().filter_map(|e| e.split_once(" => "));
+  assert_eq!(
+    passes.map(|(_, n)| n.parse::<u64>().unwrap()).sum::<u64>(),
+    answer
+  );
+}
@@ -0,0 +1,38 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_10::prelude::*;
+use divan::{Bencher, black_box};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Buttons per machine, over 20 machines of 8 lights.
+const SIZES: &[usize] = &[2, 4, 6, 8, 10];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, buttons: usize) {
+  let input = synth::machines(SEED, 20, 8, buttons);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part1(&p));
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, buttons: usize) {
+  let input = synth::machines(SEED, 20, 8, buttons);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part2(&p));
+  });
+}
+
+fn main() {
+  divan::main();
+}
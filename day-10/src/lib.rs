@@ -3,15 +3,41 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use core::fmt;
+
+use aoc_core::parse::number;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::stream::{Lines, Stream};
+use aoc_core::work::Counters;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::work::NoTally;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Error, Part, Solver};
+use aoc_core::{ParseError, Reason};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day10, Problem, Stats, parse};
+
+  #[cfg(all(feature = "part1", feature = "part2"))]
+  pub use crate::Day10Stream;
 
-  #[cfg(not(feature = "part2"))]
+  #[cfg(all(feature = "part1", feature = "parallel"))]
+  pub use crate::part1_impl::part1_parallel;
+  #[cfg(feature = "part1")]
   pub use crate::part1_impl::part1;
+  #[cfg(all(feature = "part1", feature = "instrument"))]
+  pub use crate::part1_impl::part1_instrumented;
 
+  #[cfg(all(feature = "part2", feature = "parallel"))]
+  pub use crate::part2_impl::part2_parallel;
   #[cfg(feature = "part2")]
-  pub use crate::part2_impl::part2;
+  pub use crate::part2_impl::{Part2, part2};
+  #[cfg(all(feature = "part2", feature = "instrument"))]
+  pub use crate::part2_impl::part2_instrumented;
 }
 
 
@@ -19,6 +45,7 @@ pub mod prelude {
 // Data Model
 // --------------------------
 
+#[derive(Clone, Copy)]
 pub struct Problem<'a> {
   pub input: &'a str,
 }
@@ -26,78 +53,229 @@ pub struct Problem<'a> {
 const MAX_BUTTONS: usize = 15;
 const MAX_LIGHTS: usize = 10;
 
+/// One machine: its light diagram, buttons and joltage requirements.
+pub struct Machine {
+  pub num_lights: usize,
+  pub num_buttons: usize,
+  pub num_counters: usize,
+  pub button_masks: [u16; MAX_BUTTONS],
+  pub target_mask: u16,
+  pub target: [i64; MAX_LIGHTS],
+}
 
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+// cost buckets: `cost <= 1`, then powers of ten from `< 100` to `>= 100M`
+const COST_BUCKETS: usize = 9;
+
+/// Work a part did across all machines: the rank of each machine's
+/// Gaussian elimination, how many free variables it left, and how many
+/// candidates its search tried.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+  pub machines: u32,
+  /// Machines by elimination rank.
+  pub rank: [u32; MAX_LIGHTS + 1],
+  /// Machines by free variable count.
+  pub free: [u32; MAX_BUTTONS + 1],
+  /// Machines by search cost, bucketed as `Display` labels them.
+  pub cost: [u32; COST_BUCKETS],
+  pub total_cost: u64,
+  pub max_cost: u64,
+  // cost of the machine being searched
+  current: u64,
 }
 
+impl Counters for Stats {}
 
-// --------------------------
-// Solver — Part 1
-// --------------------------
-#[cfg(not(feature = "part2"))]
-mod part1_impl {
-  use super::{MAX_BUTTONS, MAX_LIGHTS, Problem};
+impl Stats {
+  /// Counts one candidate tried by the current machine's search.
+  fn candidate(&mut self) {
+    self.current += 1;
+  }
 
-  type Row = u16;
+  /// Closes the current machine, whose elimination had `rank` and left
+  /// `free` free variables.
+  fn machine(&mut self, rank: usize, free: usize) {
+    let cost = core::mem::take(&mut self.current);
+    let bucket = match cost {
+      0..=1 => 0,
+      _ => (cost.ilog10() as usize).clamp(1, COST_BUCKETS - 1),
+    };
 
+    self.machines += 1;
+    self.rank[rank] += 1;
+    self.free[free] += 1;
+    self.cost[bucket] += 1;
+    self.total_cost += cost;
+    self.max_cost = self.max_cost.max(cost);
+  }
+}
+
+impl fmt::Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    const LABELS: [&str; COST_BUCKETS] = [
+      "cost <= 1",
+      "1 < cost < 100",
+      "100 <= cost < 1K",
+      "1K <= cost < 10K",
+      "10K <= cost < 100K",
+      "100K <= cost < 1M",
+      "1M <= cost < 10M",
+      "10M <= cost < 100M",
+      "cost >= 100M",
+    ];
+
+    writeln!(f, "=== Cost Distribution ===")?;
+    for (label, &n) in LABELS.iter().zip(&self.cost).filter(|(_, n)| **n > 0) {
+      writeln!(f, "{n:3} {label}")?;
+    }
+
+    writeln!(f, "\n=== Rank Distribution ===")?;
+    for (rank, &n) in self.rank.iter().enumerate().filter(|(_, n)| **n > 0) {
+      writeln!(f, "rank = {rank}: {n:3} cases")?;
+    }
+
+    writeln!(f, "\n=== Free Variable Distribution ===")?;
+    for (free, &n) in self.free.iter().enumerate().filter(|(_, n)| **n > 0) {
+      writeln!(f, "free = {free}: {n:3} cases")?;
+    }
 
-  struct Machine {
-    num_lights: u8,
-    num_buttons: u8,
-    button_masks: [u16; MAX_BUTTONS],
-    target_mask: u16,
+    let mean = self.total_cost / u64::from(self.machines.max(1));
+    writeln!(f, "\n=== Statistics ===")?;
+    writeln!(f, "Total cases: {}", self.machines)?;
+    writeln!(f, "Max cost: {}", self.max_cost)?;
+    writeln!(f, "Average cost: {mean}")
   }
+}
 
+// --------------------------
+// Parse
+// --------------------------
 
-  fn parse_machine(line: &str) -> Machine {
-    let mut parts = line.split_whitespace();
-    let pattern = parts.next().expect("missing pattern");
+/// Strips the `open`/`close` delimiters off `tok`, a sub-slice of `input`.
+fn delimited<'a>(
+  input: &str,
+  tok: &'a str,
+  open: char,
+  close: char,
+) -> Result<&'a str, ParseError> {
+  let inner = tok
+    .strip_prefix(open)
+    .ok_or_else(|| ParseError::at(input, tok, Reason::Expected(open)))?;
+
+  inner.strip_suffix(close).ok_or_else(|| {
+    ParseError::at(input, &tok[tok.len()..], Reason::Expected(close))
+  })
+}
 
-    let inner = &pattern[1..pattern.len() - 1];
-    let num_lights = inner.len() as u8;
+/// Parses `[.##.] (3) (1,3) {3,5,4,7}`; `line` is a sub-slice of `input`.
+fn parse_machine(input: &str, line: &str) -> Result<Machine, ParseError> {
+  let mut tokens = line.split_whitespace();
 
-    let target_mask = inner
-      .bytes()
-      .enumerate()
-      .fold(0u16, |acc, (i, b)| acc | ((b == b'#') as u16) << i);
+  let pattern = tokens.next().unwrap_or(line);
+  let lights = delimited(input, pattern, '[', ']')?;
+  if lights.len() > MAX_LIGHTS {
+    let reason = Reason::Malformed("more than 10 indicator lights");
+    return Err(ParseError::at(input, lights, reason));
+  }
 
-    let mut button_masks = [0u16; MAX_BUTTONS];
-    let mut num_buttons = 0u8;
+  let mut target_mask = 0u16;
+  for (i, c) in lights.char_indices() {
+    match c {
+      '#' => target_mask |= 1 << i,
+      '.' => {}
+      _ => {
+        return Err(ParseError::at(input, &lights[i..], Reason::Unexpected(c)));
+      }
+    }
+  }
 
-    for group in parts {
-      if group.starts_with('{') {
-        break;
+  let mut button_masks = [0u16; MAX_BUTTONS];
+  let mut target = [0i64; MAX_LIGHTS];
+  let (mut num_buttons, mut num_counters) = (0, 0);
+
+  for tok in tokens {
+    if tok.starts_with('(') {
+      if num_buttons == MAX_BUTTONS {
+        let reason = Reason::Malformed("more than 15 buttons");
+        return Err(ParseError::at(input, tok, reason));
       }
 
-      let inner = &group[1..group.len() - 1];
-      let mask = if inner.is_empty() {
-        0
-      } else {
-        inner
-          .split(',')
-          .fold(0u16, |acc, s| acc | (1 << s.parse::<u8>().unwrap()))
-      };
+      let wires = delimited(input, tok, '(', ')')?;
+      let mut mask = 0u16;
+      for field in wires.split(',').filter(|f| !f.is_empty()) {
+        let light = number::<usize>(input, field)?;
+        if light >= MAX_LIGHTS {
+          return Err(ParseError::at(input, field, Reason::OutOfRange));
+        }
+        mask |= 1 << light;
+      }
 
-      button_masks[num_buttons as usize] = mask;
+      button_masks[num_buttons] = mask;
       num_buttons += 1;
+    } else {
+      let joltages = delimited(input, tok, '{', '}')?;
+      for (i, field) in joltages.split(',').enumerate() {
+        if i == MAX_LIGHTS {
+          let reason = Reason::Malformed("more than 10 joltage counters");
+          return Err(ParseError::at(input, field, reason));
+        }
+        // small and non-negative, so elimination stays well inside i64
+        target[i] = number::<u16>(input, field)?.into();
+        num_counters = i + 1;
+      }
     }
+  }
 
-    Machine { num_lights, num_buttons, button_masks, target_mask }
+  Ok(Machine {
+    num_lights: lights.len(),
+    num_buttons,
+    num_counters,
+    button_masks,
+    target_mask,
+    target,
+  })
+}
+
+/// Checks every machine up front; the solvers re-read them lazily.
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+    parse_machine(input, line)?;
+  }
+
+  Ok(Problem { input })
+}
+
+impl<'a> Problem<'a> {
+  /// One machine per non-blank line, already validated by `parse`.
+  pub fn machines(&self) -> impl Iterator<Item = Machine> + use<'a> {
+    let input = self.input;
+    input
+      .lines()
+      .map(str::trim)
+      .filter(|l| !l.is_empty())
+      .filter_map(move |l| parse_machine(input, l).ok())
   }
+}
+
+
+// --------------------------
+// Solver — Part 1
+// --------------------------
+#[cfg(feature = "part1")]
+mod part1_impl {
+  use aoc_core::work::{NoTally, Tally};
+
+  use super::{MAX_BUTTONS, MAX_LIGHTS, Machine, Problem, Stats};
+
+  type Row = u16;
+
 
   fn build_augmented_matrix(m: &Machine, rows: &mut [Row; MAX_LIGHTS]) {
-    for (light, row_slot) in
-      rows.iter_mut().enumerate().take(m.num_lights as usize)
-    {
+    for (light, row_slot) in rows.iter_mut().enumerate().take(m.num_lights) {
       let mut row = 0u16;
 
       // Button coefficients
-      for (btn, &mask) in m
-        .button_masks
-        .iter()
-        .take(m.num_buttons as usize)
-        .enumerate()
+      for (btn, &mask) in m.button_masks.iter().take(m.num_buttons).enumerate()
       {
         if (mask >> light) & 1 == 1 {
           row |= 1 << btn;
@@ -159,6 +337,7 @@ mod part1_impl {
     pivot_col: &[i8; MAX_LIGHTS],
     rank: u8,
     num_buttons: usize,
+    tally: &mut impl Tally<Stats>,
   ) -> u32 {
     let rhs_bit = num_buttons;
 
@@ -191,11 +370,13 @@ mod part1_impl {
 
     // Find minimum weight solution
     if num_free == 0 {
+      tally.tally(Stats::candidate);
       return particular.count_ones();
     }
 
     (0..1u32 << num_free)
       .map(|mask| {
+        tally.tally(Stats::candidate);
         let mut sol = particular;
 
         for (i, &basis) in nullspace.iter().take(num_free).enumerate() {
@@ -211,78 +392,63 @@ mod part1_impl {
   }
 
   pub fn part1(p: &Problem) -> u64 {
-    let mut total = 0u64;
+    part1_tallied(p, &mut NoTally)
+  }
 
-    for line in p.input.lines() {
-      let trimmed = line.trim();
-      if trimmed.is_empty() {
-        continue;
-      }
+  /// `part1`, with the machines split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part1_parallel(p: &Problem, threads: usize) -> u64 {
+    aoc_core::parallel::sum_lines(p.input, threads, |input| {
+      part1(&Problem { input })
+    })
+  }
+
+  /// `part1`, with the work it did per machine.
+  #[cfg(feature = "instrument")]
+  pub fn part1_instrumented(p: &Problem) -> (u64, Stats) {
+    let mut stats = Stats::default();
+    (part1_tallied(p, &mut stats), stats)
+  }
 
-      let machine = parse_machine(trimmed);
-      let mut rows = [0u16; MAX_LIGHTS];
+  fn part1_tallied(p: &Problem, tally: &mut impl Tally<Stats>) -> u64 {
+    p.machines().map(|m| min_presses(&m, tally)).sum()
+  }
 
-      build_augmented_matrix(&machine, &mut rows);
+  /// Fewest presses that light `machine`'s pattern.
+  pub fn min_presses(machine: &Machine, tally: &mut impl Tally<Stats>) -> u64 {
+    let mut rows = [0u16; MAX_LIGHTS];
 
-      let (rank, pivot_col, inconsistent) = gaussian_elimination(
-        &mut rows,
-        machine.num_lights as usize,
-        machine.num_buttons as usize,
-      );
+    build_augmented_matrix(machine, &mut rows);
 
-      if inconsistent {
-        total += u32::MAX as u64;
-      } else {
-        let min_presses = solve_linear_system(
-          &rows,
-          &pivot_col,
-          rank,
-          machine.num_buttons as usize,
-        );
-        total += min_presses as u64;
-      }
-    }
+    let (rank, pivot_col, inconsistent) =
+      gaussian_elimination(&mut rows, machine.num_lights, machine.num_buttons);
+
+    let presses = if inconsistent {
+      u32::MAX as u64
+    } else {
+      solve_linear_system(&rows, &pivot_col, rank, machine.num_buttons, tally)
+        as u64
+    };
+
+    let rank = rank as usize;
+    tally.tally(|s| s.machine(rank, machine.num_buttons - rank));
 
-    total
+    presses
   }
 }
 
 #[cfg(feature = "part2")]
 mod part2_impl {
-  use super::{MAX_BUTTONS, MAX_LIGHTS, Problem};
+  use core::str::Lines;
 
-  struct Machine {
-    num_buttons: usize,
-    num_counters: usize,
-    button_masks: [u16; MAX_BUTTONS],
-    target: [i64; MAX_LIGHTS],
-  }
-
-  fn parse_machine(line: &str) -> Machine {
-    let mut button_masks = [0u16; MAX_BUTTONS];
-    let mut target = [0i64; MAX_LIGHTS];
-    let (mut num_buttons, mut num_counters) = (0, 0);
-    for tok in line.split_whitespace() {
-      if tok.starts_with('(') {
-        let inner = &tok[1..tok.len() - 1];
-        let mut mask = 0u16;
-        if !inner.is_empty() {
-          for p in inner.split(',') {
-            mask |= 1 << p.parse::<u8>().unwrap();
-          }
-        }
-        button_masks[num_buttons] = mask;
-        num_buttons += 1;
-      } else if tok.starts_with('{') {
-        let inner = &tok[1..tok.len() - 1];
-        for (i, s) in inner.split(',').enumerate() {
-          target[i] = s.parse().unwrap();
-          num_counters = i + 1;
-        }
-      }
-    }
-    Machine { num_buttons, num_counters, button_masks, target }
-  }
+  use aoc_core::checkpoint::{Checkpoint, Reader, Writer};
+  use aoc_core::resume::{Poll, Resumable};
+  use aoc_core::work::{NoTally, Tally};
+  use aoc_core::{Error, input_hash};
+
+  use super::{
+    MAX_BUTTONS, MAX_LIGHTS, Machine, Problem, Stats, parse_machine,
+  };
 
   fn gcd(mut a: i64, mut b: i64) -> i64 {
     a = a.abs();
@@ -444,133 +610,459 @@ mod part2_impl {
     Some(sum)
   }
 
-  fn solve(m: &Machine) -> u32 {
-    let (rows, cols) = (m.num_counters, m.num_buttons);
-    let mut a = [[0i64; MAX_BUTTONS]; MAX_LIGHTS];
-    let mut b = [0i64; MAX_LIGHTS];
+  /// One machine's search over its free variables, kept as an explicit
+  /// stack so it can stop after any node and carry on from there.
+  struct Search {
+    a: [[i64; MAX_BUTTONS]; MAX_LIGHTS],
+    b: [i64; MAX_LIGHTS],
+    cols: usize,
+    pivot_col: [i32; MAX_LIGHTS],
+    rank: usize,
+    is_pivot: [bool; MAX_BUTTONS],
+    free: [usize; MAX_BUTTONS],
+    nf: usize,
+    max_v: i64,
+    x: [i64; MAX_BUTTONS],
+    best: u32,
+    // the free variable being set, the value each one tries next, and the
+    // presses fixed above each depth
+    depth: usize,
+    next: [i64; MAX_BUTTONS],
+    sums: [i64; MAX_BUTTONS + 1],
+    done: bool,
+  }
 
-    for r in 0..rows {
-      b[r] = m.target[r];
-      for (c, val) in a[r].iter_mut().enumerate().take(cols) {
-        *val = ((m.button_masks[c] >> r) & 1) as i64;
+  impl Search {
+    /// An ended search, to be `start`ed.
+    const fn new() -> Self {
+      Search {
+        a: [[0; MAX_BUTTONS]; MAX_LIGHTS],
+        b: [0; MAX_LIGHTS],
+        cols: 0,
+        pivot_col: [0; MAX_LIGHTS],
+        rank: 0,
+        is_pivot: [false; MAX_BUTTONS],
+        free: [0; MAX_BUTTONS],
+        nf: 0,
+        max_v: 0,
+        x: [0; MAX_BUTTONS],
+        best: u32::MAX,
+        depth: 0,
+        next: [0; MAX_BUTTONS],
+        sums: [0; MAX_BUTTONS + 1],
+        done: true,
       }
     }
 
-    let (rank, pivot_col, bad) = eliminate_rref(&mut a, &mut b, rows, cols);
-    if bad {
-      return u32::MAX;
+    /// Eliminates `m`'s system in place, ready to search; `Err` holds its
+    /// rank and free variables if it has no solution.
+    fn start(&mut self, m: &Machine) -> Result<(), (usize, usize)> {
+      let (rows, cols) = (m.num_counters, m.num_buttons);
+      self.a = [[0; MAX_BUTTONS]; MAX_LIGHTS];
+      self.b = [0; MAX_LIGHTS];
+
+      for r in 0..rows {
+        self.b[r] = m.target[r];
+        for (c, val) in self.a[r].iter_mut().enumerate().take(cols) {
+          *val = ((m.button_masks[c] >> r) & 1) as i64;
+        }
+      }
+
+      let (rank, pivot_col, bad) =
+        eliminate_rref(&mut self.a, &mut self.b, rows, cols);
+      if bad {
+        return Err((rank, cols - rank));
+      }
+
+      self.is_pivot = [false; MAX_BUTTONS];
+      for &pc in &pivot_col[..rank] {
+        if pc >= 0 {
+          self.is_pivot[pc as usize] = true;
+        }
+      }
+
+      self.nf = 0;
+      for (c, &is_p) in self.is_pivot.iter().enumerate().take(cols) {
+        if !is_p {
+          self.free[self.nf] = c;
+          self.nf += 1;
+        }
+      }
+
+      self.cols = cols;
+      self.pivot_col = pivot_col;
+      self.rank = rank;
+      self.max_v = (0..rows).map(|r| m.target[r]).max().unwrap_or(0);
+      self.x = [0; MAX_BUTTONS];
+      self.best = u32::MAX;
+      self.depth = 0;
+      self.next[0] = 0;
+      self.sums[0] = 0;
+      self.done = false;
+      Ok(())
     }
 
-    let mut is_pivot = [false; MAX_BUTTONS];
-    for r in 0..rank {
-      if pivot_col[r] >= 0 {
-        is_pivot[pivot_col[r] as usize] = true;
+    /// Visits nodes until the search ends or `budget` runs out, counting
+    /// each off it; the fewest presses once it ends.
+    fn step(
+      &mut self,
+      budget: &mut u64,
+      tally: &mut impl Tally<Stats>,
+    ) -> Option<u32> {
+      while !self.done {
+        if *budget == 0 {
+          return None;
+        }
+        *budget -= 1;
+
+        let j = self.depth;
+        if j == self.nf {
+          tally.tally(Stats::candidate);
+          if let Some(s) = back_sub(
+            &self.a,
+            &self.b,
+            self.cols,
+            &self.pivot_col,
+            self.rank,
+            &self.is_pivot,
+            &mut self.x,
+          ) {
+            let t = self.sums[j] + s;
+            if t >= 0 && (t as u32) < self.best {
+              self.best = t as u32;
+            }
+          }
+          self.pop(tally);
+          continue;
+        }
+
+        let v = self.next[j];
+        let ns = self.sums[j] + v;
+        if v > self.max_v || ns >= self.best as i64 {
+          self.x[self.free[j]] = 0;
+          self.pop(tally);
+        } else {
+          self.x[self.free[j]] = v;
+          self.sums[j + 1] = ns;
+          self.depth = j + 1;
+          if self.depth < self.nf {
+            self.next[self.depth] = 0;
+          }
+        }
+      }
+
+      Some(self.best)
+    }
+
+    /// Back up a depth to try its next value; the root ends the search.
+    fn pop(&mut self, tally: &mut impl Tally<Stats>) {
+      if self.depth == 0 {
+        self.done = true;
+        tally.tally(|s| s.machine(self.rank, self.nf));
+        return;
       }
+      self.depth -= 1;
+      self.next[self.depth] += 1;
     }
+  }
+
+  pub fn part2(p: &Problem) -> u64 {
+    part2_tallied(p, &mut NoTally)
+  }
 
-    let mut free = [0usize; MAX_BUTTONS];
-    let mut nf = 0;
+  /// `part2`, with the machines split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part2_parallel(p: &Problem, threads: usize) -> u64 {
+    aoc_core::parallel::sum_lines(p.input, threads, |input| {
+      part2(&Problem { input })
+    })
+  }
+
+  /// `part2`, with the work it did per machine.
+  #[cfg(feature = "instrument")]
+  pub fn part2_instrumented(p: &Problem) -> (u64, Stats) {
+    let mut stats = Stats::default();
+    (part2_tallied(p, &mut stats), stats)
+  }
+
+  // out of line, `Part2`'s search stack lives in this frame only, not in
+  // `Solver::solve`'s, which part 1 runs through too
+  #[inline(never)]
+  fn part2_tallied(p: &Problem, tally: &mut impl Tally<Stats>) -> u64 {
+    let mut unlimited = u64::MAX;
+    match Part2::new(p).step_tallied(&mut unlimited, tally) {
+      Poll::Ready(total) => total,
+      Poll::Pending => unreachable!("an unlimited budget runs to the end"),
+    }
+  }
 
-    for (c, &is_p) in is_pivot.iter().enumerate().take(cols) {
-      if !is_p {
-        free[nf] = c;
-        nf += 1;
+  /// Fewest presses that meet `m`'s joltages; 0 if none do. Only
+  /// `Day10Stream`, which needs both parts, solves one machine at a time.
+  #[cfg(all(feature = "part1", feature = "part2"))]
+  pub fn min_presses(m: &Machine, tally: &mut impl Tally<Stats>) -> u64 {
+    let mut unlimited = u64::MAX;
+    let mut search = Search::new();
+    match search.start(m) {
+      Ok(()) => search.step(&mut unlimited, tally).map_or(0, presses),
+      Err((rank, free)) => {
+        tally.tally(|s| s.machine(rank, free));
+        0
       }
     }
+  }
+
+  /// A search's best as presses, counting a machine it cannot solve as 0.
+  fn presses(best: u32) -> u64 {
+    if best == u32::MAX { 0 } else { best as u64 }
+  }
+
+  /// `part2` run a slice at a time. A unit is one node of a machine's
+  /// search, or setting up the next machine's.
+  pub struct Part2<'a> {
+    input: &'a str,
+    lines: Lines<'a>,
+    // machines taken from `lines`, and the last one's search, ended
+    // between machines
+    machines: usize,
+    search: Search,
+    total: u64,
+  }
 
-    let max_t = (0..rows).map(|r| m.target[r]).max().unwrap_or(0);
-
-    let mut best = u32::MAX;
-    let mut x = [0i64; MAX_BUTTONS];
-
-    struct DfsCtx<'a> {
-      free: &'a [usize],
-      max_v: i64,
-      a: &'a [[i64; MAX_BUTTONS]; MAX_LIGHTS],
-      b: &'a [i64; MAX_LIGHTS],
-      cols: usize,
-      pivot_col: &'a [i32; MAX_LIGHTS],
-      rank: usize,
-      is_pivot: &'a [bool; MAX_BUTTONS],
-      best: &'a mut u32,
-      x: &'a mut [i64; MAX_BUTTONS],
+  impl<'a> Part2<'a> {
+    pub fn new(p: &Problem<'a>) -> Self {
+      Part2 {
+        input: p.input,
+        lines: p.input.lines(),
+        machines: 0,
+        search: Search::new(),
+        total: 0,
+      }
     }
 
-    fn dfs(j: usize, ps: i64, ctx: &mut DfsCtx<'_>) {
-      if j == ctx.free.len() {
-        if let Some(s) = back_sub(
-          ctx.a,
-          ctx.b,
-          ctx.cols,
-          ctx.pivot_col,
-          ctx.rank,
-          ctx.is_pivot,
-          ctx.x,
-        ) {
-          let t = ps + s;
-          if t >= 0 && (t as u32) < *ctx.best {
-            *ctx.best = t as u32;
-          }
+    fn step_tallied(
+      &mut self,
+      budget: &mut u64,
+      tally: &mut impl Tally<Stats>,
+    ) -> Poll<u64> {
+      loop {
+        if !self.search.done {
+          let Some(best) = self.search.step(budget, tally) else {
+            return Poll::Pending;
+          };
+          self.total += presses(best);
+        }
+
+        if *budget == 0 {
+          return Poll::Pending;
+        }
+        *budget -= 1;
+
+        // `parse` validated every machine
+        let input = self.input;
+        let mut machines = self.lines.by_ref().map(str::trim);
+        let next = machines.find(|l| !l.is_empty());
+        let Some(m) = next.and_then(|l| parse_machine(input, l).ok()) else {
+          return Poll::Ready(self.total);
+        };
+
+        self.machines += 1;
+        if let Err((rank, free)) = self.search.start(&m) {
+          tally.tally(|s| s.machine(rank, free));
         }
-        return;
       }
+    }
+  }
 
-      let col = ctx.free[j];
+  impl Resumable for Part2<'_> {
+    type Answer = u64;
 
-      for v in 0..=ctx.max_v {
-        let ns = ps + v;
-        if ns >= *ctx.best as i64 {
-          break;
+    fn step(&mut self, budget: u64) -> Poll<u64> {
+      self.step_tallied(&mut budget.max(1), &mut NoTally)
+    }
+  }
+
+  /// How far through the input, and the current machine's search frontier.
+  /// Its elimination is redone on restore, from the same input: the
+  /// checkpoint holds that input's hash and is refused against any other.
+  impl<'a> Checkpoint for Part2<'a> {
+    type Context = Problem<'a>;
+    const TAG: [u8; 4] = *b"10p2";
+    const FIELDS: usize = 8 * 3 + 1 + 8 * 2 + 4 + 8 * (4 * MAX_BUTTONS + 1);
+
+    fn write(&self, w: &mut Writer<'_>) -> Result<(), Error> {
+      w.u64(input_hash(self.input))?;
+      w.usize(self.machines)?;
+      w.u64(self.total)?;
+
+      let search = &self.search;
+      w.bool(!search.done)?;
+      if search.done {
+        return Ok(());
+      }
+      w.usize(search.nf)?;
+      w.usize(search.depth)?;
+      w.u32(search.best)?;
+      for &c in &search.free {
+        w.usize(c)?;
+      }
+      for i in 0..MAX_BUTTONS {
+        w.i64(search.x[i])?;
+        w.i64(search.next[i])?;
+      }
+      for &sum in &search.sums {
+        w.i64(sum)?;
+      }
+      Ok(())
+    }
+
+    fn read(p: Problem<'a>, r: &mut Reader<'_>) -> Result<Self, Error> {
+      if r.u64()? != input_hash(p.input) {
+        return Err(Error::Checkpoint("saved for another input"));
+      }
+      let mut part2 = Part2::new(&p);
+      part2.machines = r.usize()?;
+      part2.total = r.u64()?;
+
+      // the same machines `step_tallied` took, the last one kept
+      let mut machines = part2.lines.by_ref().map(str::trim);
+      let mut last = None;
+      for _ in 0..part2.machines {
+        last = machines.find(|l| !l.is_empty());
+        if last.is_none() {
+          return Err(Error::Checkpoint("past the last machine"));
         }
+      }
+      if !r.bool()? {
+        return Ok(part2);
+      }
 
-        ctx.x[col] = v;
-        dfs(j + 1, ns, ctx);
+      let m = last.and_then(|l| parse_machine(p.input, l).ok());
+      let search = &mut part2.search;
+      if m.is_none_or(|m| search.start(&m).is_err()) {
+        return Err(Error::Checkpoint("no search to resume"));
       }
 
-      ctx.x[col] = 0;
+      let (nf, depth) = (r.usize()?, r.usize()?);
+      search.best = r.u32()?;
+      let mut free = [0; MAX_BUTTONS];
+      for c in &mut free {
+        *c = r.usize()?;
+      }
+      if nf != search.nf || free[..nf] != search.free[..nf] || depth > nf {
+        return Err(Error::Checkpoint("search does not fit its machine"));
+      }
+      search.depth = depth;
+      for i in 0..MAX_BUTTONS {
+        search.x[i] = r.i64()?;
+        search.next[i] = r.i64()?;
+      }
+      for sum in &mut search.sums {
+        *sum = r.i64()?;
+      }
+      Ok(part2)
     }
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day10;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day10 {
+  const DAY: u8 = 10;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+    parse(input)
+  }
 
-    let free_slice = &free[..nf];
-
-    let mut ctx = DfsCtx {
-      free: free_slice,
-      max_v: max_t,
-      a: &a,
-      b: &b,
-      cols,
-      pivot_col: &pivot_col,
-      rank,
-      is_pivot: &is_pivot,
-      best: &mut best,
-      x: &mut x,
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, ParseError> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p),
     };
 
-    dfs(0, 0, &mut ctx);
+    Ok(answer.into())
+  }
+}
+
+
+// --------------------------
+// Stream
+// --------------------------
+
+// longest line a stream holds: 15 buttons wired to every light, with room
+#[cfg(all(feature = "part1", feature = "part2"))]
+const LINE_BYTES: usize = 512;
+
+/// Either part fed a chunk at a time; each machine is solved as soon as its
+/// line completes.
+#[cfg(all(feature = "part1", feature = "part2"))]
+pub struct Day10Stream {
+  lines: Lines<LINE_BYTES>,
+  part: Part,
+  total: u64,
+}
 
-    best
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Day10Stream {
+  pub const fn new(part: Part) -> Self {
+    Day10Stream { lines: Lines::new(), part, total: 0 }
   }
+}
 
-  pub fn part2(p: &Problem) -> u64 {
-    p.input
-      .lines()
-      .filter(|l| !l.trim().is_empty())
-      .map(|l| {
-        let r = solve(&parse_machine(l.trim()));
-        if r == u32::MAX { 0 } else { r as u64 }
-      })
-      .sum()
+#[cfg(all(feature = "part1", feature = "part2"))]
+fn machine(part: Part, raw: &str) -> Result<u64, Error> {
+  let line = raw.trim();
+  if line.is_empty() {
+    return Ok(0);
+  }
+
+  let m = parse_machine(raw, line)?;
+  Ok(match part {
+    Part::One => part1_impl::min_presses(&m, &mut NoTally),
+    Part::Two => part2_impl::min_presses(&m, &mut NoTally),
+  })
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Stream for Day10Stream {
+  type Answer = u64;
+
+  fn feed(&mut self, chunk: &[u8]) -> Result<(), Error> {
+    let (part, total) = (self.part, &mut self.total);
+    self.lines.feed(chunk, |line| {
+      *total += machine(part, line)?;
+      Ok(())
+    })
+  }
+
+  fn finish(mut self) -> Result<u64, Error> {
+    let (part, total) = (self.part, &mut self.total);
+    self.lines.finish(|line| {
+      *total += machine(part, line)?;
+      Ok(())
+    })?;
+    Ok(self.total)
   }
 }
 
+
 #[cfg(test)]
 mod tests {
   use super::prelude::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part1(&problem);
     assert_eq!(result, 7);
   }
@@ -579,8 +1071,59 @@ mod tests {
   #[cfg(feature = "part2")]
   fn test_part2() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part2(&problem);
     assert_eq!(result, 33);
   }
+
+  #[test]
+  #[cfg(all(feature = "part1", feature = "instrument"))]
+  fn test_part1_instrumented() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap();
+    let (result, stats) = part1_instrumented(&problem);
+    assert_eq!(result, 7);
+
+    // every free variable doubles the candidates part 1 tries
+    assert_eq!(stats.machines, 3);
+    assert_eq!(stats.rank[..5], [0, 0, 0, 1, 2]);
+    assert_eq!(stats.free[..3], [0, 2, 1]);
+    assert_eq!(stats.cost[..2], [0, 3]);
+    assert_eq!((stats.total_cost, stats.max_cost), (8, 4));
+  }
+
+  #[test]
+  #[cfg(all(feature = "part2", feature = "instrument"))]
+  fn test_part2_instrumented() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap();
+    let (result, stats) = part2_instrumented(&problem);
+    assert_eq!(result, 33);
+
+    // pruning keeps each search well under (max + 1)^free candidates
+    assert_eq!(stats.machines, 3);
+    assert_eq!(stats.rank[..5], [0, 0, 0, 1, 2]);
+    assert_eq!(stats.free[..3], [0, 2, 1]);
+    assert_eq!((stats.total_cost, stats.max_cost), (72, 49));
+  }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{ParseError, Reason};
+
+    let err = parse("[.##.] (3) (1,x) {3,5,4,7}\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 1, column: 15, reason: Reason::ExpectedNumber }
+    );
+
+    let err = parse("[.##. (3) {3,5,4,7}\n").err().unwrap();
+    assert_eq!(err.reason, Reason::Expected(']'));
+
+    let err = parse("[.##.] (3) {3,-5,4,7}\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 1, column: 15, reason: Reason::OutOfRange }
+    );
+  }
 }
@@ -1,25 +1,16 @@
+use aoc_core::ParseError;
 use day_10::prelude::*;
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?;
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+  let p1 = part1(&problem);
+  println!("Part 1: {p1}");
 
+  let p2 = part2(&problem);
+  println!("Part 2: {p2}");
 
-fn main() {
-  let problem = parse(INPUT);
-
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&problem);
-    println!("Part 1: {p1}");
-  }
-
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&problem);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
@@ -0,0 +1,30 @@
+// The library is `no_std` without `alloc`: parsing and solving must never
+// reach the heap.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::audit::{Allocations, CountingAlloc, measure};
+use aoc_core::{Part, Solver};
+use day_10::prelude::Day10;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn solve(part: Part) -> Allocations {
+  let (_, seen) = measure(|| {
+    let p = Day10::parse(INPUT).unwrap();
+    Day10::solve(part, &p, ()).unwrap()
+  });
+  seen
+}
+
+#[test]
+fn test_allocations() {
+  for part in [Part::One, Part::Two] {
+    let seen = solve(part);
+    println!("{part:?}: {seen}");
+
+    assert_eq!(seen, Allocations::default(), "{part:?}");
+  }
+}
@@ -0,0 +1,43 @@
+use fastrand::Rng;
+
+/// `n` machines of `lights` lights and `buttons` buttons, each wired to a
+/// random non-empty set of lights. The light pattern and joltages are those
+/// some presses of the buttons reach, so every machine can be solved.
+pub fn machines(seed: u64, n: usize, lights: usize, buttons: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let lines: Vec<String> = (0..n)
+    .map(|_| {
+      let mut pattern = vec!['.'; lights];
+      let mut joltages = vec![0u32; lights];
+      let wires: Vec<Vec<usize>> = (0..buttons)
+        .map(|_| {
+          let mut wires: Vec<usize> =
+            (0..lights).filter(|_| rng.bool()).collect();
+          if wires.is_empty() {
+            wires.push(rng.usize(..lights));
+          }
+          let (toggled, presses) = (rng.bool(), rng.u32(..=20));
+          for &w in &wires {
+            if toggled {
+              pattern[w] = if pattern[w] == '.' { '#' } else { '.' };
+            }
+            joltages[w] += presses;
+          }
+          wires
+        })
+        .collect();
+
+      let list = |values: Vec<String>| values.join(",");
+      let buttons: Vec<String> = wires
+        .iter()
+        .map(|w| {
+          format!("({})", list(w.iter().map(usize::to_string).collect()))
+        })
+        .collect();
+      let joltages = list(joltages.iter().map(u32::to_string).collect());
+      let pattern: String = pattern.into_iter().collect();
+      format!("[{pattern}] {} {{{joltages}}}", buttons.join(" "))
+    })
+    .collect();
+  lines.join("\n")
+}
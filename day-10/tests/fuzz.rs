@@ -0,0 +1,37 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking. Crashing inputs are minimized and saved back
+// into `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver};
+use day_10::Day10;
+
+// part 2 may try every press count up to the largest joltage on every
+// button, so big joltages make it slow rather than wrong
+const MAX_PRESSES: u64 = 100_000;
+
+fn target(input: &str) {
+  let Ok(p) = Day10::parse(input) else {
+    return;
+  };
+  let _ = Day10::solve(Part::One, &p, ());
+
+  let presses = p.machines().try_fold(0u64, |sum, m| {
+    let max = m.target[..m.num_counters]
+      .iter()
+      .max()
+      .copied()
+      .unwrap_or(0);
+    let tries = (max as u64 + 1).checked_pow(m.num_buttons as u32)?;
+    sum.checked_add(tries)
+  });
+  if presses.is_some_and(|n| n <= MAX_PRESSES) {
+    let _ = Day10::solve(Part::Two, &p, ());
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
@@ -0,0 +1,90 @@
+// Part 2 stepped with small budgets, or saved and restored between steps,
+// must reach the one-shot answer, on the sample and on seeded synth
+// machines.
+#![cfg(feature = "part2")]
+
+use aoc_core::Error;
+use aoc_core::checkpoint::Checkpoint;
+use aoc_core::resume::{Poll, Resumable};
+use day_10::prelude::{Part2, parse, part2};
+
+mod common;
+use common::synth;
+
+/// The answer, and how many steps of `budget` units it took.
+fn stepped(input: &str, budget: u64) -> (u64, usize) {
+  let mut steps = Part2::new(&parse(input).unwrap());
+  let mut calls = 1;
+  loop {
+    match steps.step(budget) {
+      Poll::Ready(answer) => return (answer, calls),
+      Poll::Pending => calls += 1,
+    }
+  }
+}
+
+#[test]
+fn test_matches_part2() {
+  let mut inputs = vec![include_str!("../sample.txt").to_string()];
+  inputs.extend((0..4).map(|seed| synth::machines(seed, 5, 6, 7)));
+
+  for input in &inputs {
+    let whole = part2(&parse(input).unwrap());
+    for budget in [0, 1, 7, 64, u64::MAX] {
+      assert_eq!(stepped(input, budget).0, whole, "budget {budget}");
+    }
+  }
+}
+
+#[test]
+fn test_budget_bounds_work() {
+  let input = include_str!("../sample.txt");
+  let (_, one_at_a_time) = stepped(input, 1);
+  let (_, at_once) = stepped(input, u64::MAX);
+
+  // the sample's searches visit well over a hundred nodes
+  assert!(one_at_a_time > 100, "{one_at_a_time} steps");
+  assert_eq!(at_once, 1);
+
+  // a finished search keeps its answer
+  let mut steps = Part2::new(&parse(input).unwrap());
+  assert_eq!(steps.step(u64::MAX), Poll::Ready(33));
+  assert_eq!(steps.step(1), Poll::Ready(33));
+}
+
+#[test]
+fn test_checkpoint() {
+  let mut inputs = vec![include_str!("../sample.txt").to_string()];
+  inputs.extend((0..4).map(|seed| synth::machines(seed, 5, 6, 7)));
+
+  for input in &inputs {
+    let p = parse(input).unwrap();
+    let whole = part2(&p);
+
+    // a power loss after every few nodes, each time resumed from the bytes
+    let mut buf = [0u8; Part2::BYTES];
+    let mut steps = Part2::new(&p);
+    let answer = loop {
+      if let Poll::Ready(answer) = steps.step(5) {
+        break answer;
+      }
+      let used = steps.save(&mut buf).unwrap();
+      steps = Part2::restore(p, &buf[..used]).unwrap();
+    };
+    assert_eq!(answer, whole);
+  }
+}
+
+#[test]
+fn test_checkpoint_refuses_other_input() {
+  let input = include_str!("../sample.txt");
+  let mut steps = Part2::new(&parse(input).unwrap());
+  assert_eq!(steps.step(50), Poll::Pending);
+
+  let mut buf = [0u8; Part2::BYTES];
+  let used = steps.save(&mut buf).unwrap();
+
+  let other = synth::machines(0, 5, 6, 7);
+  let err = Part2::restore(parse(&other).unwrap(), &buf[..used]).err();
+  assert_eq!(err, Some(Error::Checkpoint("saved for another input")));
+}
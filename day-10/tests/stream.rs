@@ -0,0 +1,48 @@
+// The sample fed in two chunks split at every byte offset, and a byte at a
+// time, must give the same answers as solving it whole.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::stream::Stream;
+use aoc_core::{Error, ParseError, Part, Reason, Solver};
+use day_10::prelude::{Day10, Day10Stream};
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn fed<'a>(
+  part: Part,
+  chunks: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<u64, Error> {
+  let mut stream = Day10Stream::new(part);
+  for chunk in chunks {
+    stream.feed(chunk)?;
+  }
+  stream.finish()
+}
+
+#[test]
+fn test_every_split() {
+  for part in [Part::One, Part::Two] {
+    let p = Day10::parse(INPUT).unwrap();
+    let whole = u64::try_from(Day10::solve(part, &p, ()).unwrap()).unwrap();
+
+    for at in 0..=INPUT.len() {
+      let (head, tail) = INPUT.as_bytes().split_at(at);
+      assert_eq!(fed(part, [head, tail]), Ok(whole), "{part:?}, split {at}");
+    }
+    assert_eq!(fed(part, INPUT.as_bytes().chunks(1)), Ok(whole), "{part:?}");
+  }
+}
+
+#[test]
+fn test_parse_error() {
+  let chunks = [&b"[.#] (0) {1,1}\n[.#] (0"[..], b",x) {1,1}\n"];
+  let err = fed(Part::Two, chunks).unwrap_err();
+  assert_eq!(
+    err,
+    Error::Parse(ParseError {
+      line: 2,
+      column: 9,
+      reason: Reason::ExpectedNumber
+    })
+  );
+}
@@ -1,24 +1,33 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 use day_11::prelude::*;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+// part 2 has its own sample, which `AOC_INPUT_PART2` names when set; the
+// real input serves both parts
+#[cfg(feature = "part2")]
+static INPUT_PART2: LazyLock<String> =
+  LazyLock::new(|| match std::env::var("AOC_INPUT_PART2") {
+    Ok(path) => {
+      aoc_core::input::load(Some(path)).unwrap_or_else(|e| panic!("{e}"))
+    }
+    Err(_) => INPUT.clone(),
+  });
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let p = parse(black_box(INPUT));
-  black_box(part1(&p));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
+  black_box(part1(&p).unwrap());
 }
 
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let p = parse(black_box(INPUT));
-  black_box(part2(&p));
+  let p = parse(black_box(INPUT_PART2.as_str())).unwrap();
+  black_box(part2(&p).unwrap());
 }
 
 fn main() {
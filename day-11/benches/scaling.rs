@@ -0,0 +1,38 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_11::prelude::*;
+use divan::{Bencher, black_box};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Devices.
+const SIZES: &[usize] = &[128, 256, 512, 1008];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, n: usize) {
+  let input = synth::devices(SEED, n);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part1(&p).unwrap());
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, n: usize) {
+  let input = synth::devices(SEED, n);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part2(&p).unwrap());
+  });
+}
+
+fn main() {
+  divan::main();
+}
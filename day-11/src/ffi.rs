@@ -0,0 +1,34 @@
+//! Day 11's C ABI, declared in `include/day_11.h`; see `aoc_core::ffi`.
+
+use aoc_core::Part;
+use aoc_core::ffi::{self, Status};
+
+use crate::Day11;
+
+/// Part 1's answer for the `len` bytes of puzzle text at `input`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `answer` to a writable
+/// `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day11_part1(
+  input: *const u8,
+  len: usize,
+  answer: *mut u64,
+) -> Status {
+  unsafe { ffi::solve::<Day11>(Part::One, input, len, answer) }
+}
+
+/// Part 2's answer for the `len` bytes of puzzle text at `input`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `answer` to a writable
+/// `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day11_part2(
+  input: *const u8,
+  len: usize,
+  answer: *mut u64,
+) -> Status {
+  unsafe { ffi::solve::<Day11>(Part::Two, input, len, answer) }
+}
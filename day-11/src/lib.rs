@@ -3,17 +3,26 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "part2")]
+use aoc_core::trace::{Event, NoTrace, TraceSink};
+use aoc_core::{Error, ParseError, Reason};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
 use heapless::{Vec, index_map::FnvIndexMap};
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day11, Problem, parse, parse_with_capacity};
 
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub use crate::part1_impl::part1;
 
   #[cfg(feature = "part2")]
-  pub use crate::part2_impl::part2;
+  pub use crate::part2_impl::{part2, part2_traced};
 }
 
 
@@ -21,21 +30,25 @@ pub mod prelude {
 // Data Model
 // --------------------------
 
-#[cfg(feature = "sample")]
-const C: usize = 3;
-#[cfg(feature = "sample")]
-const M: usize = 16;
-#[cfg(not(feature = "sample"))]
-const C: usize = 23;
-#[cfg(not(feature = "sample"))]
-const M: usize = 1024;
-
-pub struct Problem<'a> {
-  nodes: Nodes<'a>,
+/// Children one device may list, unless the caller picks its own `C`.
+pub const MAX_CHILDREN: usize = 23;
+/// Devices a `Problem` holds, unless the caller picks its own `M`; a power
+/// of two.
+pub const MAX_DEVICES: usize = 1024;
+
+/// `C` bounds the children of one device and `M` the devices, and so the
+/// stack the solvers use.
+pub struct Problem<
+  'a,
+  const C: usize = MAX_CHILDREN,
+  const M: usize = MAX_DEVICES,
+> {
+  input: &'a str,
+  nodes: Nodes<'a, C, M>,
 }
 
 #[derive(Clone, Default, PartialEq, Eq, Hash)]
-struct Node<'a> {
+struct Node<'a, const C: usize> {
   id: usize,
   name: &'a str,
   visited: bool,
@@ -43,7 +56,7 @@ struct Node<'a> {
 }
 
 #[cfg(feature = "std")]
-impl std::fmt::Debug for Node<'_> {
+impl<const C: usize> std::fmt::Debug for Node<'_, C> {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     if self.visited {
       write!(f, "Node{{ {}, visited }}", self.name)
@@ -53,7 +66,8 @@ impl std::fmt::Debug for Node<'_> {
   }
 }
 
-type Nodes<'a> = FnvIndexMap<usize, Node<'a>, M>;
+type Nodes<'a, const C: usize, const M: usize> =
+  FnvIndexMap<usize, Node<'a, C>, M>;
 
 
 fn id_from_str(s: &str) -> usize {
@@ -69,50 +83,119 @@ fn id_from_str(s: &str) -> usize {
 // Parse
 // --------------------------
 
-pub fn parse<'a>(input: &'a str) -> Problem<'a> {
-  let mut nodes: Nodes<'a> = FnvIndexMap::new();
+/// Checks a device name fits the id packing: 1 to 8 ASCII alphanumerics.
+fn device<'a>(input: &str, name: &'a str) -> Result<&'a str, ParseError> {
+  let bad = name
+    .char_indices()
+    .find(|(_, c)| !c.is_ascii_alphanumeric());
+  if let Some((i, c)) = bad {
+    return Err(ParseError::at(input, &name[i..], Reason::Unexpected(c)));
+  }
+  if name.is_empty() || name.len() > 8 {
+    let reason = Reason::Malformed("device names are 1 to 8 bytes long");
+    return Err(ParseError::at(input, name, reason));
+  }
+
+  Ok(name)
+}
+
+/// Counts the distinct device names in `input`; only needed once `M` has
+/// overflowed, so the quadratic scan is fine.
+fn count_devices(input: &str) -> usize {
+  let names = || {
+    input
+      .lines()
+      .flat_map(|l| l.split(|c: char| c == ':' || c.is_whitespace()))
+      .filter(|n| !n.is_empty())
+  };
+
+  names()
+    .enumerate()
+    .filter(|&(i, name)| !names().take(i).any(|n| n == name))
+    .count()
+}
+
+fn insert<'a, const C: usize, const M: usize>(
+  input: &str,
+  nodes: &mut Nodes<'a, C, M>,
+  name: &'a str,
+) -> Result<usize, Error> {
+  let id = id_from_str(name);
+  let node = Node { id, name, visited: false, children: Vec::new() };
+
+  if nodes.entry(id).or_insert(node).is_err() {
+    let needed = count_devices(input);
+    return Err(Error::CapacityExceeded { what: "devices", limit: M, needed });
+  }
 
-  for line in input.lines() {
-    let (mut parent_str, descendents_str) =
-      line.split_once(":").unwrap_or((line, ""));
+  Ok(id)
+}
 
-    parent_str = parent_str.trim();
+pub fn parse(input: &str) -> Result<Problem<'_>, Error> {
+  parse_with_capacity(input)
+}
 
-    let pid = id_from_str(parent_str);
-    let _ = nodes.entry(pid).or_insert(Node {
-      id: pid,
-      name: parent_str,
-      visited: false,
-      children: Vec::new(),
-    });
+/// `parse`, for a `Problem` holding at most `M` devices of `C` children.
+pub fn parse_with_capacity<'a, const C: usize, const M: usize>(
+  input: &'a str,
+) -> Result<Problem<'a, C, M>, Error> {
+  let mut nodes: Nodes<'a, C, M> = FnvIndexMap::new();
 
-    for child in descendents_str.split_whitespace() {
-      let child = child.trim();
+  for line in input.lines().filter(|l| !l.trim().is_empty()) {
+    let (parent_str, descendents_str) =
+      aoc_core::parse::split_once(input, line, ':')?;
 
-      let id = id_from_str(child);
-      let _ = nodes.entry(id).or_insert(Node {
-        id,
-        name: child,
-        visited: false,
-        children: Vec::new(),
-      });
+    let parent_str = device(input, parent_str.trim())?;
+    let pid = insert(input, &mut nodes, parent_str)?;
 
-      let _ = nodes.get_mut(&pid).unwrap().children.push(id);
+    let listed = nodes.get(&pid).map_or(0, |n| n.children.len());
+    let needed = listed + descendents_str.split_whitespace().count();
+    Error::check_capacity("children of one device", C, needed)?;
+
+    for child in descendents_str.split_whitespace() {
+      let child = device(input, child)?;
+      let id = insert(input, &mut nodes, child)?;
+
+      if let Some(parent) = nodes.get_mut(&pid) {
+        // fits: checked against C above
+        let _ = parent.children.push(id);
+      }
     }
   }
 
-  Problem { nodes }
+  Ok(Problem { input, nodes })
+}
+
+impl<const C: usize, const M: usize> Problem<'_, C, M> {
+  /// The id of device `name`; fails at the end of the input, for `reason`,
+  /// if it is not listed.
+  fn find(&self, name: &str, reason: &'static str) -> Result<usize, Error> {
+    match self.nodes.iter().find(|(_, n)| n.name == name) {
+      Some((id, _)) => Ok(*id),
+      None => {
+        let (input, reason) = (self.input, Reason::Malformed(reason));
+        Err(ParseError::at_offset(input, input.len(), reason).into())
+      }
+    }
+  }
 }
 
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
+  use aoc_core::Error;
+
   use super::{Nodes, Problem};
 
-  fn dfs<'a>(idx: usize, out: usize, nodes: &mut Nodes<'a>, count: &mut usize) {
+  fn dfs<const C: usize, const M: usize>(
+    idx: usize,
+    out: usize,
+    nodes: &mut Nodes<'_, C, M>,
+    count: &mut usize,
+  ) {
     if idx == out {
       *count += 1;
       return;
@@ -130,24 +213,17 @@ mod part1_impl {
     nodes[&idx].visited = false;
   }
 
-  pub fn part1(p: &Problem) -> usize {
-    let you = p
-      .nodes
-      .iter()
-      .find(|(_, n)| n.name == "you")
-      .map(|(id, _)| *id)
-      .unwrap();
-    let out = p
-      .nodes
-      .iter()
-      .find(|(_, n)| n.name == "out")
-      .map(|(id, _)| *id)
-      .unwrap();
+  pub fn part1<const C: usize, const M: usize>(
+    p: &Problem<C, M>,
+  ) -> Result<usize, Error> {
+    let reason = "no `you` or `out` device";
+    let you = p.find("you", reason)?;
+    let out = p.find("out", reason)?;
 
     let mut count = 0;
     dfs(you, out, &mut p.nodes.clone(), &mut count);
 
-    count
+    Ok(count)
   }
 }
 
@@ -158,15 +234,16 @@ mod part1_impl {
 
 #[cfg(feature = "part2")]
 mod part2_impl {
+  use aoc_core::Error;
   use heapless::index_map::FnvIndexMap;
 
-  use super::{M, Nodes, Problem};
+  use super::{Event, NoTrace, Nodes, Problem, TraceSink};
 
 
-  fn dfs<'a>(
+  fn dfs<const C: usize, const M: usize>(
     idx: usize,
     target: usize,
-    nodes: &mut Nodes<'a>,
+    nodes: &mut Nodes<'_, C, M>,
     memo: &mut FnvIndexMap<usize, usize, M>,
   ) -> usize {
     if idx == target {
@@ -193,66 +270,84 @@ mod part2_impl {
     count
   }
 
-  pub fn part2(p: &Problem) -> usize {
-    let svr = p
-      .nodes
-      .iter()
-      .find(|(_, n)| n.name == "svr")
-      .map(|(id, _)| *id)
-      .unwrap();
-    let dac = p
-      .nodes
-      .iter()
-      .find(|(_, n)| n.name == "dac")
-      .map(|(id, _)| *id)
-      .unwrap();
-    let fft = p
-      .nodes
-      .iter()
-      .find(|(_, n)| n.name == "fft")
-      .map(|(id, _)| *id)
-      .unwrap();
-    let out = p
-      .nodes
-      .iter()
-      .find(|(_, n)| n.name == "out")
-      .map(|(id, _)| *id)
-      .unwrap();
-
-
-    // Compute each unique segment once
-    let mut memo = FnvIndexMap::new();
-    let svr_to_dac = dfs(svr, dac, &mut p.nodes.clone(), &mut memo);
+  pub fn part2<const C: usize, const M: usize>(
+    p: &Problem<C, M>,
+  ) -> Result<usize, Error> {
+    part2_traced(p, &mut NoTrace)
+  }
 
+  /// `part2`, reporting the paths along each segment to `sink`.
+  pub fn part2_traced<const C: usize, const M: usize>(
+    p: &Problem<C, M>,
+    sink: &mut impl TraceSink,
+  ) -> Result<usize, Error> {
+    let reason = "no `svr`, `dac`, `fft` or `out` device";
+    let svr = p.find("svr", reason)?;
+    let dac = p.find("dac", reason)?;
+    let fft = p.find("fft", reason)?;
+    let out = p.find("out", reason)?;
+
+    // Compute each unique segment once. `dfs` clears every `visited` flag it
+    // sets, so one copy of the graph serves all six; only the memo, which is
+    // per target, starts over.
+    let mut nodes = p.nodes.clone();
     let mut memo = FnvIndexMap::new();
-    let svr_to_fft = dfs(svr, fft, &mut p.nodes.clone(), &mut memo);
+    let mut segment = |from, to| {
+      memo.clear();
+      dfs(from, to, &mut nodes, &mut memo)
+    };
+
+    let svr_to_dac = segment(svr, dac);
+    let svr_to_fft = segment(svr, fft);
+    let dac_to_fft = segment(dac, fft);
+    let fft_to_dac = segment(fft, dac);
+    let dac_to_out = segment(dac, out);
+    let fft_to_out = segment(fft, out);
+
+    for (from, to, count) in [
+      ("svr", "dac", svr_to_dac),
+      ("svr", "fft", svr_to_fft),
+      ("dac", "fft", dac_to_fft),
+      ("fft", "dac", fft_to_dac),
+      ("dac", "out", dac_to_out),
+      ("fft", "out", fft_to_out),
+    ] {
+      sink.event(Event::Segment { from, to, count: count as u64 });
+    }
 
-    let mut memo = FnvIndexMap::new();
-    let dac_to_fft = dfs(dac, fft, &mut p.nodes.clone(), &mut memo);
+    // Combine segments
+    let paths_dac_first = svr_to_dac * dac_to_fft * fft_to_out;
+    let paths_fft_first = svr_to_fft * fft_to_dac * dac_to_out;
 
-    let mut memo = FnvIndexMap::new();
-    let fft_to_dac = dfs(fft, dac, &mut p.nodes.clone(), &mut memo);
+    Ok(paths_dac_first + paths_fft_first)
+  }
+}
 
-    let mut memo = FnvIndexMap::new();
-    let dac_to_out = dfs(dac, out, &mut p.nodes.clone(), &mut memo);
+// --------------------------
+// Solver
+// --------------------------
 
-    let mut memo = FnvIndexMap::new();
-    let fft_to_out = dfs(fft, out, &mut p.nodes.clone(), &mut memo);
+pub struct Day11;
 
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day11 {
+  const DAY: u8 = 11;
 
-    #[cfg(feature = "std")]
-    {
-      println!(
-        "svr->dac: {}, svr->fft: {}, dac->fft: {}, fft->dac: {}, dac->out: \
-         {}, fft->out: {}",
-        svr_to_dac, svr_to_fft, dac_to_fft, fft_to_dac, dac_to_out, fft_to_out
-      );
-    }
-    // Combine segments
-    let paths_dac_first = svr_to_dac * dac_to_fft * fft_to_out;
-    let paths_fft_first = svr_to_fft * fft_to_dac * dac_to_out;
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Problem<'_>, Error> {
+    parse(input)
+  }
 
-    paths_dac_first + paths_fft_first
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, Error> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p)?,
+      Part::Two => part2_impl::part2(p)?,
+    };
+
+    Ok(answer.into())
   }
 }
 
@@ -262,11 +357,11 @@ mod tests {
   use super::prelude::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
-    let result = part1(&problem);
+    let problem = parse(input).unwrap();
+    let result = part1(&problem).unwrap();
 
     assert_eq!(result, 5);
   }
@@ -275,8 +370,82 @@ mod tests {
   #[cfg(feature = "part2")]
   fn test_part2() {
     let input = include_str!("../sample.part2.txt");
-    let problem = parse(input);
-    let result = part2(&problem);
+    let problem = parse(input).unwrap();
+    let result = part2(&problem).unwrap();
     assert_eq!(result, 2);
   }
+
+  #[test]
+  fn test_missing_device() {
+    use aoc_core::{Error, ParseError, Reason};
+
+    // valid text, but without the devices the parts start and end at
+    let problem = parse("aaa: bbb\nbbb: ccc\n").unwrap();
+    let at_end = |reason| {
+      let reason = Reason::Malformed(reason);
+      Some(Error::Parse(ParseError { line: 3, column: 1, reason }))
+    };
+
+    #[cfg(feature = "part1")]
+    assert_eq!(part1(&problem).err(), at_end("no `you` or `out` device"));
+
+    #[cfg(feature = "part2")]
+    assert_eq!(
+      part2(&problem).err(),
+      at_end("no `svr`, `dac`, `fft` or `out` device")
+    );
+  }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{Error, ParseError, Reason};
+
+    let err = parse("you: bbb ccc\nbbb ddd eee\n").err().unwrap();
+    let expected =
+      ParseError { line: 2, column: 12, reason: Reason::Expected(':') };
+    assert_eq!(err, Error::Parse(expected));
+
+    let Some(Error::Parse(err)) = parse("you: bbb c-c\n").err() else {
+      panic!("expected a parse error");
+    };
+    assert_eq!(err.reason, Reason::Unexpected('-'));
+  }
+
+  #[test]
+  fn test_capacity_exceeded() {
+    extern crate std;
+    use aoc_core::Error;
+    use std::string::String;
+
+    use super::{MAX_CHILDREN as C, MAX_DEVICES as M};
+
+    let mut input = String::from("you:");
+    for i in 0..=C {
+      input += &std::format!(" d{i}");
+    }
+    let err = parse(&input).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded {
+        what: "children of one device",
+        limit: C,
+        needed: C + 1,
+      }
+    );
+
+    // `d0` .. `dM` plus `out`
+    let mut input = String::new();
+    for i in 0..=M {
+      input += &std::format!("d{i}: out\n");
+    }
+    let err = parse(&input).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded { what: "devices", limit: M, needed: M + 2 }
+    );
+
+    // the sample fits far smaller buffers
+    let sample = include_str!("../sample.txt");
+    assert!(super::parse_with_capacity::<3, 16>(sample).is_ok());
+  }
 }
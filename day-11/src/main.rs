@@ -1,25 +1,25 @@
+use aoc_core::Error;
+#[cfg(not(feature = "trace"))]
+use aoc_core::trace::NoTrace as Sink;
+#[cfg(feature = "trace")]
+use aoc_core::trace::Stderr as Sink;
 use day_11::prelude::*;
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), Error> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?;
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+  let p1 = part1(&problem)?;
+  println!("Part 1: {p1}");
 
+  // part 2 has its own sample, the second argument when given; the real
+  // input serves both parts
+  let part2_input =
+    std::env::args().nth(2).map(|_| aoc_core::input::from_arg(2));
+  let problem = parse(part2_input.as_deref().unwrap_or(&input))?;
+  let p2 = part2_traced(&problem, &mut Sink)?;
+  println!("Part 2: {p2}");
 
-fn main() {
-  let problem = parse(INPUT);
-
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&problem);
-    println!("Part 1: {p1}");
-  }
-
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&problem);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
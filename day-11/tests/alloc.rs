@@ -0,0 +1,36 @@
+// The library is `no_std` without `alloc`: parsing and solving must never
+// reach the heap.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::audit::{Allocations, CountingAlloc, measure};
+use aoc_core::{Part, Solver};
+use day_11::prelude::Day11;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const INPUT: &str = include_str!("../sample.txt");
+// part 2 has its own sample
+const INPUT_PART2: &str = include_str!("../sample.part2.txt");
+
+fn solve(part: Part) -> Allocations {
+  let (_, seen) = measure(|| {
+    let input = match part {
+      Part::One => INPUT,
+      Part::Two => INPUT_PART2,
+    };
+    let p = Day11::parse(input).unwrap();
+    Day11::solve(part, &p, ()).unwrap()
+  });
+  seen
+}
+
+#[test]
+fn test_allocations() {
+  for part in [Part::One, Part::Two] {
+    let seen = solve(part);
+    println!("{part:?}: {seen}");
+
+    assert_eq!(seen, Allocations::default(), "{part:?}");
+  }
+}
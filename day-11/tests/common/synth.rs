@@ -0,0 +1,60 @@
+use fastrand::Rng;
+
+/// Devices per layer; the puzzle's graph is similarly narrow.
+const WIDTH: usize = 16;
+
+/// About `n` devices in layers of [`WIDTH`], as the puzzle's: `svr` wired to
+/// the whole first layer, every device to the one below it and maybe to one
+/// more in the next layer, and the last layer to `out`. `fft` and `dac` sit
+/// a third and two thirds of the way down, and `you` three layers above
+/// `out` so that part 1's paths stay few.
+pub fn devices(seed: u64, n: usize) -> String {
+  let layers = n / WIDTH;
+  assert!(layers >= 4, "{n} devices make fewer than four layers");
+  let mut rng = Rng::with_seed(seed);
+
+  let mut names = (0..)
+    .map(name)
+    .filter(|n| !["svr", "you", "dac", "fft", "out"].contains(&n.as_str()));
+  let mut grid: Vec<Vec<String>> = (0..layers)
+    .map(|_| names.by_ref().take(WIDTH).collect())
+    .collect();
+  // distinct slots, in case two of them share a layer
+  let mut slots: Vec<usize> = (0..WIDTH).collect();
+  rng.shuffle(&mut slots);
+  let named = [
+    (layers / 3, "fft"),
+    (2 * layers / 3, "dac"),
+    (layers - 3, "you"),
+  ];
+  for ((layer, named), slot) in named.into_iter().zip(slots) {
+    grid[layer][slot] = named.to_string();
+  }
+
+  let mut lines = vec![format!("svr: {}", grid[0].join(" "))];
+  for (l, layer) in grid.iter().enumerate() {
+    for (i, device) in layer.iter().enumerate() {
+      let outputs = match grid.get(l + 1) {
+        None => "out".to_string(),
+        Some(next) => {
+          let other = rng.usize(..WIDTH);
+          if other == i || rng.bool() {
+            next[i].clone()
+          } else {
+            format!("{} {}", next[i], next[other])
+          }
+        }
+      };
+      lines.push(format!("{device}: {outputs}"));
+    }
+  }
+  lines.join("\n")
+}
+
+/// Three lowercase letters for device `i`.
+fn name(i: usize) -> String {
+  [i / 676 % 26, i / 26 % 26, i % 26]
+    .iter()
+    .map(|&c| char::from(b'a' + c as u8))
+    .collect()
+}
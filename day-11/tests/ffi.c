@@ -0,0 +1,28 @@
+/* Day 11's C ABI on the sample, run by `tests/ffi.rs`. */
+#include "check.h"
+#include "day_11.h"
+
+int main(int argc, char **argv) {
+  CHECK(argc == 3);
+  size_t len, len2;
+  char *sample = read_input(argv[1], &len);
+  char *sample2 = read_input(argv[2], &len2);
+  uint64_t answer = 0;
+
+  CHECK(aoc_day11_part1((const uint8_t *)sample, len, &answer) == AOC_OK);
+  CHECK(answer == 5);
+  CHECK(aoc_day11_part2((const uint8_t *)sample2, len2, &answer) == AOC_OK);
+  CHECK(answer == 2);
+
+  // failures leave the answer alone
+  CHECK(aoc_day11_part2((const uint8_t *)sample, len, &answer) == AOC_PARSE);
+  CHECK(aoc_day11_part1((const uint8_t *)"x\n", 2, &answer) == AOC_PARSE);
+  CHECK(aoc_day11_part1((const uint8_t *)"\xff", 1, &answer) ==
+        AOC_INVALID_UTF8);
+  CHECK(aoc_day11_part1(NULL, 1, &answer) == AOC_NULL_POINTER);
+  CHECK(answer == 2);
+
+  free(sample);
+  free(sample2);
+  return 0;
+}
@@ -0,0 +1,12 @@
+// Builds day 11 as a staticlib and runs `ffi.c` against its generated header.
+#![cfg(unix)]
+
+#[test]
+fn test_c_abi() {
+  aoc_core::cc::run(
+    env!("CARGO_MANIFEST_DIR"),
+    env!("CARGO_PKG_NAME"),
+    "tests/ffi.c",
+    &["sample.txt", "sample.part2.txt"],
+  );
+}
@@ -0,0 +1,122 @@
+// Differential tests: random device graphs go through the `Solver` and
+// through a naive std reference that walks every path on its own.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use std::collections::BTreeSet;
+
+use aoc_core::{Answer, Part, Solver};
+use day_11::Day11;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  use std::collections::HashMap;
+
+  fn outputs(input: &str) -> HashMap<&str, Vec<&str>> {
+    input
+      .lines()
+      .map(|l| {
+        let (device, outputs) = l.split_once(':').unwrap();
+        (device, outputs.split_whitespace().collect())
+      })
+      .collect()
+  }
+
+  /// Calls `found` with every path from `from` to `out`.
+  fn paths<'a>(
+    outputs: &HashMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+    found: &mut impl FnMut(&[&str]),
+  ) {
+    let here = *path.last().unwrap();
+    if here == "out" {
+      return found(path);
+    }
+    for &next in outputs.get(here).into_iter().flatten() {
+      path.push(next);
+      paths(outputs, path, found);
+      path.pop();
+    }
+  }
+
+  pub fn part1(input: &str) -> usize {
+    let mut count = 0;
+    paths(&outputs(input), &mut vec!["you"], &mut |_| count += 1);
+    count
+  }
+
+  pub fn part2(input: &str) -> usize {
+    let mut count = 0;
+    paths(&outputs(input), &mut vec!["svr"], &mut |path| {
+      if path.contains(&"dac") && path.contains(&"fft") {
+        count += 1;
+      }
+    });
+    count
+  }
+}
+
+/// Graphs without cycles, as the puzzle's: the named devices and some
+/// others in a random order, each wired to one to four devices after it,
+/// with `out` last.
+fn devices() -> impl Strategy<Value = String> {
+  const NAMED: [&str; 5] = ["svr", "you", "dac", "fft", "out"];
+
+  let names = prop::collection::btree_set("[a-z]{3}", 0..=9).prop_map(
+    |others: BTreeSet<String>| {
+      let others = others.into_iter().filter(|n| !NAMED.contains(&&**n));
+      let named = NAMED[..4].iter().map(|n| n.to_string());
+      named.chain(others).collect::<Vec<_>>()
+    },
+  );
+  names.prop_shuffle().prop_flat_map(|names| {
+    let wiring = prop::collection::vec(any::<prop::sample::Index>(), 1..=4);
+    (
+      Just(names.clone()),
+      prop::collection::vec(wiring, names.len()),
+    )
+      .prop_map(|(mut names, wiring)| {
+        names.push("out".to_string());
+        let lines: Vec<String> = wiring
+          .iter()
+          .enumerate()
+          .map(|(i, picks)| {
+            let later = &names[i + 1..];
+            let outputs: BTreeSet<&str> = picks
+              .iter()
+              .map(|p| later[p.index(later.len())].as_str())
+              .collect();
+            let outputs: Vec<&str> = outputs.into_iter().collect();
+            format!("{}: {}", names[i], outputs.join(" "))
+          })
+          .collect();
+        lines.join("\n")
+      })
+  })
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day11::parse(input).unwrap();
+  Day11::solve(part, &p, ()).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in devices()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in devices()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::devices(seed, 128);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
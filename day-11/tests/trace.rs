@@ -0,0 +1,29 @@
+// Trace events tell the same story as the answers they lead to.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::trace::Collect;
+use day_11::prelude::*;
+
+const INPUT: &str = include_str!("../sample.part2.txt");
+
+#[test]
+fn test_part2_trace() {
+  let p = parse(INPUT).unwrap();
+  let mut sink = Collect::default();
+  let answer = part2_traced(&p, &mut sink).unwrap();
+
+  assert_eq!(answer, part2(&p).unwrap());
+  assert_eq!(
+    sink.events,
+    [
+      "svr -> dac: 2",
+      "svr -> fft: 1",
+      "dac -> fft: 0",
+      "fft -> dac: 1",
+      "dac -> out: 2",
+      "fft -> out: 4",
+    ]
+  );
+  // svr, fft, dac, out is the only order with paths: 1 * 1 * 2
+  assert_eq!(answer, 2);
+}
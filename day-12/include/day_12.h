@@ -0,0 +1,29 @@
+/* Generated by `cargo xtask header` from day-12/src/ffi.rs; do not edit. */
+#ifndef AOC_DAY_12_H
+#define AOC_DAY_12_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#include "aoc.h"
+
+/*
+ * Part 1's answer for the `len` bytes of puzzle text at `input`.
+ *
+ * Safety:
+ * `input` must point to `len` readable bytes and `answer` to a writable
+ * `uint64_t`.
+ */
+aoc_status aoc_day12_part1(const uint8_t *input, size_t len, uint64_t *answer);
+
+/*
+ * Part 2's answer for the `len` bytes of puzzle text at `input`.
+ *
+ * Safety:
+ * `input` must point to `len` readable bytes and `answer` to a writable
+ * `uint64_t`.
+ */
+aoc_status aoc_day12_part2(const uint8_t *input, size_t len, uint64_t *answer);
+
+#endif
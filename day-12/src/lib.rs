@@ -3,12 +3,20 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "part1")]
+use aoc_core::trace::{Event, NoTrace, TraceSink};
+use aoc_core::{Error, ParseError, Reason};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
 use heapless::Vec;
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 mod parser {
   include!("parser.rs");
 }
-use parser::{parse_regions, parse_shapes, skip_blank_lines};
+use parser::{parse_regions, parse_shapes, region_line, skip_blank_lines};
 
 mod data_model {
   include!("data_model.rs");
@@ -17,10 +25,12 @@ use data_model::{MAX_REGIONS, Region, SHAPES, Shape};
 
 
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day12, Problem, parse};
 
-  #[cfg(not(feature = "part2"))]
-  pub use crate::part1_impl::part1;
+  #[cfg(all(feature = "part1", feature = "parallel"))]
+  pub use crate::part1_impl::part1_parallel;
+  #[cfg(feature = "part1")]
+  pub use crate::part1_impl::{part1, part1_traced};
 
   #[cfg(feature = "part2")]
   pub use crate::part2_impl::part2;
@@ -40,30 +50,58 @@ impl Problem {
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ParseError {
-  Nom,
-  BadShapeDims,
-  RaggedShape,
-  TooManyRegions,
-  WrongShapeCount,
-  WrongCountsCount,
-  CountsOverflow,
-}
-
-pub fn parse(input: &str) -> Result<Problem, ParseError> {
-  let (rest, shapes_vec) = parse_shapes(input).map_err(|_| ParseError::Nom)?;
+/// Points a nom error at the byte nom stopped on.
+fn located(input: &str, err: nom::Err<nom::error::Error<&str>>) -> ParseError {
+  use nom::error::ErrorKind;
 
-  let shapes: [Shape; SHAPES] = shapes_vec
-    .as_slice()
-    .try_into()
-    .map_err(|_| ParseError::WrongShapeCount)?;
+  let e = match err {
+    nom::Err::Error(e) | nom::Err::Failure(e) => e,
+    nom::Err::Incomplete(_) => {
+      return ParseError::at_offset(input, input.len(), Reason::UnexpectedEnd);
+    }
+  };
+
+  let reason = match e.code {
+    ErrorKind::Digit => Reason::ExpectedNumber,
+    ErrorKind::MapRes => Reason::OutOfRange,
+    ErrorKind::Many1 => Reason::Malformed("shape has no rows"),
+    ErrorKind::Verify => Reason::Malformed("shape rows differ in width"),
+    ErrorKind::TooLarge => Reason::Malformed("shape is larger than 3x3"),
+    _ => match e.input.chars().next() {
+      Some(c) => Reason::Unexpected(c),
+      None => Reason::UnexpectedEnd,
+    },
+  };
+
+  ParseError::at(input, e.input, reason)
+}
 
-  let (rest, _) = skip_blank_lines(rest).map_err(|_| ParseError::Nom)?;
-  let (rest, regions) = parse_regions(rest).map_err(|_| ParseError::Nom)?;
+pub fn parse(input: &str) -> Result<Problem, Error> {
+  let (rest, (shapes_vec, count)) =
+    parse_shapes(input).map_err(|e| located(input, e))?;
 
-  // allow trailing whitespace
-  let _ = rest;
+  let shapes: [Shape; SHAPES] = match shapes_vec.as_slice().try_into() {
+    Ok(shapes) if count == SHAPES => shapes,
+    _ => {
+      let reason = Reason::Malformed("expected 6 shapes");
+      return Err(ParseError::at(input, rest, reason).into());
+    }
+  };
+
+  let (rest, _) = skip_blank_lines(rest).map_err(|e| located(input, e))?;
+  let (rest, (regions, count)) =
+    parse_regions(rest).map_err(|e| located(input, e))?;
+
+  // allow trailing whitespace; anything else is a region line that failed
+  if !rest.trim().is_empty() {
+    let reason = Reason::Malformed("expected a `WxH: counts` region");
+    let err = match region_line(rest) {
+      Err(e) => located(input, e),
+      Ok(_) => ParseError::at(input, rest, reason),
+    };
+    return Err(err.into());
+  }
+  Error::check_capacity("regions", MAX_REGIONS, count)?;
 
   Ok(Problem { shapes, regions })
 }
@@ -72,16 +110,15 @@ pub fn parse(input: &str) -> Result<Problem, ParseError> {
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
-  use super::Problem;
+  use super::{Event, NoTrace, Problem, Region, TraceSink};
 
   #[inline(always)]
   fn min_needed_space_for_count(
-    #[cfg(feature = "std")] shape_idx: usize,
-    #[cfg(feature = "std")] shape_id: u8,
     count: u16,
     pair_area: Option<usize>,
+    sink: &mut impl TraceSink,
   ) -> usize {
     let n = count as usize;
     let single_area = 9usize; // 3x3 footprint per single
@@ -93,11 +130,7 @@ mod part1_impl {
         let singles = n % 2;
         let best = pairs * pa + singles * single_area;
 
-        #[cfg(feature = "std")]
-        eprintln!(
-          "shape_idx={} shape_id={} count={} pair_area={:?} => best={}",
-          shape_idx, shape_id, count, pair_area, best
-        );
+        sink.event(Event::State { name: "paired space", value: best as i64 });
 
         best
       }
@@ -105,39 +138,41 @@ mod part1_impl {
   }
 
   pub fn part1(p: &Problem) -> usize {
-    p.regions
-      .iter()
-      .filter(|r| {
-        let mut needed_space = 0usize;
-
-        for (idx, &count) in r.counts.iter().enumerate() {
-          if count == 0 {
-            continue;
-          }
-
-          let shape = &p.shapes[idx];
-
-          needed_space += min_needed_space_for_count(
-            #[cfg(feature = "std")]
-            idx,
-            #[cfg(feature = "std")]
-            shape.id,
-            count,
-            shape.tight_pair_area,
-          );
-        }
-
-        let area = (r.w as usize) * (r.h as usize);
-
-        #[cfg(feature = "std")]
-        eprintln!(
-          "region {}x{} needed_space={} area={}",
-          r.w, r.h, needed_space, area
-        );
-
-        needed_space <= area
-      })
-      .count()
+    part1_traced(p, &mut NoTrace)
+  }
+
+  /// `part1`, reporting each region's needed space against its area to
+  /// `sink`.
+  pub fn part1_traced(p: &Problem, sink: &mut impl TraceSink) -> usize {
+    p.regions.iter().filter(|r| fits(p, r, sink)).count()
+  }
+
+  /// `part1`, with the regions split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part1_parallel(p: &Problem, threads: usize) -> usize {
+    aoc_core::parallel::sum_slices(&p.regions, threads, |regions| {
+      regions.iter().filter(|r| fits(p, r, &mut NoTrace)).count()
+    })
+  }
+
+  fn fits(p: &Problem, r: &Region, sink: &mut impl TraceSink) -> bool {
+    let mut needed_space = 0usize;
+
+    for (idx, &count) in r.counts.iter().enumerate() {
+      if count == 0 {
+        continue;
+      }
+
+      let shape = &p.shapes[idx];
+
+      needed_space +=
+        min_needed_space_for_count(count, shape.tight_pair_area, sink);
+    }
+
+    let (needed, area) = (needed_space as u64, r.w as u64 * r.h as u64);
+    sink.event(Event::Fit { needed, area });
+
+    needed <= area
   }
 }
 
@@ -158,13 +193,40 @@ mod part2_impl {
     be_awesome(p)
   }
 }
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day12;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day12 {
+  const DAY: u8 = 12;
+
+  type Input<'a> = Problem;
+  type Scratch<'a> = ();
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Problem, Error> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, Error> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p),
+    };
+
+    Ok(answer.into())
+  }
+}
 
 #[cfg(test)]
 mod tests {
   use super::prelude::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
     let problem = match parse(input) {
@@ -187,4 +249,65 @@ mod tests {
     let result = part2(&problem);
     assert_eq!(result, 42);
   }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{Error, ParseError, Reason};
+
+    // the sample's shapes, then a bad count on line 32
+    let input = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n\
+                 2:\n.##\n###\n##.\n\n3:\n##.\n###\n##.\n\n\
+                 4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n\
+                 4x4: 0 0 0 0 2 0\n12x5: 1 0 1 0 x 2\n";
+    let err = parse(input).err().unwrap();
+    let expected =
+      ParseError { line: 32, column: 15, reason: Reason::ExpectedNumber };
+    assert_eq!(err, Error::Parse(expected));
+
+    let input = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n\
+                 2:\n.##\n###\n##.\n\n3:\n##.\n###\n##.\n\n\
+                 4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n\
+                 4x4: 0 0 0 0 2 0\n12y5: 1 0 1 0 2 2\n";
+    let Some(Error::Parse(err)) = parse(input).err() else {
+      panic!("expected a parse error");
+    };
+    assert_eq!(err.reason, Reason::Unexpected('y'));
+  }
+
+  #[test]
+  fn test_capacity_exceeded() {
+    extern crate std;
+    use aoc_core::Error;
+
+    use super::MAX_REGIONS;
+
+    let shapes = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n\
+                  2:\n.##\n###\n##.\n\n3:\n##.\n###\n##.\n\n\
+                  4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n";
+
+    let regions = "4x4: 0 0 0 0 2 0\n".repeat(MAX_REGIONS + 1);
+    let input = std::format!("{shapes}{regions}");
+    let err = parse(&input).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded {
+        what: "regions",
+        limit: MAX_REGIONS,
+        needed: MAX_REGIONS + 1,
+      }
+    );
+
+    // a seventh shape, and a shape row wider than 3
+    let input = std::format!("{shapes}6:\n#\n\n4x4: 0 0 0 0 2 0\n");
+    let Some(Error::Parse(err)) = parse(&input).err() else {
+      panic!("expected a parse error");
+    };
+    assert_eq!(err.reason, aoc_core::Reason::Malformed("expected 6 shapes"));
+
+    let input = shapes.replacen("###", "####", 1);
+    let Some(Error::Parse(err)) = parse(&input).err() else {
+      panic!("expected a parse error");
+    };
+    assert_eq!((err.line, err.column), (2, 1));
+  }
 }
@@ -1,28 +1,20 @@
+use aoc_core::Error;
+#[cfg(not(feature = "trace"))]
+use aoc_core::trace::NoTrace as Sink;
+#[cfg(feature = "trace")]
+use aoc_core::trace::Stderr as Sink;
 use day_12::prelude::*;
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), Error> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?;
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+  let p1 = part1_traced(&problem, &mut Sink);
+  println!("Part 1: {p1}");
 
+  let p2 = part2(&problem);
+  println!("Part 2: {p2}");
 
-fn main() {
-  let problem = match parse(INPUT) {
-    Ok(p) => p,
-    Err(e) => panic!("Parse error: {:?}", e),
-  };
-
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&problem);
-    println!("Part 1: {p1}");
-  }
-
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&problem);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
@@ -20,14 +20,16 @@ pub mod prelude {
 // Shapes
 // -----------------------------
 
-pub fn parse_shapes(i: &str) -> IResult<&str, Vec<Shape, SHAPES>> {
+/// Keeps the first `SHAPES` blocks, and counts every block so the caller
+/// can reject inputs with more.
+pub fn parse_shapes(i: &str) -> IResult<&str, (Vec<Shape, SHAPES>, usize)> {
   let (i, _) = skip_blank_lines(i)?;
   fold_many0(
     terminated(parse_shape_block, skip_blank_lines),
-    Vec::<Shape, SHAPES>::new,
-    |mut acc, s| {
+    || (Vec::<Shape, SHAPES>::new(), 0),
+    |(mut acc, count), s| {
       let _ = acc.push(s);
-      acc
+      (acc, count + 1)
     },
   )
   .parse(i)
@@ -50,7 +52,12 @@ fn parse_shape_block(i: &str) -> IResult<&str, Shape> {
     let (rest, _) = opt(line_ending).parse(rest)?;
     cur = rest;
 
-    let _ = lines.push(row);
+    if lines.push(row).is_err() {
+      return Err(nom::Err::Failure(nom::error::Error::new(
+        i,
+        nom::error::ErrorKind::TooLarge,
+      )));
+    }
   }
 
   if lines.is_empty() {
@@ -66,7 +73,7 @@ fn parse_shape_block(i: &str) -> IResult<&str, Shape> {
   for r in &lines {
     if r.len() != w {
       return Err(nom::Err::Failure(nom::error::Error::new(
-        cur,
+        i,
         nom::error::ErrorKind::Verify,
       )));
     }
@@ -74,7 +81,7 @@ fn parse_shape_block(i: &str) -> IResult<&str, Shape> {
 
   if w == 0 || h == 0 || w > SHAPE_W || h > SHAPE_H {
     return Err(nom::Err::Failure(nom::error::Error::new(
-      cur,
+      i,
       nom::error::ErrorKind::TooLarge,
     )));
   }
@@ -261,10 +268,12 @@ pub type LineBytes = heapless::Vec<u8, SHAPE_W>;
 
 pub fn shape_row(i: &str) -> IResult<&str, LineBytes> {
   let (rest, s) = take_while1(|c| c == '.' || c == '#')(i)?;
-  let mut v = LineBytes::new();
-  for &b in s.as_bytes() {
-    let _ = v.push(b);
-  }
+  let v = LineBytes::from_slice(s.as_bytes()).map_err(|_| {
+    nom::Err::Failure(nom::error::Error::new(
+      i,
+      nom::error::ErrorKind::TooLarge,
+    ))
+  })?;
   Ok((rest, v))
 }
 
@@ -299,13 +308,17 @@ pub fn is_blank_line(i: &str) -> bool {
 // Regions
 // -----------------------------
 
-pub fn parse_regions(i: &str) -> IResult<&str, Vec<Region, MAX_REGIONS>> {
+/// Keeps the first `MAX_REGIONS` regions, and counts every region so the
+/// caller can report how many it would have needed.
+pub fn parse_regions(
+  i: &str,
+) -> IResult<&str, (Vec<Region, MAX_REGIONS>, usize)> {
   fold_many0(
     terminated(region_line, opt(line_ending)),
-    Vec::<Region, MAX_REGIONS>::new,
-    |mut acc, r| {
+    || (Vec::<Region, MAX_REGIONS>::new(), 0),
+    |(mut acc, count), r| {
       let _ = acc.push(r);
-      acc
+      (acc, count + 1)
     },
   )
   .parse(i)
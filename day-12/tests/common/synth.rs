@@ -0,0 +1,57 @@
+use fastrand::Rng;
+
+/// Six random 3x3 shapes and `n` regions of 35 to 50 cells a side. Like the
+/// puzzle's, each region either has a 3x3 slot for every present or too
+/// few cells for them all.
+pub fn farm(seed: u64, n: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let shapes: Vec<Vec<bool>> = (0..6)
+    .map(|_| {
+      loop {
+        let cells: Vec<bool> = (0..9).map(|_| rng.u8(..10) < 7).collect();
+        if cells.contains(&true) {
+          break cells;
+        }
+      }
+    })
+    .collect();
+  let blocks: Vec<String> = shapes
+    .iter()
+    .enumerate()
+    .map(|(id, cells)| {
+      let rows: Vec<String> = cells
+        .chunks(3)
+        .map(|r| r.iter().map(|&c| if c { '#' } else { '.' }).collect())
+        .collect();
+      format!("{id}:\n{}", rows.join("\n"))
+    })
+    .collect();
+  let cells: Vec<usize> = shapes
+    .iter()
+    .map(|s| s.iter().filter(|&&c| c).count())
+    .collect();
+
+  let regions: Vec<String> = (0..n)
+    .map(|_| {
+      let (w, h, roomy) = (rng.usize(35..=50), rng.usize(35..=50), rng.bool());
+      let mut counts = [0; 6];
+      let mut placed = 0;
+      loop {
+        let s = rng.usize(..6);
+        let fits = if roomy {
+          placed < (w / 3) * (h / 3)
+        } else {
+          placed <= w * h
+        };
+        if !fits {
+          break;
+        }
+        counts[s] += 1;
+        placed += if roomy { 1 } else { cells[s] };
+      }
+      let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+      format!("{w}x{h}: {}", counts.join(" "))
+    })
+    .collect();
+  format!("{}\n\n{}", blocks.join("\n\n"), regions.join("\n"))
+}
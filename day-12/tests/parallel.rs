@@ -0,0 +1,21 @@
+// The parallel part 1, split across 1 to 8 threads, must match the
+// sequential one, on the sample and on seeded synth farms.
+#![cfg(all(feature = "part1", feature = "parallel"))]
+
+use day_12::prelude::*;
+
+mod common;
+use common::synth;
+
+#[test]
+fn test_matches_sequential() {
+  let mut inputs = vec![include_str!("../sample.txt").to_string()];
+  inputs.extend((0..4).map(|seed| synth::farm(seed, 1 << (seed * 2))));
+
+  for input in &inputs {
+    let p = parse(input).unwrap();
+    for threads in 1..=8 {
+      assert_eq!(part1_parallel(&p, threads), part1(&p));
+    }
+  }
+}
@@ -0,0 +1,210 @@
+// Differential tests: random shapes and regions go through the `Solver` and
+// through a naive std reference that searches for an actual packing. Part 2
+// has no puzzle to check against.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Part, Solver};
+use day_12::Day12;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  /// A shape's cells, relative to its first cell in reading order.
+  type Orientation = Vec<(isize, isize)>;
+
+  struct Region {
+    w: usize,
+    h: usize,
+    counts: Vec<usize>,
+  }
+
+  fn orientations(rows: &[&str]) -> Vec<Orientation> {
+    let mut cells: Vec<(isize, isize)> = Vec::new();
+    for (y, row) in rows.iter().enumerate() {
+      for (x, c) in row.chars().enumerate() {
+        if c == '#' {
+          cells.push((x as isize, y as isize));
+        }
+      }
+    }
+
+    let mut all: Vec<Orientation> = Vec::new();
+    for _ in 0..2 {
+      for _ in 0..4 {
+        cells = cells.iter().map(|&(x, y)| (-y, x)).collect();
+        let mut o = cells.clone();
+        o.sort_by_key(|&(x, y)| (y, x));
+        let (x0, y0) = o[0];
+        let o: Orientation = o.iter().map(|&(x, y)| (x - x0, y - y0)).collect();
+        if !all.contains(&o) {
+          all.push(o);
+        }
+      }
+      cells = cells.iter().map(|&(x, y)| (-x, y)).collect();
+    }
+    all
+  }
+
+  fn parse(input: &str) -> (Vec<Vec<Orientation>>, Vec<Region>) {
+    let blocks: Vec<&str> = input.split("\n\n").collect();
+    let (regions, shapes) = blocks.split_last().unwrap();
+
+    let shapes = shapes
+      .iter()
+      .map(|b| orientations(&b.lines().skip(1).collect::<Vec<_>>()))
+      .collect();
+    let regions = regions
+      .lines()
+      .map(|l| {
+        let (size, counts) = l.split_once(": ").unwrap();
+        let (w, h) = size.split_once('x').unwrap();
+        Region {
+          w: w.parse().unwrap(),
+          h: h.parse().unwrap(),
+          counts: counts.split(' ').map(|c| c.parse().unwrap()).collect(),
+        }
+      })
+      .collect();
+    (shapes, regions)
+  }
+
+  /// Covers the first free cell with some remaining shape, or leaves it
+  /// empty, and recurses; `empty` counts the cells not yet decided.
+  fn packs(
+    shapes: &[Vec<Orientation>],
+    grid: &mut Vec<Vec<bool>>,
+    counts: &mut [usize],
+    empty: usize,
+  ) -> bool {
+    let needed: usize = (0..shapes.len())
+      .map(|s| counts[s] * shapes[s][0].len())
+      .sum();
+    if needed == 0 {
+      return true;
+    }
+    if needed > empty {
+      return false;
+    }
+    let w = grid[0].len();
+    let at = grid.iter().flatten().position(|&taken| !taken).unwrap();
+    let (x, y) = ((at % w) as isize, (at / w) as isize);
+
+    for s in 0..shapes.len() {
+      if counts[s] == 0 {
+        continue;
+      }
+      for o in &shapes[s] {
+        let cells: Option<Vec<(usize, usize)>> = o
+          .iter()
+          .map(|&(dx, dy)| {
+            let (cx, cy) = (usize::try_from(x + dx).ok()?, (y + dy) as usize);
+            (cx < w && cy < grid.len() && !grid[cy][cx]).then_some((cx, cy))
+          })
+          .collect();
+        let Some(cells) = cells else {
+          continue;
+        };
+
+        cells.iter().for_each(|&(cx, cy)| grid[cy][cx] = true);
+        counts[s] -= 1;
+        let packed = packs(shapes, grid, counts, empty - o.len());
+        counts[s] += 1;
+        cells.iter().for_each(|&(cx, cy)| grid[cy][cx] = false);
+        if packed {
+          return true;
+        }
+      }
+    }
+
+    grid[y as usize][x as usize] = true;
+    let packed = packs(shapes, grid, counts, empty - 1);
+    grid[y as usize][x as usize] = false;
+    packed
+  }
+
+  pub fn part1(input: &str) -> usize {
+    let (shapes, regions) = parse(input);
+    regions
+      .into_iter()
+      .filter(|r| {
+        let mut grid = vec![vec![false; r.w]; r.h];
+        packs(&shapes, &mut grid, &mut r.counts.clone(), r.w * r.h)
+      })
+      .count()
+  }
+}
+
+fn shape() -> impl Strategy<Value = String> {
+  prop::collection::vec(prop::bool::weighted(0.7), 9)
+    .prop_filter("empty shape", |cells| cells.contains(&true))
+    .prop_map(|cells| {
+      let cells: Vec<char> =
+        cells.iter().map(|&c| if c { '#' } else { '.' }).collect();
+      let rows: Vec<String> =
+        cells.chunks(3).map(|r| r.iter().collect()).collect();
+      rows.join("\n")
+    })
+}
+
+/// Like the puzzle's, a region either has a 3x3 slot for every present or
+/// too few cells for them all; the heuristic is exact only on those.
+fn region(cells: [usize; 6]) -> impl Strategy<Value = String> {
+  // enough picks to overfill a 10x10 region with one-cell shapes
+  let picks = prop::collection::vec(0..6usize, 128);
+  (3..=10usize, 3..=10usize, prop::bool::ANY, picks).prop_map(
+    move |(w, h, roomy, picks)| {
+      let mut counts = [0; 6];
+      let mut placed = 0;
+      for s in picks {
+        let fits = if roomy {
+          placed < (w / 3) * (h / 3)
+        } else {
+          placed <= w * h
+        };
+        if !fits {
+          break;
+        }
+        counts[s] += 1;
+        placed += if roomy { 1 } else { cells[s] };
+      }
+      let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+      format!("{w}x{h}: {}", counts.join(" "))
+    },
+  )
+}
+
+fn farms() -> impl Strategy<Value = String> {
+  prop::collection::vec(shape(), 6).prop_flat_map(|shapes| {
+    let cells = std::array::from_fn(|s| shapes[s].matches('#').count());
+    let blocks: Vec<String> = shapes
+      .iter()
+      .enumerate()
+      .map(|(id, shape)| format!("{id}:\n{shape}"))
+      .collect();
+    prop::collection::vec(region(cells), 1..=8).prop_map(move |regions| {
+      format!("{}\n\n{}", blocks.join("\n\n"), regions.join("\n"))
+    })
+  })
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in farms()) {
+    let p = Day12::parse(&input).unwrap();
+    let answer = Day12::solve(Part::One, &p, ()).unwrap();
+    prop_assert_eq!(answer, naive::part1(&input).into());
+  }
+
+  // the reference cannot pack regions as large as the puzzle's, so these
+  // are only solved
+  #[test]
+  fn test_synth_solves(seed in any::<u64>()) {
+    let input = synth::farm(seed, 20);
+    let p = Day12::parse(&input).unwrap();
+    for part in [Part::One, Part::Two] {
+      Day12::solve(part, &p, ()).unwrap();
+    }
+  }
+}
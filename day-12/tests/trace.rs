@@ -0,0 +1,29 @@
+// Trace events tell the same story as the answers they lead to.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::trace::Collect;
+use day_12::prelude::*;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+#[test]
+fn test_part1_trace() {
+  let p = parse(INPUT).unwrap();
+  let mut sink = Collect::default();
+  let answer = part1_traced(&p, &mut sink);
+  assert_eq!(answer, part1(&p));
+
+  // one fit per region, and the regions that fit are the answer
+  let fits: Vec<(u64, u64)> = sink
+    .events
+    .iter()
+    .filter_map(|e| e.strip_prefix("needs "))
+    .map(|e| {
+      let (needed, area) =
+        e.trim_end_matches(" cells").split_once(" of ").unwrap();
+      (needed.parse().unwrap(), area.parse().unwrap())
+    })
+    .collect();
+  assert_eq!(fits, [(16, 16), (52, 60), (61, 60)]);
+  assert_eq!(fits.iter().filter(|(n, a)| n <= a).count(), answer);
+}
@@ -0,0 +1,42 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_2::parse;
+#[cfg(feature = "part1")]
+use day_2::part1_impl::part1;
+#[cfg(feature = "part2")]
+use day_2::part2_impl::part2;
+use divan::{Bencher, black_box};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Ids per range, over ten ranges.
+const SIZES: &[u64] = &[100, 1_000, 10_000, 100_000];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, span: u64) {
+  let input = synth::ranges(SEED, 10, span);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part1(&p));
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, span: u64) {
+  let input = synth::ranges(SEED, 10, span);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part2(&p));
+  });
+}
+
+fn main() {
+  divan::main();
+}
@@ -3,6 +3,13 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use aoc_core::ParseError;
+use aoc_core::parse::{number, split_once};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod range;
 pub mod u64_handlers;
 
@@ -22,22 +29,83 @@ pub struct Problem<'a> {
 // Parse
 // --------------------------
 
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+/// Checks every `start-end` range up front; `RangeIter` re-reads them lazily.
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  for range in input.trim().split(',') {
+    let (start, end) = split_once(input, range, '-')?;
+    number::<u64>(input, start)?;
+    number::<u64>(input, end)?;
+  }
+
+  Ok(Problem { input })
+}
+
+/// Sum of the numbers in `ranges` whose digits `keep` accepts.
+#[cfg(any(feature = "part1", feature = "part2"))]
+fn sum_matching(
+  ranges: impl Iterator<Item = (u64, u64)>,
+  keep: fn(&str) -> bool,
+) -> u64 {
+  let mut total: u64 = 0;
+  let mut buf = [0u8; 20];
+
+  for (from, to) in ranges {
+    for n in from..=to {
+      if keep(write_u64_into_buf(n, &mut buf)) {
+        total += n;
+      }
+    }
+  }
+
+  total
+}
+
+/// `sum_matching` over `input`'s ranges, the numbers in them cut into one
+/// near-equal span per thread, however unevenly the ranges themselves are
+/// sized.
+#[cfg(all(feature = "parallel", any(feature = "part1", feature = "part2")))]
+fn sum_matching_parallel(
+  input: &str,
+  threads: usize,
+  keep: fn(&str) -> bool,
+) -> u64 {
+  use aoc_core::parallel;
+
+  let ranges: std::vec::Vec<_> = RangeIter::new(input).collect();
+  let len =
+    |&(from, to): &(u64, u64)| (to as u128 + 1).saturating_sub(from as u128);
+  let total: u128 = ranges.iter().map(len).sum();
+  let runs = threads.max(1) as u128;
+
+  parallel::sum(runs as usize, |run| {
+    let run = run as u128;
+    let (lo, hi) = (total * run / runs, total * (run + 1) / runs);
+
+    // each range's overlap with the numbers `lo..hi` of all of them
+    let mut start = 0;
+    let spans = ranges.iter().filter_map(|range| {
+      let (at, end) = (start, start + len(range));
+      start = end;
+      let (a, b) = (at.max(lo), end.min(hi));
+      let from = range.0 as u128;
+      (a < b).then(|| ((from + a - at) as u64, (from + b - at - 1) as u64))
+    });
+    sum_matching(spans, keep)
+  })
 }
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 pub mod part1_impl {
-  use super::{Problem, RangeIter, write_u64_into_buf};
+  use super::{Problem, RangeIter, sum_matching};
 
   fn is_repeated_pattern(s: &str) -> bool {
     let len = s.len();
 
-    if len % 2 != 0 {
+    if !len.is_multiple_of(2) {
       return false;
     }
 
@@ -52,24 +120,20 @@ pub mod part1_impl {
   }
 
   pub fn part1(p: &Problem) -> u64 {
-    let mut total: u64 = 0;
-    let mut buf = [0u8; 20];
-
-    for (from, to) in RangeIter::new(p.input.trim()) {
-      for n in from..=to {
-        let s = write_u64_into_buf(n, &mut buf);
-        if is_repeated_pattern(s) {
-          total += n as u64;
-        }
-      }
-    }
+    sum_matching(RangeIter::new(p.input.trim()), is_repeated_pattern)
+  }
 
-    total
+  /// `part1`, with the numbers split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part1_parallel(p: &Problem, threads: usize) -> u64 {
+    super::sum_matching_parallel(p.input.trim(), threads, is_repeated_pattern)
   }
 }
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 pub use part1_impl::part1;
+#[cfg(all(feature = "part1", feature = "parallel"))]
+pub use part1_impl::part1_parallel;
 
 // --------------------------
 // Solver — Part 2
@@ -77,7 +141,7 @@ pub use part1_impl::part1;
 
 #[cfg(feature = "part2")]
 pub mod part2_impl {
-  use super::{Problem, RangeIter, write_u64_into_buf};
+  use super::{Problem, RangeIter, sum_matching};
 
   fn is_repeated_at_least_twice(s: &str) -> bool {
     let n = s.len();
@@ -120,24 +184,52 @@ pub mod part2_impl {
   }
 
   pub fn part2(p: &Problem) -> u64 {
-    let mut total: u64 = 0;
-    let mut buf = [0u8; 20];
-
-    for (from, to) in RangeIter::new(p.input.trim()) {
-      for n in from..=to {
-        let s = write_u64_into_buf(n, &mut buf);
-        if is_repeated_at_least_twice(s) {
-          total += n;
-        }
-      }
-    }
+    sum_matching(RangeIter::new(p.input.trim()), is_repeated_at_least_twice)
+  }
 
-    total
+  /// `part2`, with the numbers split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part2_parallel(p: &Problem, threads: usize) -> u64 {
+    super::sum_matching_parallel(
+      p.input.trim(),
+      threads,
+      is_repeated_at_least_twice,
+    )
   }
 }
 
 #[cfg(feature = "part2")]
 pub use part2_impl::part2;
+#[cfg(all(feature = "part2", feature = "parallel"))]
+pub use part2_impl::part2_parallel;
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day2;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day2 {
+  const DAY: u8 = 2;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, ParseError> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p),
+    };
+
+    Ok(answer.into())
+  }
+}
 
 
 #[cfg(test)]
@@ -145,10 +237,10 @@ mod tests {
   use super::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let sample = include_str!("../sample.txt");
-    let problem = parse(sample);
+    let problem = parse(sample).unwrap();
     let result = part1(&problem);
     assert_eq!(result, 1227775554);
   }
@@ -157,8 +249,25 @@ mod tests {
   #[cfg(feature = "part2")]
   fn test_part2() {
     let sample = include_str!("../sample.txt");
-    let problem = parse(sample);
+    let problem = parse(sample).unwrap();
     let result = part2(&problem);
     assert_eq!(result, 4174379265);
   }
+
+  #[test]
+  fn test_parse_error() {
+    let err = parse("11-22,95-1x5\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError {
+        line: 1,
+        column: 11,
+        reason: aoc_core::Reason::ExpectedNumber,
+      }
+    );
+
+    // `RangeIter` reads digits only, so a sign is refused up front
+    let err = parse("+11-22,95-115\n").err().unwrap();
+    assert_eq!((err.line, err.column), (1, 1));
+  }
 }
@@ -0,0 +1,17 @@
+use fastrand::Rng;
+
+/// `n` disjoint ranges of `span` ids each, starting anywhere below 10^11 so
+/// their ids have up to 12 digits, as in the puzzle.
+pub fn ranges(seed: u64, n: usize, span: u64) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let slot = 100_000_000_000 / n.max(1) as u64;
+  assert!(span < slot, "{n} ranges of {span} ids do not fit");
+
+  let ranges: Vec<String> = (0..n as u64)
+    .map(|i| {
+      let start = 1 + i * slot + rng.u64(..slot - span);
+      format!("{start}-{}", start + span - 1)
+    })
+    .collect();
+  ranges.join(",")
+}
@@ -0,0 +1,32 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking. Crashing inputs are minimized and saved back
+// into `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver};
+use day_2::Day2;
+use day_2::range::RangeIter;
+
+// the solvers visit every id, so wide ranges are slow rather than wrong
+const MAX_IDS: u64 = 100_000;
+
+fn target(input: &str) {
+  let Ok(p) = Day2::parse(input) else {
+    return;
+  };
+  let ids = RangeIter::new(input.trim())
+    .map(|(from, to)| to.saturating_sub(from).saturating_add(1))
+    .fold(0, u64::saturating_add);
+  if ids > MAX_IDS {
+    return;
+  }
+  for part in [Part::One, Part::Two] {
+    let _ = Day2::solve(part, &p, ());
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
@@ -0,0 +1,29 @@
+// The parallel parts, split across 1 to 8 threads, must match the
+// sequential ones, on the sample and on seeded synth ranges of very
+// different sizes.
+#![cfg(all(feature = "part1", feature = "part2", feature = "parallel"))]
+
+use day_2::{parse, part1, part1_parallel, part2, part2_parallel};
+
+mod common;
+use common::synth;
+
+#[test]
+fn test_matches_sequential() {
+  let mut inputs = vec![include_str!("../sample.txt").to_string()];
+  inputs.extend(
+    [(1, 1), (3, 1_000), (40, 50), (7, 20_000)]
+      .map(|(n, span)| synth::ranges(n as u64, n, span)),
+  );
+  // one range smaller than the thread count, and one that is empty
+  inputs.push("11-12".to_string());
+  inputs.push("12-11,95-115".to_string());
+
+  for input in &inputs {
+    let p = parse(input).unwrap();
+    for threads in 1..=8 {
+      assert_eq!(part1_parallel(&p, threads), part1(&p), "{input}");
+      assert_eq!(part2_parallel(&p, threads), part2(&p), "{input}");
+    }
+  }
+}
@@ -0,0 +1,86 @@
+// Differential tests: random id ranges go through the `Solver` and through a
+// naive std reference that formats every id and compares its pieces.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver};
+use day_2::Day2;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  fn ids(input: &str) -> impl Iterator<Item = u64> + '_ {
+    input.trim().split(',').flat_map(|range| {
+      let (a, b) = range.split_once('-').unwrap();
+      a.parse::<u64>().unwrap()..=b.parse().unwrap()
+    })
+  }
+
+  /// Whether `id` is some digit sequence repeated `times` times.
+  fn repeats(id: &str, times: usize) -> bool {
+    id.len().is_multiple_of(times) && {
+      let piece = &id[..id.len() / times];
+      piece.repeat(times) == id
+    }
+  }
+
+  pub fn part1(input: &str) -> u64 {
+    ids(input).filter(|id| repeats(&id.to_string(), 2)).sum()
+  }
+
+  pub fn part2(input: &str) -> u64 {
+    ids(input)
+      .filter(|id| {
+        let id = id.to_string();
+        (2..=id.len()).any(|times| repeats(&id, times))
+      })
+      .sum()
+  }
+}
+
+/// A range of up to a few hundred ids of at most 12 digits, like the
+/// puzzle's. Half of them start just below a repeated id, so that the sums
+/// are rarely zero.
+fn range() -> impl Strategy<Value = String> {
+  let anywhere = 1..100_000_000_000u64;
+  let repeated = (1..=6u32)
+    .prop_flat_map(|digits| {
+      let piece = 10u64.pow(digits - 1)..10u64.pow(digits);
+      (piece, 2..=12 / digits as usize, 0..200u64)
+    })
+    .prop_map(|(piece, times, before)| {
+      let id: u64 = piece.to_string().repeat(times).parse().unwrap();
+      id.saturating_sub(before).max(1)
+    });
+  (prop_oneof![anywhere, repeated], 0..300u64)
+    .prop_map(|(start, len)| format!("{start}-{}", start + len))
+}
+
+fn ranges() -> impl Strategy<Value = String> {
+  prop::collection::vec(range(), 1..12).prop_map(|ranges| ranges.join(","))
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day2::parse(input).unwrap();
+  Day2::solve(part, &p, ()).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in ranges()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in ranges()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::ranges(seed, 5, 1_000);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
@@ -0,0 +1,29 @@
+// Fails if a part's stack high-water mark, parse included, grows past
+// BUDGET. Debug builds need several times the stack of release ones, so the
+// budget is sized for `cargo test`; `aoc stack` reports release numbers.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::stack::measure_stack;
+use aoc_core::{Part, Solver};
+use day_2::Day2;
+
+const INPUT: &str = include_str!("../sample.txt");
+const BUDGET: usize = 8 << 10;
+
+fn solve(part: Part) -> usize {
+  let (_, used) = measure_stack(|| {
+    let p = Day2::parse(INPUT).unwrap();
+    Day2::solve(part, &p, ()).unwrap()
+  });
+  used
+}
+
+#[test]
+fn test_stack_budget() {
+  for part in [Part::One, Part::Two] {
+    let used = solve(part);
+    println!("{part:?}: {used} bytes");
+
+    assert!(used <= BUDGET, "{part:?}: {used} bytes, budget {BUDGET}");
+  }
+}
@@ -1,24 +1,41 @@
+use std::sync::LazyLock;
+
 use day_3::prelude::*;
 use divan::black_box;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+/// Thread counts the parallel parts are split across, against the
+/// sequential `bench_part*` above them.
+#[cfg(feature = "parallel")]
+const THREADS: &[usize] = &[1, 2, 4, 8];
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let p = parse(black_box(INPUT));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
   black_box(part1(&p));
 }
 
+#[cfg(all(feature = "part1", feature = "parallel"))]
+#[divan::bench(args = THREADS)]
+fn bench_part1_parallel(threads: usize) {
+  let p = parse(black_box(INPUT.as_str())).unwrap();
+  black_box(part1_parallel(&p, threads));
+}
+
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let p = parse(black_box(INPUT));
-  black_box(part2(&p));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
+  black_box(part2(&p).unwrap());
+}
+
+#[cfg(all(feature = "part2", feature = "parallel"))]
+#[divan::bench(args = THREADS)]
+fn bench_part2_parallel(threads: usize) {
+  let p = parse(black_box(INPUT.as_str())).unwrap();
+  black_box(part2_parallel(&p, threads).unwrap());
 }
 
 fn main() {
@@ -3,6 +3,18 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::checkpoint::{Checkpoint, Reader, Writer};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::stream::{Lines, Stream};
+use aoc_core::trace::{Event, NoTrace, TraceSink};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Error, Part, Solver};
+use aoc_core::{ParseError, Reason};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 // using precomputed powers of 10 for efficiency in embedded/no_std contexts
 #[cfg(feature = "part2")]
 const K: usize = 12;
@@ -21,13 +33,20 @@ pub const POW10: [u64; K] = {
 
 
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day3, Problem, parse};
+
+  #[cfg(all(feature = "part1", feature = "part2"))]
+  pub use crate::Day3Stream;
 
-  #[cfg(not(feature = "part2"))]
-  pub use crate::part1_impl::part1;
+  #[cfg(all(feature = "part1", feature = "parallel"))]
+  pub use crate::part1_impl::part1_parallel;
+  #[cfg(feature = "part1")]
+  pub use crate::part1_impl::{part1, part1_traced};
 
+  #[cfg(all(feature = "part2", feature = "parallel"))]
+  pub use crate::part2_impl::part2_parallel;
   #[cfg(feature = "part2")]
-  pub use crate::part2_impl::part2;
+  pub use crate::part2_impl::{part2, part2_traced};
 }
 
 // --------------------------
@@ -42,16 +61,40 @@ pub struct Problem<'a> {
 // Parse
 // --------------------------
 
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+/// Every bank is a line of battery joltages, one digit each.
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  for line in input.lines() {
+    check_bank(input, line)?;
+  }
+
+  Ok(Problem { input })
+}
+
+/// Rejects the first non-digit in `line`, a sub-slice of `input`.
+fn check_bank(input: &str, line: &str) -> Result<(), ParseError> {
+  let bad = line.char_indices().find(|(_, c)| !c.is_ascii_digit());
+  if let Some((i, c)) = bad {
+    return Err(ParseError::at(input, &line[i..], Reason::Unexpected(c)));
+  }
+  Ok(())
+}
+
+/// Part 2 picks 12 batteries, so every bank needs at least that many.
+#[cfg(feature = "part2")]
+fn check_part2(input: &str, line: &str) -> Result<(), ParseError> {
+  if line.len() < K {
+    let reason = Reason::Malformed("bank has fewer than 12 batteries");
+    return Err(ParseError::at(input, line, reason));
+  }
+  Ok(())
 }
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
-  use super::Problem;
+  use super::{Event, NoTrace, Problem, TraceSink};
 
   pub fn find_max_joltage(bank: &str) -> usize {
     let bytes = bank.as_bytes();
@@ -63,9 +106,8 @@ mod part1_impl {
     for &b in bytes.iter().rev() {
       let tens_digit = (b - b'0') as usize;
 
-      let already_seen_digit = !first;
-      let not_leading_zero = tens_digit != 0;
-      if already_seen_digit && not_leading_zero {
+      // a leading `0` still picks two batteries: `05` is 5 jolts
+      if !first {
         let candidate = tens_digit * 10 + best_right;
         if candidate > acc {
           acc = candidate;
@@ -83,14 +125,24 @@ mod part1_impl {
 
 
   pub fn part1(p: &Problem) -> u64 {
+    part1_traced(p, &mut NoTrace)
+  }
+
+  /// `part1`, with the banks split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part1_parallel(p: &Problem, threads: usize) -> u64 {
+    aoc_core::parallel::sum_lines(p.input, threads, |input| {
+      part1(&Problem { input })
+    })
+  }
+
+  /// `part1`, reporting each bank's joltage to `sink`.
+  pub fn part1_traced(p: &Problem, sink: &mut impl TraceSink) -> u64 {
     p.input
       .lines()
       .map(|line| {
         let j = find_max_joltage(line) as u64;
-
-        #[cfg(feature = "std")]
-        eprintln!("Line: '{}', max joltage: {}", line, j);
-
+        sink.event(Event::Value { line, value: j });
         j
       })
       .sum()
@@ -102,23 +154,24 @@ mod part1_impl {
 // --------------------------
 #[cfg(feature = "part2")]
 mod part2_impl {
-  use super::{K, POW10, Problem};
+  use super::{
+    Event, K, NoTrace, POW10, ParseError, Problem, TraceSink, check_part2,
+  };
 
+  /// The best 12 batteries of `bank`; 0 for banks shorter than that, which
+  /// `check_part2` rejects.
   pub fn find_max_joltage(bank: &str) -> u64 {
     let bytes = bank.as_bytes();
 
-    if bytes.len() < K {
-      unreachable!("Input line too short for part 2 logic");
-    }
-
     let mut best_right: [u64; K - 1] = [0; K - 1];
     let mut acc: u64 = 0;
 
     for (seen, &b) in bytes.iter().rev().enumerate() {
       let first_digit = (b - b'0') as u64;
 
-      // sentinel to indicate we have enough digits seen
-      if best_right[10] != 0 {
+      // only once 11 batteries lie to the right; a count, not a value, as
+      // `0` batteries make zero suffixes
+      if seen >= K - 1 {
         let candidate = first_digit * POW10[K - 1] + best_right[K - 2];
         if candidate > acc {
           acc = candidate;
@@ -147,31 +200,166 @@ mod part2_impl {
     acc
   }
 
-  pub fn part2(p: &Problem) -> u64 {
-    p.input
+  /// Rejects the first bank too short to pick 12 from.
+  fn check_banks(p: &Problem) -> Result<(), ParseError> {
+    p.input.lines().try_for_each(|line| check_part2(p.input, line))
+  }
+
+  pub fn part2(p: &Problem) -> Result<u64, ParseError> {
+    part2_traced(p, &mut NoTrace)
+  }
+
+  /// `part2`, with the banks split across `threads` threads.
+  #[cfg(feature = "parallel")]
+  pub fn part2_parallel(
+    p: &Problem,
+    threads: usize,
+  ) -> Result<u64, ParseError> {
+    // checked whole first, so an error's line counts from the top
+    check_banks(p)?;
+    Ok(aoc_core::parallel::sum_lines(p.input, threads, |input| {
+      total(input, &mut NoTrace)
+    }))
+  }
+
+  /// `part2`, reporting each bank's joltage to `sink`.
+  pub fn part2_traced(
+    p: &Problem,
+    sink: &mut impl TraceSink,
+  ) -> Result<u64, ParseError> {
+    check_banks(p)?;
+    Ok(total(p.input, sink))
+  }
+
+  fn total(input: &str, sink: &mut impl TraceSink) -> u64 {
+    input
       .lines()
       .map(|line| {
         let j = find_max_joltage(line);
-
-        #[cfg(feature = "std")]
-        eprintln!("Line: '{}', max joltage: {}", line, j);
-
+        sink.event(Event::Value { line, value: j });
         j
       })
       .sum()
   }
 }
 
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day3;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day3 {
+  const DAY: u8 = 3;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, ParseError> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p)?,
+    };
+
+    Ok(answer.into())
+  }
+}
+
+
+// --------------------------
+// Stream
+// --------------------------
+
+// longest bank a stream holds; puzzle banks are 100 batteries
+#[cfg(all(feature = "part1", feature = "part2"))]
+const LINE_BYTES: usize = 128;
+
+/// Either part fed a chunk at a time; banks are summed as they complete.
+#[cfg(all(feature = "part1", feature = "part2"))]
+pub struct Day3Stream {
+  lines: Lines<LINE_BYTES>,
+  part: Part,
+  total: u64,
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Day3Stream {
+  pub const fn new(part: Part) -> Self {
+    Day3Stream { lines: Lines::new(), part, total: 0 }
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+fn bank(part: Part, line: &str) -> Result<u64, Error> {
+  check_bank(line, line)?;
+  match part {
+    Part::One => Ok(part1_impl::find_max_joltage(line) as u64),
+    Part::Two => {
+      check_part2(line, line)?;
+      Ok(part2_impl::find_max_joltage(line))
+    }
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Stream for Day3Stream {
+  type Answer = u64;
+
+  fn feed(&mut self, chunk: &[u8]) -> Result<(), Error> {
+    let (part, total) = (self.part, &mut self.total);
+    self.lines.feed(chunk, |line| {
+      *total += bank(part, line)?;
+      Ok(())
+    })
+  }
+
+  fn finish(mut self) -> Result<u64, Error> {
+    let (part, total) = (self.part, &mut self.total);
+    self.lines.finish(|line| {
+      *total += bank(part, line)?;
+      Ok(())
+    })?;
+    Ok(self.total)
+  }
+}
+
+/// The running total and any half-received bank.
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Checkpoint for Day3Stream {
+  type Context = ();
+  const TAG: [u8; 4] = *b"3str";
+  const FIELDS: usize = 1 + 8 + Lines::<LINE_BYTES>::FIELDS;
+
+  fn write(&self, w: &mut Writer<'_>) -> Result<(), Error> {
+    w.part(self.part)?;
+    w.u64(self.total)?;
+    self.lines.write(w)
+  }
+
+  fn read(_: (), r: &mut Reader<'_>) -> Result<Self, Error> {
+    let part = r.part()?;
+    let total = r.u64()?;
+    Ok(Day3Stream { lines: Lines::read((), r)?, part, total })
+  }
+}
+
 
 #[cfg(test)]
 mod tests {
   use super::prelude::*;
+  use aoc_core::{ParseError, Reason};
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part1(&problem);
     assert_eq!(result, 357);
   }
@@ -180,8 +368,30 @@ mod tests {
   #[cfg(feature = "part2")]
   fn test_part2() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part2(&problem);
-    assert_eq!(result, 3121910778619);
+    assert_eq!(result, Ok(3121910778619));
+  }
+
+  #[test]
+  #[cfg(feature = "part2")]
+  fn test_part2_zeros_and_short_banks() {
+    let problem = parse("100000000000
+").unwrap();
+    assert_eq!(part2(&problem), Ok(100000000000));
+
+    let err = part2(&parse("987654321111
+12345
+").unwrap()).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+  }
+
+  #[test]
+  fn test_parse_error() {
+    let err = parse("987654321111111\n81111a111111119\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 2, column: 6, reason: Reason::Unexpected('a') }
+    );
   }
 }
@@ -0,0 +1,10 @@
+use fastrand::Rng;
+
+/// `n` banks of `len` batteries labelled 0 to 9.
+pub fn banks(seed: u64, n: usize, len: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let banks: Vec<String> = (0..n)
+    .map(|_| (0..len).map(|_| char::from(b'0' + rng.u8(0..=9))).collect())
+    .collect();
+  banks.join("\n")
+}
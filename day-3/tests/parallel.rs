@@ -0,0 +1,23 @@
+// The parallel parts, split across 1 to 8 threads, must match the
+// sequential ones, on the sample and on seeded synth banks.
+#![cfg(all(feature = "part1", feature = "part2", feature = "parallel"))]
+
+use day_3::prelude::*;
+
+mod common;
+use common::synth;
+
+#[test]
+fn test_matches_sequential() {
+  let mut inputs = vec![include_str!("../sample.txt").to_string()];
+  inputs.extend((0..4).map(|seed| synth::banks(seed, 1 << (seed * 3), 100)));
+  inputs.push(String::new());
+
+  for input in &inputs {
+    let p = parse(input).unwrap();
+    for threads in 1..=8 {
+      assert_eq!(part1_parallel(&p, threads), part1(&p));
+      assert_eq!(part2_parallel(&p, threads), part2(&p));
+    }
+  }
+}
@@ -0,0 +1,87 @@
+// Differential tests: random battery banks go through the `Solver` and
+// through a naive std reference that tries every pick of batteries.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver};
+use day_3::Day3;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  fn banks(input: &str) -> impl Iterator<Item = Vec<u64>> + '_ {
+    input
+      .lines()
+      .map(|l| l.bytes().map(|b| (b - b'0') as u64).collect())
+  }
+
+  pub fn part1(input: &str) -> u64 {
+    banks(input)
+      .map(|bank| {
+        let mut best = 0;
+        for i in 0..bank.len() {
+          for j in i + 1..bank.len() {
+            best = best.max(bank[i] * 10 + bank[j]);
+          }
+        }
+        best
+      })
+      .sum()
+  }
+
+  /// The largest `k`-digit joltage keeping the order of `bank`: the first
+  /// digit is the largest that leaves `k - 1` batteries after it.
+  fn largest(bank: &[u64], k: usize) -> u64 {
+    if k == 0 {
+      return 0;
+    }
+    let window = &bank[..bank.len() - (k - 1)];
+    let digit = *window.iter().max().unwrap();
+    let at = window.iter().position(|&d| d == digit).unwrap();
+    digit * 10u64.pow(k as u32 - 1) + largest(&bank[at + 1..], k - 1)
+  }
+
+  /// `None` if any bank is too short to pick 12 from.
+  pub fn part2(input: &str) -> Option<u64> {
+    banks(input)
+      .map(|bank| (bank.len() >= 12).then(|| largest(&bank, 12)))
+      .sum()
+  }
+}
+
+/// Banks of batteries labelled 0 to 9, some too short for part 2's 12.
+fn banks() -> impl Strategy<Value = String> {
+  let bank = prop::collection::vec(0..=9u8, 0..=100)
+    .prop_map(|digits| digits.iter().map(|d| (b'0' + d) as char).collect());
+  prop::collection::vec(bank, 0..20)
+    .prop_map(|banks: Vec<String>| banks.join("\n"))
+}
+
+fn solve(part: Part, input: &str) -> Option<Answer> {
+  let p = Day3::parse(input).unwrap();
+  Day3::solve(part, &p, ()).ok()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in banks()) {
+    let expected = naive::part1(&input).into();
+    prop_assert_eq!(solve(Part::One, &input), Some(expected));
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in banks()) {
+    let expected = naive::part2(&input).map(Answer::from);
+    prop_assert_eq!(solve(Part::Two, &input), expected);
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>(), len in 0..=50usize) {
+    let input = synth::banks(seed, 10, len);
+    let expected = naive::part1(&input).into();
+    prop_assert_eq!(solve(Part::One, &input), Some(expected));
+    let expected = naive::part2(&input).map(Answer::from);
+    prop_assert_eq!(solve(Part::Two, &input), expected);
+  }
+}
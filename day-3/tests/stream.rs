@@ -0,0 +1,72 @@
+// The sample fed in two chunks split at every byte offset, and a byte at a
+// time, must give the same answers as solving it whole.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::checkpoint::Checkpoint;
+use aoc_core::stream::Stream;
+use aoc_core::{Error, ParseError, Part, Reason, Solver};
+use day_3::prelude::{Day3, Day3Stream};
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn fed<'a>(
+  part: Part,
+  chunks: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<u64, Error> {
+  let mut stream = Day3Stream::new(part);
+  for chunk in chunks {
+    stream.feed(chunk)?;
+  }
+  stream.finish()
+}
+
+#[test]
+fn test_every_split() {
+  for part in [Part::One, Part::Two] {
+    let p = Day3::parse(INPUT).unwrap();
+    let whole = u64::try_from(Day3::solve(part, &p, ()).unwrap()).unwrap();
+
+    for at in 0..=INPUT.len() {
+      let (head, tail) = INPUT.as_bytes().split_at(at);
+      assert_eq!(fed(part, [head, tail]), Ok(whole), "{part:?}, split {at}");
+    }
+    assert_eq!(fed(part, INPUT.as_bytes().chunks(1)), Ok(whole), "{part:?}");
+  }
+}
+
+#[test]
+fn test_parse_error() {
+  let err = fed(Part::One, [&b"98765\n8111"[..], b"1a1\n"]).unwrap_err();
+  assert_eq!(
+    err,
+    Error::Parse(ParseError {
+      line: 2,
+      column: 6,
+      reason: Reason::Unexpected('a')
+    })
+  );
+
+  let err = fed(Part::Two, [&b"98765\n"[..]]).unwrap_err();
+  assert!(matches!(err, Error::Parse(ParseError { line: 1, .. })));
+}
+
+#[test]
+fn test_checkpoint() {
+  for part in [Part::One, Part::Two] {
+    let p = Day3::parse(INPUT).unwrap();
+    let whole = u64::try_from(Day3::solve(part, &p, ()).unwrap()).unwrap();
+
+    // saved at every offset, mid-line or not, and finished from the bytes
+    for at in 0..=INPUT.len() {
+      let (head, tail) = INPUT.as_bytes().split_at(at);
+      let mut stream = Day3Stream::new(part);
+      stream.feed(head).unwrap();
+
+      let mut buf = [0u8; Day3Stream::BYTES];
+      let used = stream.save(&mut buf).unwrap();
+      let mut resumed = Day3Stream::restore((), &buf[..used]).unwrap();
+      resumed.feed(tail).unwrap();
+      assert_eq!(resumed.finish(), Ok(whole), "{part:?}, saved at {at}");
+    }
+  }
+}
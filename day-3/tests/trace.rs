@@ -0,0 +1,33 @@
+// Trace events tell the same story as the answers they lead to.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::trace::Collect;
+use day_3::prelude::*;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+/// The joltages reported for each bank, summed.
+fn total(events: &[String]) -> u64 {
+  events
+    .iter()
+    .map(|e| e.split_once(" => ").unwrap().1.parse::<u64>().unwrap())
+    .sum()
+}
+
+#[test]
+fn test_trace() {
+  let p = parse(INPUT).unwrap();
+
+  let mut sink = Collect::default();
+  let answer = part1_traced(&p, &mut sink);
+  assert_eq!(answer, part1(&p));
+  assert_eq!(sink.events[0], "\"987654321111111\" => 98");
+  assert_eq!(sink.events.len(), INPUT.lines().count());
+  assert_eq!(total(&sink.events), answer);
+
+  let mut sink = Collect::default();
+  let answer = part2_traced(&p, &mut sink).unwrap();
+  assert_eq!(Ok(answer), part2(&p));
+  assert_eq!(sink.events[0], "\"987654321111111\" => 987654321111");
+  assert_eq!(total(&sink.events), answer);
+}
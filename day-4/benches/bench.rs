@@ -1,26 +1,24 @@
+use std::sync::LazyLock;
+
 use day_4::prelude::*;
 #[cfg(feature = "part2")]
 use day_4::tinysetqueue::prelude::*;
 use divan::black_box;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
-
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let p = parse(black_box(INPUT));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
   black_box(part1(&p));
 }
 
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let problem = parse(black_box(INPUT));
-  let total = problem.width * problem.height;
+  let problem = parse(black_box(INPUT.as_str())).unwrap();
+  let (grid, total) = (problem.grid(), requirements(&problem).cells);
 
   // allocate caller-managed buffers once per run to reflect the no_alloc API
   let mut present = vec![false; total];
@@ -31,7 +29,7 @@ fn bench_part2() {
   let mut queue =
     TinySetQueue::new(&mut queue_buf, &mut in_queue, MembershipMode::InQueue);
 
-  black_box(part2(&problem, &mut present, &mut degree, &mut queue));
+  black_box(part2(&grid, &mut present, &mut degree, &mut queue));
 }
 
 fn main() {
@@ -0,0 +1,47 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_4::prelude::*;
+#[cfg(feature = "part2")]
+use day_4::tinysetqueue::prelude::*;
+use divan::{Bencher, black_box};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Side of the square grid.
+const SIZES: &[usize] = &[32, 64, 128, 256];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, side: usize) {
+  let input = synth::grid(SEED, side, side);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part1(&p));
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, side: usize) {
+  let input = synth::grid(SEED, side, side);
+  bencher.bench(|| {
+    let problem = parse(black_box(&input)).unwrap();
+    let (grid, total) = (problem.grid(), requirements(&problem).cells);
+    let mut present = vec![false; total];
+    let mut degree = vec![0u8; total];
+    let mut queue_buf = vec![0usize; total];
+    let mut in_queue = vec![false; total];
+    let mut queue =
+      TinySetQueue::new(&mut queue_buf, &mut in_queue, MembershipMode::InQueue);
+    black_box(part2(&grid, &mut present, &mut degree, &mut queue));
+  });
+}
+
+fn main() {
+  divan::main();
+}
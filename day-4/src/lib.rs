@@ -3,62 +3,177 @@
 #[cfg(any(feature = "std", test))]
 extern crate std;
 
+use core::fmt;
+
+#[cfg(any(feature = "part1", feature = "part2"))]
+use aoc_core::Error;
+#[cfg(feature = "part2")]
+use aoc_core::Workspace;
+#[cfg(feature = "part1")]
+use aoc_core::stream::{Lines, Stream};
+use aoc_core::work::Counters;
+use aoc_core::{ParseError, Reason};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::workspace::Carve;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
 #[cfg(feature = "part2")]
 pub mod tinysetqueue;
 
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day4, Problem, Stats, parse};
 
-  #[cfg(not(feature = "part2"))]
-  pub use crate::part1_impl::part1;
+  #[cfg(feature = "part2")]
+  pub use crate::{Grid, Requirements, Scratch, requirements};
+
+  #[cfg(feature = "part1")]
+  pub use crate::{Day4Stream, part1_impl::part1};
 
   #[cfg(feature = "part2")]
   pub use crate::part2_impl::part2;
+  #[cfg(all(feature = "part2", feature = "instrument"))]
+  pub use crate::part2_impl::part2_instrumented;
 }
 
 // --------------------------
 // Data Model
 // --------------------------
 
-#[cfg(not(feature = "part2"))]
 pub struct Problem<'a> {
   pub input: &'a str,
 }
 
+/// Part 2 indexes flat buffers, so it needs the grid's dimensions up front.
 #[cfg(feature = "part2")]
-pub struct Problem<'a> {
+pub struct Grid<'a> {
   pub input: &'a str,
   pub width: usize,
   pub height: usize,
 }
 
+/// Caller-owned working memory for part 2, each slice `width * height` long.
+#[cfg(feature = "part2")]
+#[derive(Default)]
+pub struct Scratch<'a> {
+  pub present: &'a mut [bool],
+  pub degree: &'a mut [u8],
+  pub queue_buf: &'a mut [usize],
+  pub in_queue: &'a mut [bool],
+}
+
+/// Work part 2's queue did peeling rolls off the grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+  /// Rolls scheduled for removal.
+  pub pushes: u64,
+  /// Neighbour counts lowered by a removal.
+  pub updates: u64,
+}
+
+impl Counters for Stats {}
+
+impl fmt::Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "pushes: {}", self.pushes)?;
+    writeln!(f, "updates: {}", self.updates)
+  }
+}
+
 
 // --------------------------
 // Parse
 // --------------------------
 
-#[cfg(not(feature = "part2"))]
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+/// Checks the grid is rectangular and holds only rolls (`@`) and floor (`.`).
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  let width = input.lines().next().unwrap_or("").len();
+
+  for line in input.lines() {
+    check_row(input, line, width)?;
+  }
+
+  Ok(Problem { input })
+}
+
+/// Checks `line`, a sub-slice of `input`, is a `width`-wide row.
+fn check_row(input: &str, line: &str, width: usize) -> Result<(), ParseError> {
+  let bad = line.char_indices().find(|&(_, c)| c != '@' && c != '.');
+  if let Some((i, c)) = bad {
+    return Err(ParseError::at(input, &line[i..], Reason::Unexpected(c)));
+  }
+  if line.len() != width {
+    let reason = Reason::Malformed("row width differs from the first row");
+    return Err(ParseError::at(input, line, reason));
+  }
+  Ok(())
+}
+
+#[cfg(feature = "part2")]
+impl<'a> Problem<'a> {
+  pub fn grid(&self) -> Grid<'a> {
+    let height = self.input.lines().count();
+    let width = self.input.lines().next().unwrap_or("").len();
+
+    Grid { input: self.input, width, height }
+  }
+}
+
+// --------------------------
+// Scratch
+// --------------------------
+
+/// Part 2's working memory for one grid: `cells` values in each of the four
+/// buffers, `bytes` of workspace in all.
+#[cfg(feature = "part2")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Requirements {
+  pub cells: usize,
+  pub bytes: usize,
 }
 
 #[cfg(feature = "part2")]
-pub fn parse<'a>(input: &'a str) -> Problem<'a> {
-  let height = input.lines().count();
-  let width = input.lines().next().unwrap_or("").len();
+pub fn requirements(p: &Problem) -> Requirements {
+  let grid = p.grid();
+  let cells = grid.width * grid.height;
+  let bytes = 2 * Workspace::size_of::<bool>(cells)
+    + Workspace::size_of::<u8>(cells)
+    + Workspace::size_of::<usize>(cells);
+
+  Requirements { cells, bytes }
+}
 
-  Problem { input, width, height }
+#[cfg(feature = "part2")]
+impl Requirements {
+  /// Cuts a `Scratch` out of `ws`, which needs `bytes` left.
+  pub fn carve<'a>(
+    &self,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    Ok(Scratch {
+      present: ws.take(self.cells, || false)?,
+      degree: ws.take(self.cells, || 0)?,
+      queue_buf: ws.take(self.cells, || 0)?,
+      in_queue: ws.take(self.cells, || false)?,
+    })
+  }
 }
 
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
   use super::Problem;
 
-  fn process_row(prev: Option<&[u8]>, cur: &[u8], next: Option<&[u8]>) -> u64 {
+  pub fn process_row(
+    prev: Option<&[u8]>,
+    cur: &[u8],
+    next: Option<&[u8]>,
+  ) -> u64 {
     cur
       .iter()
       .enumerate()
@@ -122,14 +237,113 @@ mod part1_impl {
   }
 }
 
+// --------------------------
+// Stream — Part 1
+// --------------------------
+
+// widest row a stream holds; puzzle grids are under 140 wide
+#[cfg(feature = "part1")]
+const MAX_WIDTH: usize = 256;
+
+/// Part 1 fed a chunk at a time. A roll's neighbours are all in the rows
+/// either side of it, so only the two rows before the latest are kept; part
+/// 2 peels rolls anywhere in the grid and has no stream.
+#[cfg(feature = "part1")]
+pub struct Day4Stream {
+  lines: Lines<MAX_WIDTH>,
+  window: Window,
+}
+
+/// The last two rows seen, oldest first, and the rolls scored so far.
+#[cfg(feature = "part1")]
+struct Window {
+  rows: [[u8; MAX_WIDTH]; 2],
+  seen: usize,
+  width: usize,
+  total: u64,
+}
+
+#[cfg(feature = "part1")]
+impl Window {
+  fn prev(&self) -> Option<&[u8]> {
+    (self.seen > 1).then(|| &self.rows[0][..self.width])
+  }
+
+  fn line(&mut self, line: &str) -> Result<(), Error> {
+    if self.seen == 0 {
+      Error::check_capacity("row width", MAX_WIDTH, line.len())?;
+      self.width = line.len();
+    }
+    check_row(line, line, self.width)?;
+
+    if self.seen > 0 {
+      let cur = &self.rows[1][..self.width];
+      self.total +=
+        part1_impl::process_row(self.prev(), cur, Some(line.as_bytes()));
+    }
+
+    self.rows.swap(0, 1);
+    self.rows[1][..self.width].copy_from_slice(line.as_bytes());
+    self.seen += 1;
+    Ok(())
+  }
+
+  fn finish(&self) -> u64 {
+    if self.seen == 0 {
+      return self.total;
+    }
+    let cur = &self.rows[1][..self.width];
+    self.total + part1_impl::process_row(self.prev(), cur, None)
+  }
+}
+
+#[cfg(feature = "part1")]
+impl Day4Stream {
+  pub const fn new() -> Self {
+    Day4Stream {
+      lines: Lines::new(),
+      window: Window { rows: [[0; MAX_WIDTH]; 2], seen: 0, width: 0, total: 0 },
+    }
+  }
+}
+
+#[cfg(feature = "part1")]
+impl Default for Day4Stream {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(feature = "part1")]
+impl Stream for Day4Stream {
+  type Answer = u64;
+
+  fn feed(&mut self, chunk: &[u8]) -> Result<(), Error> {
+    self.lines.feed(chunk, |line| self.window.line(line))
+  }
+
+  fn finish(mut self) -> Result<u64, Error> {
+    self.lines.finish(|line| self.window.line(line))?;
+    Ok(self.window.finish())
+  }
+}
+
 // --------------------------
 // Solver — Part 2
 // --------------------------
 
 #[cfg(feature = "part2")]
 mod part2_impl {
-  use super::Problem;
-  use crate::tinysetqueue::TinySetQueue;
+  use aoc_core::work::{NoTally, Tally};
+
+  use super::{Grid, Stats};
+  use crate::tinysetqueue::{PushResult, TinySetQueue};
+
+  fn tally_push(tally: &mut impl Tally<Stats>, pushed: PushResult) {
+    if let PushResult::Inserted = pushed {
+      tally.tally(|s| s.pushes += 1);
+    }
+  }
 
   #[allow(clippy::too_many_arguments)]
   fn compute_degree_row(
@@ -141,6 +355,7 @@ mod part2_impl {
     present: &mut [bool],
     degree: &mut [u8],
     queue: &mut TinySetQueue<usize>,
+    tally: &mut impl Tally<Stats>,
   ) {
     let idx_base = y * width;
 
@@ -191,9 +406,10 @@ mod part2_impl {
       degree[idx] = count;
 
       if count < 4 {
-        queue
+        let pushed = queue
           .push(idx)
           .expect("queue overflow during initial degree compute");
+        tally_push(tally, pushed);
       }
     }
   }
@@ -255,10 +471,33 @@ mod part2_impl {
 
 
   pub fn part2(
-    p: &Problem,
+    p: &Grid,
+    present: &mut [bool],
+    degree: &mut [u8],
+    queue: &mut TinySetQueue<usize>,
+  ) -> u64 {
+    part2_tallied(p, present, degree, queue, &mut NoTally)
+  }
+
+  /// `part2`, with the work its queue did.
+  #[cfg(feature = "instrument")]
+  pub fn part2_instrumented(
+    p: &Grid,
     present: &mut [bool],
     degree: &mut [u8],
     queue: &mut TinySetQueue<usize>,
+  ) -> (u64, Stats) {
+    let mut stats = Stats::default();
+    let answer = part2_tallied(p, present, degree, queue, &mut stats);
+    (answer, stats)
+  }
+
+  fn part2_tallied(
+    p: &Grid,
+    present: &mut [bool],
+    degree: &mut [u8],
+    queue: &mut TinySetQueue<usize>,
+    tally: &mut impl Tally<Stats>,
   ) -> u64 {
     let width = p.width;
     let height = p.height;
@@ -287,6 +526,7 @@ mod part2_impl {
           present,
           degree,
           queue,
+          tally,
         );
 
         // Slide the window forward after processing the middle row
@@ -308,6 +548,7 @@ mod part2_impl {
         present,
         degree,
         queue,
+        tally,
       );
     }
 
@@ -324,11 +565,13 @@ mod part2_impl {
         }
 
         degree[n_idx] -= 1;
+        tally.tally(|s| s.updates += 1);
 
         if degree[n_idx] < 4 {
-          queue
+          let pushed = queue
             .push(n_idx)
             .expect("queue overflow during removal phase");
+          tally_push(tally, pushed);
         }
       }
     }
@@ -337,6 +580,65 @@ mod part2_impl {
   }
 }
 
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day4;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day4 {
+  const DAY: u8 = 4;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = Scratch<'a>;
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Problem<'_>, Error> {
+    Ok(parse(input)?)
+  }
+
+  fn solve<'a>(
+    part: Part,
+    p: &Problem<'a>,
+    scratch: Scratch<'a>,
+  ) -> Result<Answer, Error> {
+    use crate::tinysetqueue::prelude::{MembershipMode, TinySetQueue};
+
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => {
+        let cells = requirements(p).cells;
+        let Scratch { present, degree, queue_buf, in_queue } = scratch;
+        Error::check_capacity("present cells", present.len(), cells)?;
+        Error::check_capacity("degree cells", degree.len(), cells)?;
+        Error::check_capacity("queue cells", queue_buf.len(), cells)?;
+        Error::check_capacity("in-queue cells", in_queue.len(), cells)?;
+
+        let mut queue =
+          TinySetQueue::new(queue_buf, in_queue, MembershipMode::InQueue);
+        part2_impl::part2(&p.grid(), present, degree, &mut queue)
+      }
+    };
+
+    Ok(answer.into())
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Carve for Day4 {
+  fn bytes(p: &Problem) -> usize {
+    requirements(p).bytes
+  }
+
+  fn carve<'a>(
+    p: &Problem,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    requirements(p).carve(ws)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::prelude::*;
@@ -344,14 +646,28 @@ mod tests {
   use std::vec;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part1(&problem);
     assert_eq!(result, 13);
   }
 
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{ParseError, Reason};
+
+    let err = parse("..@@.\n@@#..\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 2, column: 3, reason: Reason::Unexpected('#') }
+    );
+
+    let err = parse("..@@.\n@@.\n").err().unwrap();
+    assert_eq!((err.line, err.column), (2, 1));
+  }
+
   // Part 2 works with externally allocated buffers, rather like a usb device controller or similar embedded problems
   #[test]
   #[cfg(feature = "part2")]
@@ -359,9 +675,9 @@ mod tests {
     use crate::tinysetqueue::prelude::{MembershipMode, TinySetQueue};
 
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let grid = parse(input).unwrap().grid();
 
-    let total = problem.width * problem.height;
+    let total = grid.width * grid.height;
 
     // working buffers
     let mut present = vec![false; total];
@@ -373,8 +689,45 @@ mod tests {
     let mut queue =
       TinySetQueue::new(&mut queue_buf, &mut binding, MembershipMode::InQueue);
 
-    let result = part2(&problem, &mut present, &mut degree, &mut queue);
+    let result = part2(&grid, &mut present, &mut degree, &mut queue);
 
     assert_eq!(result, 43);
   }
+
+  #[test]
+  #[cfg(all(feature = "part2", feature = "instrument"))]
+  fn test_part2_instrumented() {
+    use crate::tinysetqueue::prelude::{MembershipMode, TinySetQueue};
+
+    let input = include_str!("../sample.txt");
+    let grid = parse(input).unwrap().grid();
+
+    let total = grid.width * grid.height;
+    let mut present = vec![false; total];
+    let mut degree = vec![0u8; total];
+    let mut queue_buf = vec![0usize; total];
+    let mut binding = vec![false; total];
+    let mut queue =
+      TinySetQueue::new(&mut queue_buf, &mut binding, MembershipMode::InQueue);
+
+    let (result, stats) =
+      part2_instrumented(&grid, &mut present, &mut degree, &mut queue);
+
+    // every roll removed was pushed exactly once
+    assert_eq!(result, 43);
+    assert_eq!(stats, Stats { pushes: 43, updates: 104 });
+  }
+
+  #[test]
+  #[cfg(all(feature = "part1", feature = "part2"))]
+  fn test_capacity_exceeded() {
+    use aoc_core::{Error, Part, Solver};
+
+    let problem = parse(include_str!("../sample.txt")).unwrap();
+    let err = Day4::solve(Part::Two, &problem, Scratch::default());
+    assert!(matches!(
+      err,
+      Err(Error::CapacityExceeded { limit: 0, needed: 100, .. })
+    ));
+  }
 }
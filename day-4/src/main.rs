@@ -1,38 +1,30 @@
+use aoc_core::ParseError;
 use day_4::prelude::*;
-#[cfg(feature = "part2")]
 use day_4::tinysetqueue::prelude::*;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?;
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+  let p1 = part1(&problem);
+  println!("Part 1: {p1}");
 
-fn main() {
-  let problem = parse(INPUT);
+  // allocate caller-managed working buffers for part2
+  let grid = problem.grid();
+  let total = requirements(&problem).cells;
 
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&problem);
-    println!("Part 1: {p1}");
-  }
+  // working buffers
+  let mut present = vec![false; total];
+  let mut degree = vec![0u8; total];
 
-  #[cfg(feature = "part2")]
-  {
-    // allocate caller-managed working buffers for part2
-    let total = problem.width * problem.height;
+  let mut queue_buf = vec![0usize; total];
+  let mut in_queue = vec![false; total]; // ← FIX: must bind to a variable
 
-    // working buffers
-    let mut present = vec![false; total];
-    let mut degree = vec![0u8; total];
+  let mut queue =
+    TinySetQueue::new(&mut queue_buf, &mut in_queue, MembershipMode::InQueue);
 
-    let mut queue_buf = vec![0usize; total];
-    let mut in_queue = vec![false; total]; // ← FIX: must bind to a variable
+  let p2 = part2(&grid, &mut present, &mut degree, &mut queue);
+  println!("Part 2: {p2}");
 
-    let mut queue =
-      TinySetQueue::new(&mut queue_buf, &mut in_queue, MembershipMode::InQueue);
-
-    let p2 = part2(&problem, &mut present, &mut degree, &mut queue);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
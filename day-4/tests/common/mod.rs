@@ -0,0 +1,3 @@
+// Shared by this day's integration tests and, through `#[path]`, its
+// benches; not part of the library.
+pub mod synth;
@@ -0,0 +1,15 @@
+use fastrand::Rng;
+
+/// A `width` by `height` grid with a roll on about 60% of the cells, dense
+/// enough for part 2 to take several rounds.
+pub fn grid(seed: u64, width: usize, height: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let rows: Vec<String> = (0..height)
+    .map(|_| {
+      (0..width)
+        .map(|_| if rng.u8(..10) < 6 { '@' } else { '.' })
+        .collect()
+    })
+    .collect();
+  rows.join("\n")
+}
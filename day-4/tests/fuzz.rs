@@ -0,0 +1,26 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking. Crashing inputs are minimized and saved back
+// into `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver, Workspace};
+use day_4::prelude::*;
+
+fn target(input: &str) {
+  let Ok(p) = Day4::parse(input) else {
+    return;
+  };
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+
+  for part in [Part::One, Part::Two] {
+    let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+    let _ = Day4::solve(part, &p, scratch);
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
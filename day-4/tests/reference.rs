@@ -0,0 +1,105 @@
+// Differential tests: random roll layouts go through the `Solver` and
+// through a naive std reference that sweeps the whole grid until it settles.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver, Workspace};
+use day_4::prelude::*;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  fn grid(input: &str) -> Vec<Vec<bool>> {
+    input
+      .lines()
+      .map(|l| l.bytes().map(|b| b == b'@').collect())
+      .collect()
+  }
+
+  fn neighbours(grid: &[Vec<bool>], x: usize, y: usize) -> usize {
+    let mut count = 0;
+    for ny in y.saturating_sub(1)..=y + 1 {
+      for nx in x.saturating_sub(1)..=x + 1 {
+        let roll = grid.get(ny).and_then(|row| row.get(nx));
+        if (nx, ny) != (x, y) && roll == Some(&true) {
+          count += 1;
+        }
+      }
+    }
+    count
+  }
+
+  fn accessible(grid: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+      for (x, &roll) in row.iter().enumerate() {
+        if roll && neighbours(grid, x, y) < 4 {
+          found.push((x, y));
+        }
+      }
+    }
+    found
+  }
+
+  pub fn part1(input: &str) -> u64 {
+    accessible(&grid(input)).len() as u64
+  }
+
+  pub fn part2(input: &str) -> u64 {
+    let mut grid = grid(input);
+    let mut removed = 0;
+    loop {
+      let found = accessible(&grid);
+      if found.is_empty() {
+        return removed;
+      }
+      for &(x, y) in &found {
+        grid[y][x] = false;
+      }
+      removed += found.len() as u64;
+    }
+  }
+}
+
+/// Rectangular grids; the density varies so that some are packed tightly
+/// enough for part 2 to leave rolls behind.
+fn grids() -> impl Strategy<Value = String> {
+  (1..=16usize, 1..=16usize, 0.0..1.0f64).prop_flat_map(
+    |(width, height, density)| {
+      let cell = prop::bool::weighted(density)
+        .prop_map(|roll| if roll { '@' } else { '.' });
+      let row = prop::collection::vec(cell, width)
+        .prop_map(|cells| cells.into_iter().collect::<String>());
+      prop::collection::vec(row, height).prop_map(|rows| rows.join("\n"))
+    },
+  )
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day4::parse(input).unwrap();
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+
+  Day4::solve(part, &p, scratch).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in grids()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in grids()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::grid(seed, 20, 20);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
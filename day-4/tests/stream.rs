@@ -0,0 +1,66 @@
+// The sample fed in two chunks split at every byte offset, and a byte at a
+// time, must give part 1's answer for the whole grid.
+#![cfg(feature = "part1")]
+
+use aoc_core::stream::Stream;
+use aoc_core::{Error, ParseError, Reason};
+use day_4::prelude::{Day4Stream, parse, part1};
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn fed<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Result<u64, Error> {
+  let mut stream = Day4Stream::new();
+  for chunk in chunks {
+    stream.feed(chunk)?;
+  }
+  stream.finish()
+}
+
+#[test]
+fn test_every_split() {
+  let whole = part1(&parse(INPUT).unwrap());
+
+  for at in 0..=INPUT.len() {
+    let (head, tail) = INPUT.as_bytes().split_at(at);
+    assert_eq!(fed([head, tail]), Ok(whole), "split {at}");
+  }
+  assert_eq!(fed(INPUT.as_bytes().chunks(1)), Ok(whole));
+
+  // one and two rows have no full window
+  assert_eq!(fed([&b"@@\n"[..]]), Ok(2));
+  assert_eq!(fed([&b"@@\n@."[..]]), Ok(3));
+  assert_eq!(fed([]), Ok(0));
+}
+
+#[test]
+fn test_parse_error() {
+  let err = fed([&b"..@\n.."[..], b"#\n"]).unwrap_err();
+  assert_eq!(
+    err,
+    Error::Parse(ParseError {
+      line: 2,
+      column: 3,
+      reason: Reason::Unexpected('#')
+    })
+  );
+
+  let err = fed([&b"..@\n..\n"[..]]).unwrap_err();
+  assert!(matches!(err, Error::Parse(ParseError { line: 2, .. })));
+}
+
+#[test]
+fn test_row_too_wide() {
+  // a row wider than the stream holds, whole in one chunk or split
+  let row = [b'.'; 300];
+  for at in [0, 150] {
+    let (head, tail) = row.split_at(at);
+    let err = fed([head, tail, b"\n"]).unwrap_err();
+    assert!(matches!(
+      err,
+      Error::CapacityExceeded { limit: 256, needed: 300, .. }
+    ));
+  }
+
+  let err = fed([&row[..], b"\n"].concat().chunks(400)).unwrap_err();
+  assert!(matches!(err, Error::CapacityExceeded { limit: 256, .. }));
+}
@@ -1,3 +1,5 @@
+use std::sync::LazyLock;
+
 use day_5::prelude::*;
 use divan::black_box;
 
@@ -6,17 +8,13 @@ mod std_parse {
 }
 use std_parse::parse_std;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
-
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
   // Parse fresh each time because part1 MUTATES the ranges array
-  let (mut ranges, ingredients) = parse_std(black_box(INPUT));
+  let (mut ranges, ingredients) = parse_std(black_box(INPUT.as_str())).unwrap();
 
   black_box(part1(&mut ranges, &ingredients));
 }
@@ -24,7 +22,7 @@ fn bench_part1() {
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let (mut ranges, ingredients) = parse_std(black_box(INPUT));
+  let (mut ranges, ingredients) = parse_std(black_box(INPUT.as_str())).unwrap();
 
   black_box(part2(&mut ranges, &ingredients));
 }
@@ -0,0 +1,43 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_5::prelude::*;
+use divan::{Bencher, black_box};
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+use std_parse::parse_std;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Ranges, and as many ingredient ids.
+const SIZES: &[usize] = &[100, 1_000, 10_000];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, n: usize) {
+  let input = synth::database(SEED, n, n);
+  bencher.bench(|| {
+    let (mut ranges, ingredients) = parse_std(black_box(&input)).unwrap();
+    black_box(part1(&mut ranges, &ingredients));
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, n: usize) {
+  let input = synth::database(SEED, n, n);
+  bencher.bench(|| {
+    let (mut ranges, ingredients) = parse_std(black_box(&input)).unwrap();
+    black_box(part2(&mut ranges, &ingredients));
+  });
+}
+
+fn main() {
+  divan::main();
+}
@@ -6,13 +6,27 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+use aoc_core::parse::{number, split_once};
+use aoc_core::{Error, ParseError, Reason, Workspace};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::workspace::Carve;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 
 pub mod prelude {
-  #[cfg(not(feature = "part2"))]
+  pub use crate::{
+    Day5, Problem, Requirements, Scratch, for_each_entry, parse, requirements,
+  };
+
+  #[cfg(feature = "part1")]
   pub mod part1_solver {
     include!("./part1.rs");
   }
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub use part1_solver::part1;
 
   #[cfg(feature = "part2")]
@@ -22,11 +36,11 @@ pub mod prelude {
   #[cfg(feature = "part2")]
   pub use part2_solver::part2;
 
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub mod ranged_search {
     include!("./ranged_search.rs");
   }
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub use ranged_search::RangeSearch;
 
   pub mod merge {
@@ -34,3 +48,175 @@ pub mod prelude {
   }
   pub use merge::merge_intervals;
 }
+
+// --------------------------
+// Data Model
+// --------------------------
+
+pub struct Problem<'a> {
+  pub input: &'a str,
+}
+
+/// Caller-owned storage the ranges and ingredient ids are parsed into.
+#[derive(Default)]
+pub struct Scratch<'a> {
+  pub ranges: &'a mut [(u64, u64)],
+  pub ingredients: &'a mut [u64],
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  for_each_entry(input, |_, _| {}, |_| {})?;
+  Ok(Problem { input })
+}
+
+/// Walks the `start-end` ranges, then the ingredient ids after the blank line,
+/// stopping at the first malformed line.
+pub fn for_each_entry(
+  input: &str,
+  mut range: impl FnMut(u64, u64),
+  mut ingredient: impl FnMut(u64),
+) -> Result<(), ParseError> {
+  let mut lines = input.lines();
+
+  for line in &mut lines {
+    let line = line.trim();
+    if line.is_empty() {
+      break;
+    }
+    let (a, b) = split_once(input, line, '-')?;
+    let (start, end) = (number(input, a)?, number::<u64>(input, b)?);
+    if end < start {
+      let reason = Reason::Malformed("range ends before it starts");
+      return Err(ParseError::at(input, b, reason));
+    }
+    // merged ranges end one past their last id, which must still fit
+    if end == u64::MAX {
+      return Err(ParseError::at(input, b, Reason::OutOfRange));
+    }
+    range(start, end);
+  }
+
+  for line in lines {
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    ingredient(number(input, line)?);
+  }
+
+  Ok(())
+}
+
+/// The `no_std` twin of `parse_std`: fills caller buffers instead of `Vec`s
+/// and returns how many ranges and ingredients were written.
+#[cfg(all(feature = "part1", feature = "part2"))]
+fn parse_into(
+  input: &str,
+  ranges: &mut [(u64, u64)],
+  ingredients: &mut [u64],
+) -> Result<(usize, usize), ParseError> {
+  let (mut n_ranges, mut n_ingredients) = (0, 0);
+
+  for_each_entry(
+    input,
+    |a, b| {
+      ranges[n_ranges] = (a, b);
+      n_ranges += 1;
+    },
+    |id| {
+      ingredients[n_ingredients] = id;
+      n_ingredients += 1;
+    },
+  )?;
+
+  Ok((n_ranges, n_ingredients))
+}
+
+// --------------------------
+// Scratch
+// --------------------------
+
+/// Working memory for one input: a range or ingredient slot per line, since
+/// either may fill every line, and `bytes` of workspace in all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Requirements {
+  pub lines: usize,
+  pub bytes: usize,
+}
+
+pub fn requirements(p: &Problem) -> Requirements {
+  let lines = p.input.lines().count();
+  let bytes =
+    Workspace::size_of::<(u64, u64)>(lines) + Workspace::size_of::<u64>(lines);
+
+  Requirements { lines, bytes }
+}
+
+impl Requirements {
+  /// Cuts a `Scratch` out of `ws`, which needs `bytes` left.
+  pub fn carve<'a>(
+    &self,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    Ok(Scratch {
+      ranges: ws.take(self.lines, || (0, 0))?,
+      ingredients: ws.take(self.lines, || 0)?,
+    })
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day5;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day5 {
+  const DAY: u8 = 5;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = Scratch<'a>;
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve<'a>(
+    part: Part,
+    p: &Problem<'a>,
+    scratch: Scratch<'a>,
+  ) -> Result<Answer, ParseError> {
+    let (n_ranges, n_ingredients) =
+      parse_into(p.input, scratch.ranges, scratch.ingredients)?;
+
+    let ranges = &mut scratch.ranges[..n_ranges];
+    let ingredients = &scratch.ingredients[..n_ingredients];
+
+    let answer = match part {
+      Part::One => prelude::part1(ranges, ingredients),
+      Part::Two => prelude::part2(ranges, ingredients),
+    };
+
+    Ok(answer.into())
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Carve for Day5 {
+  fn bytes(p: &Problem) -> usize {
+    requirements(p).bytes
+  }
+
+  fn carve<'a>(
+    p: &Problem,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    requirements(p).carve(ws)
+  }
+}
@@ -1,3 +1,4 @@
+use aoc_core::ParseError;
 use day_5::prelude::*;
 
 mod std_parse;
@@ -5,25 +6,17 @@ mod std_parse;
 use std_parse::parse_std;
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
-
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
-
-fn main() {
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
   // std_parse does the std-only parsing for ranges + ingredients
-  let (mut ranges, ingredients) = parse_std(INPUT);
+  let (ranges, ingredients) = parse_std(&input)?;
+
+  // both parts merge the ranges in place, so each gets its own copy
+  let p1 = part1(&mut ranges.clone(), &ingredients);
+  println!("Part 1: {p1}");
 
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&mut ranges, &ingredients);
-    println!("Part 1: {p1}");
-  }
+  let p2 = part2(&mut ranges.clone(), &ingredients);
+  println!("Part 2: {p2}");
 
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&mut ranges, &ingredients);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
@@ -0,0 +1,45 @@
+// Part 1 builds a `RangeSearch`, whose two lookup tables are the crate's only
+// heap use; everything else runs in caller-owned memory.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::audit::{Allocations, CountingAlloc, measure};
+use aoc_core::{Part, Solver, Workspace};
+use day_5::prelude::*;
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+use std_parse::parse_std;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn solve(part: Part) -> Allocations {
+  let needs = requirements(&parse(INPUT).unwrap());
+
+  // caller-owned block, allocated before measuring
+  let mut block = vec![0u8; needs.bytes];
+
+  let (_, seen) = measure(|| {
+    let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+    let p = Day5::parse(INPUT).unwrap();
+    Day5::solve(part, &p, scratch).unwrap()
+  });
+  seen
+}
+
+#[test]
+fn test_allocations() {
+  let one = solve(Part::One);
+  let two = solve(Part::Two);
+  println!("One: {one}\nTwo: {two}");
+
+  assert_eq!(one.count, 2, "RangeSearch starts and ends only");
+  assert_eq!(two, Allocations::default());
+
+  // the std-side owner, for comparison
+  let (_, seen) = measure(|| parse_std(INPUT).unwrap());
+  println!("parse_std: {seen}");
+}
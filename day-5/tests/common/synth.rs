@@ -0,0 +1,21 @@
+use fastrand::Rng;
+
+/// `ranges` fresh ranges and `ids` ingredient ids, spread so that ranges
+/// overlap, nest and touch as the puzzle's do and about half of the ids
+/// fall inside one.
+pub fn database(seed: u64, ranges: usize, ids: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let base = rng.u64(..1 << 48);
+  let extent = 50 * ranges as u64;
+
+  let ranges: Vec<String> = (0..ranges)
+    .map(|_| {
+      let start = base + rng.u64(..extent);
+      format!("{start}-{}", start + rng.u64(..100))
+    })
+    .collect();
+  let ids: Vec<String> = (0..ids)
+    .map(|_| (base + rng.u64(..extent)).to_string())
+    .collect();
+  format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"))
+}
@@ -0,0 +1,36 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking, through both the `Solver` and the std parser
+// the binary uses. Crashing inputs are minimized and saved back into
+// `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver, Workspace};
+use day_5::prelude::*;
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+use std_parse::parse_std;
+
+fn target(input: &str) {
+  if let Ok(p) = Day5::parse(input) {
+    let needs = requirements(&p);
+    let mut block = vec![0u8; needs.bytes];
+
+    for part in [Part::One, Part::Two] {
+      let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+      let _ = Day5::solve(part, &p, scratch);
+    }
+  }
+
+  if let Ok((ranges, ingredients)) = parse_std(input) {
+    part1(&mut ranges.clone(), &ingredients);
+    part2(&mut ranges.clone(), &ingredients);
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
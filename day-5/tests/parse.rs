@@ -0,0 +1,27 @@
+use aoc_core::{ParseError, Reason};
+use day_5::prelude::*;
+
+#[test]
+fn test_parse_error() {
+  let err = parse("3-5\n10-14\n\n1\n5x\n").err().unwrap();
+  assert_eq!(
+    err,
+    ParseError { line: 5, column: 2, reason: Reason::ExpectedNumber }
+  );
+
+  let err = parse("3-5\n10\n\n1\n").err().unwrap();
+  assert_eq!(
+    err,
+    ParseError { line: 2, column: 3, reason: Reason::Expected('-') }
+  );
+
+  let err = parse("3-5\n50-0\n\n1\n").err().unwrap();
+  let reason = Reason::Malformed("range ends before it starts");
+  assert_eq!(err, ParseError { line: 2, column: 4, reason });
+
+  let err = parse("0-18446744073709551615\n\n1\n").err().unwrap();
+  assert_eq!(
+    err,
+    ParseError { line: 1, column: 3, reason: Reason::OutOfRange }
+  );
+}
@@ -13,7 +13,7 @@ use std_parse::parse_std;
 #[cfg(feature = "part2")]
 fn test_part2() {
   let input = include_str!("../sample.txt");
-  let (mut ranges, ingredients) = parse_std(input);
+  let (mut ranges, ingredients) = parse_std(input).unwrap();
 
   // ingredients are irrelevant in part2
   let result = part2(&mut ranges, &ingredients);
@@ -0,0 +1,91 @@
+// Differential tests: random ranges and ids go through the `Solver` and
+// through a naive std reference that checks every range and enumerates every
+// fresh id.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver, Workspace};
+use day_5::prelude::*;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  use std::collections::BTreeSet;
+
+  fn database(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
+    let (ranges, ids) = input.split_once("\n\n").unwrap();
+    let ranges = ranges
+      .lines()
+      .map(|l| {
+        let (a, b) = l.split_once('-').unwrap();
+        (a.parse().unwrap(), b.parse().unwrap())
+      })
+      .collect();
+    let ids = ids.lines().map(|l| l.parse().unwrap()).collect();
+    (ranges, ids)
+  }
+
+  pub fn part1(input: &str) -> usize {
+    let (ranges, ids) = database(input);
+    ids
+      .iter()
+      .filter(|&&id| ranges.iter().any(|&(a, b)| (a..=b).contains(&id)))
+      .count()
+  }
+
+  pub fn part2(input: &str) -> usize {
+    let (ranges, _) = database(input);
+    let fresh: BTreeSet<u64> =
+      ranges.iter().flat_map(|&(a, b)| a..=b).collect();
+    fresh.len()
+  }
+}
+
+/// Short ranges near one random base, so that they overlap, nest and touch
+/// as the puzzle's do, followed by ids around them.
+fn databases() -> impl Strategy<Value = String> {
+  (0..u64::MAX / 2).prop_flat_map(|base| {
+    let range = (0..300u64, 0..40u64)
+      .prop_map(move |(start, len)| (base + start, base + start + len));
+    let id = (0..360u64).prop_map(move |offset| base + offset);
+    (
+      prop::collection::vec(range, 1..30),
+      prop::collection::vec(id, 0..30),
+    )
+      .prop_map(|(ranges, ids)| {
+        let ranges: Vec<String> =
+          ranges.iter().map(|(a, b)| format!("{a}-{b}")).collect();
+        let ids: Vec<String> = ids.iter().map(u64::to_string).collect();
+        format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"))
+      })
+  })
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day5::parse(input).unwrap();
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+
+  Day5::solve(part, &p, scratch).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in databases()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in databases()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::database(seed, 30, 30);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
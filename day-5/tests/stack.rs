@@ -0,0 +1,35 @@
+// Fails if a part's stack high-water mark, parse included, grows past
+// BUDGET. Debug builds need several times the stack of release ones, so the
+// budget is sized for `cargo test`; `aoc stack` reports release numbers.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::stack::measure_stack;
+use aoc_core::{Part, Solver, Workspace};
+use day_5::prelude::*;
+
+const INPUT: &str = include_str!("../sample.txt");
+const BUDGET: usize = 16 << 10;
+
+fn solve(part: Part) -> usize {
+  let needs = requirements(&parse(INPUT).unwrap());
+
+  // the caller-owned block lives on the heap, outside the measurement
+  let mut block = vec![0u8; needs.bytes];
+
+  let (_, used) = measure_stack(|| {
+    let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+    let p = Day5::parse(INPUT).unwrap();
+    Day5::solve(part, &p, scratch).unwrap()
+  });
+  used
+}
+
+#[test]
+fn test_stack_budget() {
+  for part in [Part::One, Part::Two] {
+    let used = solve(part);
+    println!("{part:?}: {used} bytes");
+
+    assert!(used <= BUDGET, "{part:?}: {used} bytes, budget {BUDGET}");
+  }
+}
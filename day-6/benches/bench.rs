@@ -1,21 +1,22 @@
+use std::sync::LazyLock;
+
 use day_6::prelude::*;
 use divan::black_box;
 
 mod std_parse {
   include!("../src/std_parse.rs");
 }
-use std_parse::parse;
-
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+#[cfg(feature = "part1")]
+use std_parse::parse_part1;
+#[cfg(feature = "part2")]
+use std_parse::parse_part2;
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let problem = parse(black_box(INPUT));
+  let problem = parse_part1(black_box(INPUT.as_str())).unwrap();
 
   if let Some(first_row) = problem.rows.first() {
     let mut accumulator = first_row.clone();
@@ -31,16 +32,16 @@ fn bench_part1() {
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let problem = parse(black_box(INPUT));
+  let problem = parse_part2(black_box(INPUT.as_str())).unwrap();
 
   if !problem.rows.is_empty() {
     #[allow(nonstandard_style)]
-    let (W, G, R) = problem.get_parameters();
+    let (W, G, _) = problem.get_parameters();
 
     let mut groups_cols = vec![vec![0usize; W]; G];
     let mut groups_lens = vec![0usize; G];
     let mut current_cols = vec![0usize; W];
-    let mut problem_row_vals = vec![0u64; R];
+    let mut problem_row_vals = vec![0u64; W];
 
     let mut groups_cols_refs: Vec<&mut [usize]> =
       groups_cols.iter_mut().map(|v| v.as_mut_slice()).collect();
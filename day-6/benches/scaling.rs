@@ -0,0 +1,65 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_6::prelude::*;
+use divan::{Bencher, black_box};
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+#[cfg(feature = "part1")]
+use std_parse::parse_part1;
+#[cfg(feature = "part2")]
+use std_parse::parse_part2;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Problems of four numbers each.
+const SIZES: &[usize] = &[100, 300, 1_000, 3_000];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, problems: usize) {
+  let input = synth::worksheet(SEED, problems, 4);
+  bencher.bench(|| {
+    let problem = parse_part1(black_box(&input)).unwrap();
+    let mut accumulator = problem.rows[0].clone();
+    part1(&problem.rows[1..], &problem.operands, &mut accumulator);
+    black_box(accumulator.into_iter().sum::<u64>());
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, problems: usize) {
+  let input = synth::worksheet(SEED, problems, 4);
+  bencher.bench(|| {
+    let problem = parse_part2(black_box(&input)).unwrap();
+    #[allow(nonstandard_style)]
+    let (W, G, _) = problem.get_parameters();
+
+    let mut groups_cols = vec![vec![0usize; W]; G];
+    let mut groups_lens = vec![0usize; G];
+    let mut current_cols = vec![0usize; W];
+    let mut problem_row_vals = vec![0u64; W];
+    let mut groups_cols_refs: Vec<&mut [usize]> =
+      groups_cols.iter_mut().map(|v| v.as_mut_slice()).collect();
+
+    black_box(part2(
+      &problem.rows[..],
+      &problem.operands,
+      &mut groups_cols_refs,
+      &mut groups_lens,
+      &mut current_cols,
+      &mut problem_row_vals,
+    ));
+  });
+}
+
+fn main() {
+  divan::main();
+}
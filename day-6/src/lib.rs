@@ -3,14 +3,30 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use aoc_core::parse::number;
+#[cfg(feature = "part2")]
+use aoc_core::trace::{Event, NoTrace, TraceSink};
+use aoc_core::workspace::Plain;
+use aoc_core::{Error, ParseError, Reason, Workspace};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::workspace::Carve;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 pub mod prelude {
-  pub use crate::Operand;
+  pub use crate::{
+    Day6, Operand, Requirements, Scratch, Worksheet, parse, parse_operand,
+    requirements,
+  };
 
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub use crate::part1_impl::part1;
 
   #[cfg(feature = "part2")]
-  pub use crate::part2_impl::part2;
+  pub use crate::part2_impl::{part2, part2_traced};
 }
 
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -22,10 +38,218 @@ pub enum Operand {
   Subtraction,
 }
 
+// a fieldless enum: one byte, nothing to drop
+unsafe impl Plain for Operand {}
+
+impl Operand {
+  /// Wraps on overflow and treats division by zero as zero, so a hostile
+  /// worksheet gets a wrong answer rather than a panic.
+  fn apply(&self, a: u64, b: u64) -> u64 {
+    match self {
+      Operand::Multiplication => a.wrapping_mul(b),
+      Operand::Division => a.checked_div(b).unwrap_or(0),
+      Operand::Addition => a.wrapping_add(b),
+      Operand::Subtraction => a.wrapping_sub(b),
+    }
+  }
+
+  /// The worksheet's symbol for this operand.
+  pub fn symbol(&self) -> char {
+    match self {
+      Operand::Multiplication => '*',
+      Operand::Division => '/',
+      Operand::Addition => '+',
+      Operand::Subtraction => '-',
+    }
+  }
+}
+
+// --------------------------
+// Data Model
+// --------------------------
+
+/// The raw worksheet: number rows followed by one operand row.
+pub struct Worksheet<'a> {
+  pub input: &'a str,
+}
+
+/// Caller-owned working memory; `operands` holds one slot per problem column.
+/// Part 1 uses `accumulator` and `row`, part 2 the remaining buffers.
+#[derive(Default)]
+pub struct Scratch<'a> {
+  pub operands: &'a mut [Operand],
+  pub accumulator: &'a mut [u64],
+  pub row: &'a mut [u64],
+  pub rows: &'a mut [&'a str],
+  pub groups_cols: &'a mut [&'a mut [usize]],
+  pub groups_lens: &'a mut [usize],
+  pub current_cols: &'a mut [usize],
+  pub problem_row_vals: &'a mut [u64],
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+/// Parses one operand token; `token` is a sub-slice of `input`.
+pub fn parse_operand(input: &str, token: &str) -> Result<Operand, ParseError> {
+  match token {
+    "*" => Ok(Operand::Multiplication),
+    "/" => Ok(Operand::Division),
+    "+" => Ok(Operand::Addition),
+    "-" => Ok(Operand::Subtraction),
+    _ => {
+      let c = token.chars().next().unwrap_or(' ');
+      Err(ParseError::at(input, token, Reason::Unexpected(c)))
+    }
+  }
+}
+
+/// Checks the number rows hold only digits and spaces, that every token on
+/// the trailing row is an operand, and that both parts see one problem per
+/// operand: as many values on each row, and as many blocks of columns.
+pub fn parse(input: &str) -> Result<Worksheet<'_>, ParseError> {
+  let rows = input.lines().filter(|l| !l.trim().is_empty());
+  let Some(op_line) = rows.clone().next_back() else {
+    return Ok(Worksheet { input });
+  };
+  let num_rows = rows.clone().count() - 1;
+
+  let mut num_operands = 0;
+  for token in op_line.split_whitespace() {
+    parse_operand(input, token)?;
+    num_operands += 1;
+  }
+
+  for line in rows.clone().take(num_rows) {
+    let bad = line
+      .char_indices()
+      .find(|&(_, c)| c != ' ' && !c.is_ascii_digit());
+    if let Some((i, c)) = bad {
+      return Err(ParseError::at(input, &line[i..], Reason::Unexpected(c)));
+    }
+
+    let mut values = 0;
+    for field in line.split_whitespace() {
+      number::<u64>(input, field)?;
+      values += 1;
+    }
+    if values != num_operands {
+      let reason = Reason::Malformed("row and operands differ in count");
+      return Err(ParseError::at(input, line, reason));
+    }
+  }
+
+  // part 2 splits problems at columns that are blank on every number row
+  let width = rows.clone().take(num_rows).map(str::len).max().unwrap_or(0);
+  let blank = |c: usize| {
+    rows
+      .clone()
+      .take(num_rows)
+      .all(|l| l.as_bytes().get(c).is_none_or(|&b| b == b' '))
+  };
+  let blocks = (0..width)
+    .filter(|&c| !blank(c) && (c == 0 || blank(c - 1)))
+    .count();
+  if num_rows > 0 && blocks != num_operands {
+    let reason = Reason::Malformed("columns and operands differ in count");
+    return Err(ParseError::at(input, op_line, reason));
+  }
+
+  Ok(Worksheet { input })
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl<'a> Worksheet<'a> {
+  /// Splits off the trailing operand row, parsing it into `operands`.
+  /// Returns the number rows and how many operands were written.
+  fn split_operands(
+    &self,
+    operands: &mut [Operand],
+  ) -> Result<(impl Iterator<Item = &'a str> + use<'a>, usize), ParseError> {
+    let rows = self.input.lines().filter(|l| !l.trim().is_empty());
+    let num_rows = rows.clone().count().saturating_sub(1);
+
+    let mut num_operands = 0;
+    if let Some(op_line) = rows.clone().nth(num_rows) {
+      for token in op_line.split_whitespace() {
+        operands[num_operands] = parse_operand(self.input, token)?;
+        num_operands += 1;
+      }
+    }
+
+    Ok((rows.take(num_rows), num_operands))
+  }
+}
+
+// --------------------------
+// Scratch
+// --------------------------
+
+/// Working memory for one worksheet, from its number `rows`, widest line
+/// (`width`) and problem count (`groups`), with `bytes` of workspace in all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Requirements {
+  pub rows: usize,
+  pub width: usize,
+  pub groups: usize,
+  pub bytes: usize,
+}
+
+pub fn requirements(w: &Worksheet) -> Requirements {
+  let lines = || w.input.lines().filter(|l| !l.trim().is_empty());
+  let rows = lines().count().saturating_sub(1);
+  let width = lines().map(str::len).max().unwrap_or(0);
+  let groups = lines()
+    .next_back()
+    .map_or(0, |l| l.split_whitespace().count());
+
+  let bytes = Workspace::size_of::<Operand>(groups)
+    + 2 * Workspace::size_of::<u64>(groups)
+    + Workspace::size_of::<&str>(rows)
+    + Workspace::size_of::<usize>(groups * width)
+    + Workspace::size_of::<&mut [usize]>(groups)
+    + Workspace::size_of::<usize>(groups)
+    + Workspace::size_of::<usize>(width)
+    + Workspace::size_of::<u64>(width);
+
+  Requirements { rows, width, groups, bytes }
+}
+
+impl Requirements {
+  /// Cuts a `Scratch` out of `ws`, which needs `bytes` left. Part 2's
+  /// column groups share one `groups * width` block.
+  pub fn carve<'a>(
+    &self,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    let Requirements { rows, width, groups, .. } = *self;
+
+    let operands = ws.take(groups, || Operand::Addition)?;
+    let accumulator = ws.take(groups, || 0)?;
+    let row = ws.take(groups, || 0)?;
+    let rows = ws.take(rows, || "")?;
+    let block = ws.take(groups * width, || 0)?;
+    let mut columns = block.chunks_mut(width.max(1));
+    let groups_cols = ws.take(groups, || columns.next().unwrap_or_default())?;
+
+    Ok(Scratch {
+      operands,
+      accumulator,
+      row,
+      rows,
+      groups_cols,
+      groups_lens: ws.take(groups, || 0)?,
+      current_cols: ws.take(width, || 0)?,
+      problem_row_vals: ws.take(width, || 0)?,
+    })
+  }
+}
+
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
   use super::Operand;
 
@@ -40,12 +264,7 @@ mod part1_impl {
         .zip(operands.iter());
 
       for ((acc, &val), op) in iter {
-        match op {
-          Operand::Multiplication => *acc *= val,
-          Operand::Division => *acc /= val,
-          Operand::Addition => *acc += val,
-          Operand::Subtraction => *acc -= val,
-        }
+        *acc = op.apply(*acc, val);
       }
     }
   }
@@ -65,7 +284,7 @@ mod part1_impl {
 
 #[cfg(feature = "part2")]
 mod part2_impl {
-  use super::Operand;
+  use super::{Event, NoTrace, Operand, TraceSink};
 
 
   pub fn part2<S: AsRef<str>>(
@@ -75,18 +294,43 @@ mod part2_impl {
     groups_cols: &mut [&mut [usize]], // caller-provided 2D buffer
     groups_lens: &mut [usize],        // lengths for each group
     current_cols: &mut [usize],       // scratch buffer for 1 group
-    problem_row_vals: &mut [u64],     // scratch row buffer
+    problem_row_vals: &mut [u64],     // one value per column of a problem
+  ) -> u64 {
+    part2_traced(
+      rows,
+      operands,
+      groups_cols,
+      groups_lens,
+      current_cols,
+      problem_row_vals,
+      &mut NoTrace,
+    )
+  }
+
+  /// `part2`, reporting each problem's column values to `sink`.
+  pub fn part2_traced<S: AsRef<str>>(
+    rows: &[S],
+    operands: &[Operand],
+    groups_cols: &mut [&mut [usize]],
+    groups_lens: &mut [usize],
+    current_cols: &mut [usize],
+    problem_row_vals: &mut [u64],
+    sink: &mut impl TraceSink,
   ) -> u64 {
     #[allow(nonstandard_style)]
-    let W = rows[0].as_ref().len();
+    let W = rows.iter().map(|r| r.as_ref().len()).max().unwrap_or(0);
     #[allow(nonstandard_style)]
     let R = rows.len();
 
+    // short rows read as blank past their end
+    let at =
+      |r: usize, c: usize| *rows[r].as_ref().as_bytes().get(c).unwrap_or(&b' ');
+
     let mut groups_count = 0usize;
     let mut current_len = 0usize;
 
     for c in 0..W {
-      let column_empty = (0..R).all(|r| rows[r].as_ref().as_bytes()[c] == b' ');
+      let column_empty = (0..R).all(|r| at(r, c) == b' ');
 
       if column_empty {
         // finished building a group, commit it
@@ -122,39 +366,136 @@ mod part2_impl {
         let c = groups_cols[group_index][k];
         let mut value = 0u64;
 
-        for row in rows.iter() {
-          let ch = row.as_ref().as_bytes()[c];
+        for r in 0..R {
+          let ch = at(r, c);
           if ch.is_ascii_digit() {
-            value = value * 10 + (ch - b'0') as u64;
+            value = value.wrapping_mul(10).wrapping_add((ch - b'0') as u64);
           }
         }
 
         problem_row_vals[k] = value;
       }
 
-      #[cfg(feature = "std")]
-      {
-        eprintln!(
-          "GROUP {} (op {:?}): {:?}",
-          group_index,
-          op,
-          &problem_row_vals[..cols_len]
-        );
-      }
+      sink.event(Event::Group {
+        index: group_index,
+        op: op.symbol(),
+        values: &problem_row_vals[..cols_len],
+      });
 
       let mut group_value = problem_row_vals[0];
       for &v in &problem_row_vals[1..cols_len] {
-        group_value = match op {
-          Operand::Addition => group_value + v,
-          Operand::Subtraction => group_value - v,
-          Operand::Multiplication => group_value * v,
-          Operand::Division => group_value / v,
-        };
+        group_value = op.apply(group_value, v);
       }
 
-      total += group_value;
+      total = total.wrapping_add(group_value);
     }
 
     total
   }
 }
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day6;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day6 {
+  const DAY: u8 = 6;
+
+  type Input<'a> = Worksheet<'a>;
+  type Scratch<'a> = Scratch<'a>;
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Worksheet<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve<'a>(
+    part: Part,
+    w: &Worksheet<'a>,
+    scratch: Scratch<'a>,
+  ) -> Result<Answer, ParseError> {
+    let answer = match part {
+      Part::One => solve_part1(w, scratch)?,
+      Part::Two => solve_part2(w, scratch)?,
+    };
+
+    Ok(answer.into())
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Carve for Day6 {
+  fn bytes(w: &Worksheet) -> usize {
+    requirements(w).bytes
+  }
+
+  fn carve<'a>(
+    w: &Worksheet,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    requirements(w).carve(ws)
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+fn solve_part1<'a>(
+  w: &Worksheet<'a>,
+  scratch: Scratch<'a>,
+) -> Result<u64, ParseError> {
+  let (mut rows, g) = w.split_operands(scratch.operands)?;
+  let operands = &scratch.operands[..g];
+  let accumulator = &mut scratch.accumulator[..g];
+
+  let Some(first) = rows.next() else {
+    return Ok(0);
+  };
+  fill_row(first, accumulator);
+
+  for line in rows {
+    let row = &mut scratch.row[..g];
+    fill_row(line, row);
+    part1_impl::part1(&[&*row], operands, accumulator);
+  }
+
+  Ok(accumulator.iter().fold(0, |sum, &v| sum.wrapping_add(v)))
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+fn solve_part2<'a>(
+  w: &Worksheet<'a>,
+  scratch: Scratch<'a>,
+) -> Result<u64, ParseError> {
+  let (rows, g) = w.split_operands(scratch.operands)?;
+
+  let mut r = 0;
+  for line in rows {
+    scratch.rows[r] = line;
+    r += 1;
+  }
+
+  if r == 0 {
+    return Ok(0);
+  }
+
+  Ok(part2_impl::part2(
+    &scratch.rows[..r],
+    &scratch.operands[..g],
+    scratch.groups_cols,
+    scratch.groups_lens,
+    scratch.current_cols,
+    scratch.problem_row_vals,
+  ))
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+fn fill_row(line: &str, row: &mut [u64]) {
+  let values = line
+    .split_whitespace()
+    .filter_map(|s| s.parse::<u64>().ok());
+  for (slot, value) in row.iter_mut().zip(values) {
+    *slot = value;
+  }
+}
@@ -1,21 +1,20 @@
+use aoc_core::ParseError;
+#[cfg(not(feature = "trace"))]
+use aoc_core::trace::NoTrace as Sink;
+#[cfg(feature = "trace")]
+use aoc_core::trace::Stderr as Sink;
 use day_6::prelude::*;
 
 mod std_parse;
-use std_parse::parse;
+use std_parse::{parse_part1, parse_part2};
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
-
-
-fn main() {
-  let problem = parse(INPUT);
-
-  #[cfg(not(feature = "part2"))]
   {
+    let problem = parse_part1(&input)?;
+
     if let Some(first_row) = problem.rows.first() {
       let mut results = first_row.clone();
 
@@ -29,27 +28,29 @@ fn main() {
     }
   }
 
-  #[cfg(feature = "part2")]
   {
+    let problem = parse_part2(&input)?;
+
     if !problem.rows.is_empty() {
       #[allow(nonstandard_style)]
-      let (W, G, R) = problem.get_parameters();
+      let (W, G, _) = problem.get_parameters();
 
       let mut groups_cols = vec![vec![0usize; W]; G];
       let mut groups_lens = vec![0usize; G];
       let mut current_cols = vec![0usize; W];
-      let mut problem_row_vals = vec![0u64; R];
+      let mut problem_row_vals = vec![0u64; W];
 
       let mut groups_cols_refs: Vec<&mut [usize]> =
         groups_cols.iter_mut().map(|v| v.as_mut_slice()).collect();
 
-      let p2 = part2(
+      let p2 = part2_traced(
         &problem.rows[..],
         &problem.operands,
         &mut groups_cols_refs,
         &mut groups_lens,
         &mut current_cols,
         &mut problem_row_vals,
+        &mut Sink,
       );
 
       println!("Part 2: {p2}");
@@ -57,4 +58,6 @@ fn main() {
       panic!("No columns found in the problem");
     }
   }
+
+  Ok(())
 }
@@ -1,22 +1,29 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
-use day_6::prelude::Operand;
+use aoc_core::ParseError;
+#[cfg(feature = "part1")]
+use aoc_core::parse::number;
+use day_6::prelude::{Operand, parse_operand};
 
 
-pub struct Problem {
-  #[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
+pub struct Part1Problem {
   pub rows: Vec<Vec<u64>>,
-  #[cfg(feature = "part2")]
+  pub operands: Vec<Operand>,
+}
+
+#[cfg(feature = "part2")]
+pub struct Part2Problem {
   pub rows: Vec<String>,
   pub operands: Vec<Operand>,
 }
 
 #[cfg(feature = "part2")]
-impl Problem {
+impl Part2Problem {
   #[allow(nonstandard_style)]
   pub fn get_parameters(&self) -> (usize, usize, usize) {
-    let W = self.rows[0].len();
+    let W = self.rows.iter().map(String::len).max().unwrap_or(0);
     let G = self.operands.len();
     let R = self.rows.len();
 
@@ -24,63 +31,51 @@ impl Problem {
   }
 }
 
-#[cfg(not(feature = "part2"))]
-pub fn parse(input: &str) -> Problem {
+#[cfg(feature = "part1")]
+pub fn parse_part1(input: &str) -> Result<Part1Problem, ParseError> {
   let mut lines: Vec<&str> =
     input.lines().filter(|l| !l.trim().is_empty()).collect();
 
-  if lines.is_empty() {
-    return Problem { rows: Vec::new(), operands: Vec::new() };
-  }
-
-  let op_line = lines.pop().expect("Input must have operands line");
+  let Some(op_line) = lines.pop() else {
+    return Ok(Part1Problem { rows: Vec::new(), operands: Vec::new() });
+  };
 
-  let operands: Vec<Operand> = op_line
+  let operands = op_line
     .split_whitespace()
-    .map(|op_str| match op_str {
-      "*" => Operand::Multiplication,
-      "/" => Operand::Division,
-      "+" => Operand::Addition,
-      "-" => Operand::Subtraction,
-      _ => panic!("unknown operand"),
-    })
-    .collect();
+    .map(|token| parse_operand(input, token))
+    .collect::<Result<Vec<Operand>, _>>()?;
 
-  let rows: Vec<Vec<u64>> = lines
+  let rows = lines
     .iter()
     .map(|line| {
       line
         .split_whitespace()
-        .filter_map(|s| s.parse::<u64>().ok())
-        .collect()
+        .map(|field| number::<u64>(input, field))
+        .collect::<Result<Vec<u64>, _>>()
     })
-    .collect();
+    .collect::<Result<Vec<_>, _>>()?;
 
-  Problem { rows, operands }
+  Ok(Part1Problem { rows, operands })
 }
 
 #[cfg(feature = "part2")]
-pub fn parse(input: &str) -> Problem {
-  let mut lines: Vec<&str> = input.lines().filter(|l| !l.is_empty()).collect();
+pub fn parse_part2(input: &str) -> Result<Part2Problem, ParseError> {
+  // rows stay raw text here, so let the no_std parser vet them
+  day_6::prelude::parse(input)?;
 
-  if lines.is_empty() {
-    return Problem { rows: Vec::new(), operands: Vec::new() };
-  }
+  let mut lines: Vec<&str> =
+    input.lines().filter(|l| !l.trim().is_empty()).collect();
 
-  let op_line = lines.pop().expect("No operands line");
+  let Some(op_line) = lines.pop() else {
+    return Ok(Part2Problem { rows: Vec::new(), operands: Vec::new() });
+  };
 
-  let operands: Vec<Operand> = op_line
+  let operands = op_line
     .split_whitespace()
-    .map(|op_str| match op_str {
-      "*" => Operand::Multiplication,
-      "/" => Operand::Division,
-      "+" => Operand::Addition,
-      "-" => Operand::Subtraction,
-      _ => panic!("unknown operand"),
-    })
-    .collect();
+    .map(|token| parse_operand(input, token))
+    .collect::<Result<Vec<Operand>, _>>()?;
 
   let rows: Vec<String> = lines.into_iter().map(String::from).collect();
 
-  Problem { rows, operands }
+  Ok(Part2Problem { rows, operands })
 }
@@ -0,0 +1,47 @@
+// The worksheet is solved in caller-owned memory: parsing and solving must
+// never reach the heap. The std-side owners are reported for comparison.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::audit::{Allocations, CountingAlloc, measure};
+use aoc_core::{Part, Solver, Workspace};
+use day_6::prelude::*;
+
+#[allow(dead_code)]
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+use std_parse::{parse_part1, parse_part2};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn solve(part: Part) -> Allocations {
+  let needs = requirements(&parse(INPUT).unwrap());
+
+  // caller-owned block, allocated before measuring
+  let mut block = vec![0u8; needs.bytes];
+
+  let (_, seen) = measure(|| {
+    let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+    let p = Day6::parse(INPUT).unwrap();
+    Day6::solve(part, &p, scratch).unwrap()
+  });
+  seen
+}
+
+#[test]
+fn test_allocations() {
+  for part in [Part::One, Part::Two] {
+    let seen = solve(part);
+    println!("{part:?}: {seen}");
+
+    assert_eq!(seen, Allocations::default(), "{part:?}");
+  }
+
+  let (_, seen) = measure(|| parse_part1(INPUT).unwrap());
+  println!("parse_part1: {seen}");
+  let (_, seen) = measure(|| parse_part2(INPUT).unwrap());
+  println!("parse_part2: {seen}");
+}
@@ -0,0 +1,28 @@
+use fastrand::Rng;
+
+/// `problems` column-aligned problems of `rows` numbers each, laid out as
+/// in the puzzle: up to four digits, padded on either side, one number
+/// filling the column, and `+` or `*` under its left edge.
+pub fn worksheet(seed: u64, problems: usize, rows: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let mut lines = vec![Vec::new(); rows + 1];
+  for _ in 0..problems {
+    let width = rng.usize(1..=4);
+    let full = rng.usize(..rows);
+    for (row, line) in lines[..rows].iter_mut().enumerate() {
+      let len = if row == full {
+        width
+      } else {
+        rng.usize(1..=width)
+      };
+      let value = rng.u64(10u64.pow(len as u32 - 1)..10u64.pow(len as u32));
+      let pad = rng.usize(..=width - len);
+      let cell = format!("{}{value}", " ".repeat(pad));
+      line.push(format!("{cell:width$}"));
+    }
+    let op = if rng.bool() { '+' } else { '*' };
+    lines[rows].push(format!("{op:width$}"));
+  }
+  let lines: Vec<String> = lines.iter().map(|l| l.join(" ")).collect();
+  lines.join("\n")
+}
@@ -0,0 +1,67 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking, through both the `Solver` and the std parsers
+// the binary uses. Crashing inputs are minimized and saved back into
+// `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver, Workspace};
+use day_6::prelude::*;
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+use std_parse::{parse_part1, parse_part2};
+
+fn solve(part: Part, input: &str) {
+  let Ok(p) = Day6::parse(input) else {
+    return;
+  };
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+  let _ = Day6::solve(part, &p, scratch);
+}
+
+/// The binary's path: owned rows, then the part functions directly.
+#[allow(nonstandard_style)]
+fn solve_std(input: &str) {
+  if let Ok(problem) = parse_part1(input)
+    && let Some(first_row) = problem.rows.first()
+  {
+    let mut results = first_row.clone();
+    part1(&problem.rows[1..], &problem.operands, &mut results);
+  }
+
+  if let Ok(problem) = parse_part2(input)
+    && !problem.rows.is_empty()
+  {
+    let (W, G, _) = problem.get_parameters();
+    let mut groups_cols = vec![vec![0usize; W]; G];
+    let mut groups_lens = vec![0usize; G];
+    let mut current_cols = vec![0usize; W];
+    let mut problem_row_vals = vec![0u64; W];
+    let mut groups_cols_refs: Vec<&mut [usize]> =
+      groups_cols.iter_mut().map(|v| v.as_mut_slice()).collect();
+
+    part2(
+      &problem.rows[..],
+      &problem.operands,
+      &mut groups_cols_refs,
+      &mut groups_lens,
+      &mut current_cols,
+      &mut problem_row_vals,
+    );
+  }
+}
+
+fn target(input: &str) {
+  solve(Part::One, input);
+  solve(Part::Two, input);
+  solve_std(input);
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
@@ -0,0 +1,39 @@
+use aoc_core::{ParseError, Reason, Workspace};
+use day_6::prelude::*;
+
+#[test]
+fn test_parse_error() {
+  let err = parse("123 328\n 45 6a4\n*   +\n").err().unwrap();
+  assert_eq!(
+    err,
+    ParseError { line: 2, column: 6, reason: Reason::Unexpected('a') }
+  );
+
+  let err = parse("123 328\n 45 64\n*   %\n").err().unwrap();
+  assert_eq!(
+    err,
+    ParseError { line: 3, column: 5, reason: Reason::Unexpected('%') }
+  );
+
+  let err = parse("123 328\n 45\n*   +\n").err().unwrap();
+  let reason = Reason::Malformed("row and operands differ in count");
+  assert_eq!(err, ParseError { line: 2, column: 1, reason });
+
+  // two values per row, but every column holds a digit somewhere
+  let err = parse("1 2\n 3 4\n* +\n").err().unwrap();
+  let reason = Reason::Malformed("columns and operands differ in count");
+  assert_eq!(err, ParseError { line: 3, column: 1, reason });
+}
+
+#[test]
+fn test_requirements() {
+  let needs = requirements(&parse(include_str!("../sample.txt")).unwrap());
+  assert_eq!((needs.rows, needs.width, needs.groups), (3, 15, 4));
+
+  // exactly `bytes` is enough wherever the block starts
+  let mut block = vec![0u8; needs.bytes + 8];
+  for start in 0..8 {
+    let mut ws = Workspace::new(&mut block[start..start + needs.bytes]);
+    assert!(needs.carve(&mut ws).is_ok(), "block at +{start}");
+  }
+}
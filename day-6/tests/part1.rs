@@ -1,19 +1,20 @@
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
+#[allow(dead_code)]
 mod std_parse {
   include!("../src/std_parse.rs");
 }
-#[cfg(not(feature = "part2"))]
-use std_parse::parse;
+#[cfg(feature = "part1")]
+use std_parse::parse_part1;
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 use day_6::prelude::*;
 
 
 #[test]
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 fn test_part1() {
   let input = include_str!("../sample.txt");
-  let problem = parse(input);
+  let problem = parse_part1(input).unwrap();
 
   if let Some(first_row) = problem.rows.first() {
     let mut results = first_row.clone();
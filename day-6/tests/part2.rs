@@ -1,9 +1,10 @@
 #[cfg(feature = "part2")]
+#[allow(dead_code)]
 mod std_parse {
   include!("../src/std_parse.rs");
 }
 #[cfg(feature = "part2")]
-use std_parse::parse;
+use std_parse::parse_part2;
 
 #[cfg(feature = "part2")]
 use day_6::prelude::*;
@@ -13,16 +14,16 @@ use day_6::prelude::*;
 #[cfg(feature = "part2")]
 fn test_part2() {
   let input = include_str!("../sample.txt");
-  let problem = parse(input);
+  let problem = parse_part2(input).unwrap();
 
   if !problem.rows.is_empty() {
     #[allow(nonstandard_style)]
-    let (W, G, R) = problem.get_parameters();
+    let (W, G, _) = problem.get_parameters();
 
     let mut groups_cols = vec![vec![0usize; W]; G];
     let mut groups_lens = vec![0usize; G];
     let mut current_cols = vec![0usize; W];
-    let mut problem_row_vals = vec![0u64; R];
+    let mut problem_row_vals = vec![0u64; W];
 
     let mut groups_cols_refs: Vec<&mut [usize]> =
       groups_cols.iter_mut().map(|v| v.as_mut_slice()).collect();
@@ -0,0 +1,137 @@
+// Differential tests: random worksheets go through the `Solver` and through a
+// naive std reference that reads the sheet as a grid of characters.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver, Workspace};
+use day_6::prelude::*;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  fn apply(op: char, values: impl Iterator<Item = u64>) -> u64 {
+    match op {
+      '+' => values.sum(),
+      '*' => values.product(),
+      _ => unreachable!("the puzzle only adds and multiplies"),
+    }
+  }
+
+  pub fn part1(input: &str) -> u64 {
+    let lines: Vec<&str> = input.lines().collect();
+    let (ops, rows) = lines.split_last().unwrap();
+    let rows: Vec<Vec<u64>> = rows
+      .iter()
+      .map(|r| r.split_whitespace().map(|v| v.parse().unwrap()).collect())
+      .collect();
+
+    ops
+      .split_whitespace()
+      .enumerate()
+      .map(|(i, op)| {
+        apply(op.chars().next().unwrap(), rows.iter().map(|r| r[i]))
+      })
+      .sum()
+  }
+
+  /// Problems are blocks of columns between blank columns; each column is
+  /// one number, read top to bottom, and the block's operand sits under it.
+  pub fn part2(input: &str) -> u64 {
+    let grid: Vec<Vec<char>> =
+      input.lines().map(|l| l.chars().collect()).collect();
+    let width = grid.iter().map(Vec::len).max().unwrap();
+    let at = |x: usize, y: usize| *grid[y].get(x).unwrap_or(&' ');
+    let (ops, rows) = (grid.len() - 1, 0..grid.len() - 1);
+
+    let mut total = 0;
+    let mut block: Vec<usize> = Vec::new();
+    for x in 0..=width {
+      if x < width && (0..grid.len()).any(|y| at(x, y) != ' ') {
+        block.push(x);
+        continue;
+      }
+      if block.is_empty() {
+        continue;
+      }
+      let op = block
+        .iter()
+        .map(|&x| at(x, ops))
+        .find(|&c| c != ' ')
+        .unwrap();
+      let values = block.iter().rev().map(|&x| {
+        let digits: String = rows
+          .clone()
+          .map(|y| at(x, y))
+          .filter(char::is_ascii_digit)
+          .collect();
+        digits.parse::<u64>().unwrap()
+      });
+      total += apply(op, values);
+      block.clear();
+    }
+    total
+  }
+}
+
+/// Column-aligned problems as in the puzzle: numbers of up to four digits,
+/// padded on either side, one of them filling the column, and `+` or `*`
+/// under the left edge.
+fn worksheets() -> impl Strategy<Value = String> {
+  (2..=4usize).prop_flat_map(|rows| {
+    let problem = (1..=4usize).prop_flat_map(move |width| {
+      let cell = (any::<usize>(), any::<usize>(), any::<u64>());
+      (
+        Just(width),
+        prop::bool::ANY,
+        0..rows,
+        prop::collection::vec(cell, rows),
+      )
+    });
+    prop::collection::vec(problem, 1..=6).prop_map(move |problems| {
+      let mut lines = vec![Vec::new(); rows + 1];
+      for (width, add, full, cells) in problems {
+        for (row, (len, pad, value)) in cells.into_iter().enumerate() {
+          let len = if row == full { width } else { 1 + len % width };
+          let low = 10u64.pow(len as u32 - 1);
+          let value = low + value % (9 * low);
+          let pad = pad % (width - len + 1);
+          let cell = format!("{}{value}", " ".repeat(pad));
+          lines[row].push(format!("{cell:width$}"));
+        }
+        let op = if add { '+' } else { '*' };
+        lines[rows].push(format!("{op:width$}"));
+      }
+      let lines: Vec<String> = lines.iter().map(|l| l.join(" ")).collect();
+      lines.join("\n")
+    })
+  })
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day6::parse(input).unwrap();
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+
+  Day6::solve(part, &p, scratch).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in worksheets()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in worksheets()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::worksheet(seed, 10, 4);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
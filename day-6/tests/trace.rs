@@ -0,0 +1,45 @@
+// Trace events tell the same story as the answers they lead to.
+#![cfg(feature = "part2")]
+
+#[allow(dead_code)]
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+use std_parse::parse_part2;
+
+use aoc_core::trace::Collect;
+use day_6::prelude::*;
+
+#[test]
+fn test_part2_trace() {
+  let input = include_str!("../sample.txt");
+  let problem = parse_part2(input).unwrap();
+  #[allow(nonstandard_style)]
+  let (W, G, _) = problem.get_parameters();
+
+  let mut groups_cols = vec![vec![0usize; W]; G];
+  let mut groups_cols_refs: Vec<&mut [usize]> =
+    groups_cols.iter_mut().map(|v| v.as_mut_slice()).collect();
+  let mut sink = Collect::default();
+
+  let answer = part2_traced(
+    &problem.rows[..],
+    &problem.operands,
+    &mut groups_cols_refs,
+    &mut vec![0usize; G],
+    &mut vec![0usize; W],
+    &mut vec![0u64; W],
+    &mut sink,
+  );
+
+  assert_eq!(answer, 3263827);
+  assert_eq!(
+    sink.events,
+    [
+      "group 0 (*): [1, 24, 356]",
+      "group 1 (+): [369, 248, 8]",
+      "group 2 (*): [32, 581, 175]",
+      "group 3 (+): [623, 431, 4]",
+    ]
+  );
+}
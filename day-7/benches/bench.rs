@@ -1,3 +1,5 @@
+use std::sync::LazyLock;
+
 use day_7::prelude::*;
 use divan::black_box;
 
@@ -5,26 +7,22 @@ mod std_parse {
   include!("../src/std_parse.rs");
 }
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
-
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let mut problem_data = std_parse::parse(black_box(INPUT));
-  let mut p = problem_data.as_problem();
-  black_box(part1(&mut p));
+  let mut problem_data = std_parse::parse(black_box(INPUT.as_str())).unwrap();
+  let mut p = problem_data.as_part1();
+  black_box(part1(&mut p).unwrap());
 }
 
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let mut problem_data = std_parse::parse(black_box(INPUT));
-  let mut p = problem_data.as_problem();
-  black_box(part2(&mut p));
+  let mut problem_data = std_parse::parse(black_box(INPUT.as_str())).unwrap();
+  let mut p = problem_data.as_part2();
+  black_box(part2(&mut p).unwrap());
 }
 
 fn main() {
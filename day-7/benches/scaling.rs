@@ -0,0 +1,44 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_7::prelude::*;
+use divan::{Bencher, black_box};
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Side of the square manifold.
+const SIZES: &[usize] = &[32, 64, 128, 256, 512];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, side: usize) {
+  let input = synth::manifold(SEED, side, side);
+  bencher.bench(|| {
+    let mut problem_data = std_parse::parse(black_box(&input)).unwrap();
+    let mut p = problem_data.as_part1();
+    black_box(part1(&mut p).unwrap());
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, side: usize) {
+  let input = synth::manifold(SEED, side, side);
+  bencher.bench(|| {
+    let mut problem_data = std_parse::parse(black_box(&input)).unwrap();
+    let mut p = problem_data.as_part2();
+    black_box(part2(&mut p).unwrap());
+  });
+}
+
+fn main() {
+  divan::main();
+}
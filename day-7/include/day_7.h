@@ -0,0 +1,51 @@
+/* Generated by `cargo xtask header` from day-7/src/ffi.rs; do not edit. */
+#ifndef AOC_DAY_7_H
+#define AOC_DAY_7_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#include "aoc.h"
+
+/*
+ * Bytes of scratch either part needs for the `len` bytes at `input`.
+ *
+ * Safety:
+ * `input` must point to `len` readable bytes and `bytes` to a writable
+ * `size_t`.
+ */
+aoc_status aoc_day7_scratch_size(
+    const uint8_t *input,
+    size_t len,
+    size_t *bytes);
+
+/*
+ * Part 1's answer, working in `scratch_len` caller bytes at `scratch`.
+ *
+ * Safety:
+ * `input` must point to `len` readable bytes, `scratch` to `scratch_len`
+ * writable bytes and `answer` to a writable `uint64_t`.
+ */
+aoc_status aoc_day7_part1(
+    const uint8_t *input,
+    size_t len,
+    uint8_t *scratch,
+    size_t scratch_len,
+    uint64_t *answer);
+
+/*
+ * Part 2's answer, working in `scratch_len` caller bytes at `scratch`.
+ *
+ * Safety:
+ * `input` must point to `len` readable bytes, `scratch` to `scratch_len`
+ * writable bytes and `answer` to a writable `uint64_t`.
+ */
+aoc_status aoc_day7_part2(
+    const uint8_t *input,
+    size_t len,
+    uint8_t *scratch,
+    size_t scratch_len,
+    uint64_t *answer);
+
+#endif
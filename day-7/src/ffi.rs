@@ -0,0 +1,84 @@
+//! Day 7's C ABI, declared in `include/day_7.h`; see `aoc_core::ffi`.
+//!
+//! Both parts take one caller byte buffer of at least `aoc_day7_scratch_size`
+//! bytes, which `Requirements::carve` cuts into the splitter map, part 1's
+//! queue and part 2's beam counts.
+
+use aoc_core::ffi::{self, Status};
+use aoc_core::{Part, Solver, Workspace};
+
+use crate::{Day7, requirements};
+
+/// Bytes of scratch either part needs for the `len` bytes at `input`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `bytes` to a writable
+/// `size_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day7_scratch_size(
+  input: *const u8,
+  len: usize,
+  bytes: *mut usize,
+) -> Status {
+  let size = (|| -> Result<usize, Status> {
+    let text = unsafe { ffi::input(input, len)? };
+    Ok(requirements(&Day7::parse(text)?).bytes)
+  })();
+
+  unsafe { ffi::write(bytes, size) }
+}
+
+unsafe fn solve(
+  part: Part,
+  input: *const u8,
+  len: usize,
+  scratch: *mut u8,
+  scratch_len: usize,
+  answer: *mut u64,
+) -> Status {
+  let solved = (|| -> Result<u64, Status> {
+    let text = unsafe { ffi::input(input, len)? };
+    let problem = Day7::parse(text)?;
+    let needs = requirements(&problem);
+    ffi::check_scratch(scratch_len, needs.bytes)?;
+
+    let block = unsafe { ffi::buffer(scratch, scratch_len, 0)? };
+    let scratch = needs.carve(&mut Workspace::new(block))?;
+    let answer = Day7::solve(part, &problem, scratch)?;
+    u64::try_from(answer).map_err(|_| Status::CapacityExceeded)
+  })();
+
+  unsafe { ffi::write(answer, solved) }
+}
+
+/// Part 1's answer, working in `scratch_len` caller bytes at `scratch`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes, `scratch` to `scratch_len`
+/// writable bytes and `answer` to a writable `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day7_part1(
+  input: *const u8,
+  len: usize,
+  scratch: *mut u8,
+  scratch_len: usize,
+  answer: *mut u64,
+) -> Status {
+  unsafe { solve(Part::One, input, len, scratch, scratch_len, answer) }
+}
+
+/// Part 2's answer, working in `scratch_len` caller bytes at `scratch`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes, `scratch` to `scratch_len`
+/// writable bytes and `answer` to a writable `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day7_part2(
+  input: *const u8,
+  len: usize,
+  scratch: *mut u8,
+  scratch_len: usize,
+  answer: *mut u64,
+) -> Status {
+  unsafe { solve(Part::Two, input, len, scratch, scratch_len, answer) }
+}
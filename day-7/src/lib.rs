@@ -3,12 +3,29 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use core::fmt;
+
+use aoc_core::work::Counters;
+use aoc_core::{Error, ParseError, Reason, Workspace};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::workspace::Carve;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 
 pub mod prelude {
-  pub use crate::Problem;
+  pub use crate::{
+    Day7, Grid, Part1Problem, Part1Scratch, Part2Problem, Part2Scratch,
+    Requirements, Scratch, Stats, parse, requirements,
+  };
 
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub use crate::part1_impl::part1;
+  #[cfg(all(feature = "part1", feature = "instrument"))]
+  pub use crate::part1_impl::part1_instrumented;
 
   #[cfg(feature = "part2")]
   pub use crate::part2_impl::part2;
@@ -18,37 +35,280 @@ pub mod prelude {
 // Data Model
 // --------------------------
 
-pub struct Problem<'a> {
+/// Part 1's view: the splitter map, and the queue that follows the beams.
+pub struct Part1Problem<'a> {
   pub splitters: &'a [bool],
   pub start: (usize, usize),
   pub width: usize,
   pub height: usize,
 
-  #[cfg(not(feature = "part2"))]
   pub buf: &'a mut [usize],
-  #[cfg(not(feature = "part2"))]
   pub in_queue: &'a mut [bool],
+}
+
+/// Part 2's view: the splitter map, and two rows of beam counts.
+pub struct Part2Problem<'a> {
+  pub splitters: &'a [bool],
+  pub start: (usize, usize),
+  pub width: usize,
+  pub height: usize,
 
-  #[cfg(feature = "part2")]
   pub counts: &'a mut [u64],
 }
 
+/// Work part 1's queue did following the beams.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+  /// Beam cells scheduled.
+  pub pushes: u64,
+  /// Beams that reached an already visited cell.
+  pub merged: u64,
+}
+
+impl Counters for Stats {}
+
+impl fmt::Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "pushes: {}", self.pushes)?;
+    writeln!(f, "merged: {}", self.merged)
+  }
+}
+
+/// The raw manifold diagram, measured but not yet unpacked.
+pub struct Grid<'a> {
+  pub input: &'a str,
+  pub start: (usize, usize),
+  pub width: usize,
+  pub height: usize,
+}
+
+/// Caller-owned memory backing the `Part1Problem` built from a `Grid`.
+#[derive(Default)]
+pub struct Part1Scratch<'a> {
+  pub splitters: &'a mut [bool],
+  pub buf: &'a mut [usize],
+  pub in_queue: &'a mut [bool],
+}
+
+/// Caller-owned memory backing the `Part2Problem` built from a `Grid`.
+#[derive(Default)]
+pub struct Part2Scratch<'a> {
+  pub splitters: &'a mut [bool],
+  pub counts: &'a mut [u64],
+}
+
+/// Memory for either part, as the `Solver` takes it; `part1` and `part2`
+/// hand each part its own buffers.
+#[derive(Default)]
+pub struct Scratch<'a> {
+  pub splitters: &'a mut [bool],
+  pub buf: &'a mut [usize],
+  pub in_queue: &'a mut [bool],
+  pub counts: &'a mut [u64],
+}
+
+impl<'a> Scratch<'a> {
+  pub fn part1(self) -> Part1Scratch<'a> {
+    Part1Scratch {
+      splitters: self.splitters,
+      buf: self.buf,
+      in_queue: self.in_queue,
+    }
+  }
+
+  pub fn part2(self) -> Part2Scratch<'a> {
+    Part2Scratch { splitters: self.splitters, counts: self.counts }
+  }
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+/// Checks the diagram is rectangular, holds only `.`, `^` and `S`, and has a
+/// start.
+pub fn parse(input: &str) -> Result<Grid<'_>, ParseError> {
+  let height = input.lines().count();
+  let width = input.lines().next().map(|l| l.len()).unwrap_or(0);
+
+  let mut start = None;
+  for (y, line) in input.lines().enumerate() {
+    for (x, c) in line.char_indices() {
+      match c {
+        '.' | '^' => {}
+        'S' => start = Some((x, y)),
+        _ => {
+          return Err(ParseError::at(input, &line[x..], Reason::Unexpected(c)));
+        }
+      }
+    }
+    if line.len() != width {
+      let reason = Reason::Malformed("row width differs from the first row");
+      return Err(ParseError::at(input, line, reason));
+    }
+  }
+
+  let Some(start) = start else {
+    let reason = Reason::Malformed("no start `S`");
+    return Err(ParseError::at_offset(input, input.len(), reason));
+  };
+
+  Ok(Grid { input, start, width, height })
+}
+
+impl<'a> Grid<'a> {
+  /// Unpacks the splitter map into `scratch` and lends it out as part 1's
+  /// view.
+  pub fn as_part1<'s>(
+    &self,
+    scratch: Part1Scratch<'s>,
+  ) -> Result<Part1Problem<'s>, Error> {
+    Ok(Part1Problem {
+      splitters: self.unpack(scratch.splitters)?,
+      start: self.start,
+      width: self.width,
+      height: self.height,
+      buf: scratch.buf,
+      in_queue: scratch.in_queue,
+    })
+  }
+
+  /// Unpacks the splitter map into `scratch` and lends it out as part 2's
+  /// view.
+  pub fn as_part2<'s>(
+    &self,
+    scratch: Part2Scratch<'s>,
+  ) -> Result<Part2Problem<'s>, Error> {
+    Ok(Part2Problem {
+      splitters: self.unpack(scratch.splitters)?,
+      start: self.start,
+      width: self.width,
+      height: self.height,
+      counts: scratch.counts,
+    })
+  }
+
+  fn unpack<'s>(
+    &self,
+    splitters: &'s mut [bool],
+  ) -> Result<&'s [bool], Error> {
+    let cells = self.width * self.height;
+    Error::check_capacity("splitter cells", splitters.len(), cells)?;
+    let splitters = &mut splitters[..cells];
+    splitters.fill(false);
+
+    for (y, line) in self.input.lines().enumerate() {
+      for (x, b) in line.bytes().enumerate() {
+        if b == b'^' {
+          splitters[y * self.width + x] = true;
+        }
+      }
+    }
+
+    Ok(splitters)
+  }
+}
+
+// --------------------------
+// Scratch
+// --------------------------
+
+/// Working memory for one diagram: `cells` values for the splitter map and
+/// part 1's queue, `counts` beam counts for part 2, and `bytes` of
+/// workspace in all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Requirements {
+  pub cells: usize,
+  pub counts: usize,
+  pub bytes: usize,
+}
+
+pub fn requirements(g: &Grid) -> Requirements {
+  let cells = g.width * g.height;
+  let counts = 2 * g.width;
+
+  let bytes = Workspace::size_of::<bool>(cells)
+    + Workspace::size_of::<usize>(cells)
+    + Workspace::size_of::<bool>(cells)
+    + Workspace::size_of::<u64>(counts);
+
+  Requirements { cells, counts, bytes }
+}
+
+impl Requirements {
+  /// Cuts a `Scratch` out of `ws`, which needs `bytes` left.
+  pub fn carve<'a>(
+    &self,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    Ok(Scratch {
+      splitters: ws.take(self.cells, || false)?,
+      buf: ws.take(self.cells, || 0)?,
+      in_queue: ws.take(self.cells, || false)?,
+      counts: ws.take(self.counts, || 0)?,
+    })
+  }
+}
+
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
-  use tinysetqueue::{MembershipMode, TinySetQueue};
+  use aoc_core::Error;
+  use aoc_core::work::{NoTally, Tally};
+  use tinysetqueue::{MembershipMode, PushResult, TinySetQueue};
+
+  use super::{Part1Problem, Stats};
+
+  /// Counts a push, or fails with `full` when the queue had no room for it.
+  fn tally_push(
+    tally: &mut impl Tally<Stats>,
+    pushed: Result<PushResult, usize>,
+    full: Error,
+  ) -> Result<(), Error> {
+    match pushed {
+      Ok(PushResult::Inserted) => tally.tally(|s| s.pushes += 1),
+      Ok(PushResult::AlreadyPresent) => tally.tally(|s| s.merged += 1),
+      Err(_) => return Err(full),
+    }
+    Ok(())
+  }
 
-  use super::Problem;
+  pub fn part1(p: &mut Part1Problem) -> Result<u64, Error> {
+    part1_tallied(p, &mut NoTally)
+  }
+
+  /// `part1`, with the work its queue did.
+  #[cfg(feature = "instrument")]
+  pub fn part1_instrumented(
+    p: &mut Part1Problem,
+  ) -> Result<(u64, Stats), Error> {
+    let mut stats = Stats::default();
+    let splits = part1_tallied(p, &mut stats)?;
+    Ok((splits, stats))
+  }
 
-  pub fn part1(p: &mut Problem) -> u64 {
+  fn part1_tallied(
+    p: &mut Part1Problem,
+    tally: &mut impl Tally<Stats>,
+  ) -> Result<u64, Error> {
     let size = p.width * p.height;
+    Error::check_capacity("splitter cells", p.splitters.len(), size)?;
+
+    // every cell is queued at most once, so `size` slots always suffice
+    let limit = p.buf.len().min(p.in_queue.len());
+    Error::check_capacity("queued beams", limit, size)?;
+    let full = Error::CapacityExceeded {
+      what: "queued beams",
+      limit,
+      needed: size,
+    };
 
     let mut queue =
       TinySetQueue::new(p.buf, p.in_queue, MembershipMode::Visited);
 
-    let _ = queue.push(p.start.1 * p.width + p.start.0);
+    tally_push(tally, queue.push(p.start.1 * p.width + p.start.0), full)?;
 
     let mut splits = 0;
     while let Some(idx) = queue.pop() {
@@ -64,17 +324,17 @@ mod part1_impl {
         splits += 1;
 
         if x > 0 {
-          let _ = queue.push(next_idx - 1);
+          tally_push(tally, queue.push(next_idx - 1), full)?;
         }
         if x < p.width - 1 {
-          let _ = queue.push(next_idx + 1);
+          tally_push(tally, queue.push(next_idx + 1), full)?;
         }
       } else {
-        let _ = queue.push(next_idx);
+        tally_push(tally, queue.push(next_idx), full)?;
       }
     }
 
-    splits
+    Ok(splits)
   }
 }
 
@@ -84,12 +344,15 @@ mod part1_impl {
 
 #[cfg(feature = "part2")]
 mod part2_impl {
-  use super::Problem;
+  use aoc_core::Error;
 
-  pub fn part2(p: &mut Problem) -> u64 {
-    if p.counts.len() < 2 * p.width {
-      return 0;
-    }
+  use super::Part2Problem;
+
+  pub fn part2(p: &mut Part2Problem) -> Result<u64, Error> {
+    let size = p.width * p.height;
+    Error::check_capacity("splitter cells", p.splitters.len(), size)?;
+    // two rows of counts: the one being read and the one being written
+    Error::check_capacity("beam counts", p.counts.len(), 2 * p.width)?;
 
     p.counts.fill(0);
 
@@ -130,7 +393,52 @@ mod part2_impl {
 
     let final_row = &p.counts[src_offset..src_offset + p.width];
 
-    final_row.iter().sum()
+    Ok(final_row.iter().sum())
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day7;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day7 {
+  const DAY: u8 = 7;
+
+  type Input<'a> = Grid<'a>;
+  type Scratch<'a> = Scratch<'a>;
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Grid<'_>, Error> {
+    Ok(parse(input)?)
+  }
+
+  fn solve<'a>(
+    part: Part,
+    g: &Grid<'a>,
+    scratch: Scratch<'a>,
+  ) -> Result<Answer, Error> {
+    let answer = match part {
+      Part::One => part1_impl::part1(&mut g.as_part1(scratch.part1())?)?,
+      Part::Two => part2_impl::part2(&mut g.as_part2(scratch.part2())?)?,
+    };
+    Ok(answer.into())
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Carve for Day7 {
+  fn bytes(g: &Grid) -> usize {
+    requirements(g).bytes
+  }
+
+  fn carve<'a>(
+    g: &Grid,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    requirements(g).carve(ws)
   }
 }
 
@@ -143,26 +451,127 @@ mod tests {
   use std_parse::parse;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  fn test_requirements() {
+    let grid = crate::parse(include_str!("../sample.txt")).unwrap();
+    let needs = requirements(&grid);
+    assert_eq!(needs.cells, grid.width * grid.height);
+
+    // exactly `bytes` is enough for both parts
+    let mut block = [0u8; 4096];
+    let mut ws = aoc_core::Workspace::new(&mut block[..needs.bytes]);
+    assert!(needs.carve(&mut ws).is_ok());
+    assert!(needs.carve(&mut ws).is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let mut problem_data = parse(input);
-    let mut problem = problem_data.as_problem();
+    let mut problem_data = parse(input).unwrap();
+    let mut problem = problem_data.as_part1();
+
+    let result = part1(&mut problem).unwrap();
+
+    assert_eq!(result, 21);
+  }
+
+  #[test]
+  #[cfg(all(feature = "part1", feature = "instrument"))]
+  fn test_part1_instrumented() {
+    let input = include_str!("../sample.txt");
+    let mut problem_data = parse(input).unwrap();
+    let mut problem = problem_data.as_part1();
 
-    let result = part1(&mut problem);
+    let (result, stats) = part1_instrumented(&mut problem).unwrap();
 
     assert_eq!(result, 21);
+    assert_eq!(stats, Stats { pushes: 82, merged: 13 });
   }
 
   #[test]
   #[cfg(feature = "part2")]
   fn test_part2() {
     let input = include_str!("../sample.txt");
-    let mut problem_data = parse(input);
-    let mut problem = problem_data.as_problem();
+    let mut problem_data = parse(input).unwrap();
+    let mut problem = problem_data.as_part2();
 
-    let result = part2(&mut problem);
+    let result = part2(&mut problem).unwrap();
 
     assert_eq!(result, 40);
   }
+
+  #[test]
+  fn test_capacity_exceeded() {
+    use aoc_core::{Error, Workspace};
+
+    let grid = crate::parse(include_str!("../sample.txt")).unwrap();
+    let needs = requirements(&grid);
+    let cells = grid.width * grid.height;
+
+    // a splitter map a cell short is reported, not sliced past its end
+    let short = Requirements { cells: cells - 1, ..needs };
+    let mut block = [0u8; 4096];
+    let scratch = short.carve(&mut Workspace::new(&mut block)).unwrap();
+    let err = grid.as_part1(scratch.part1()).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded {
+        what: "splitter cells",
+        limit: cells - 1,
+        needed: cells,
+      }
+    );
+
+    #[cfg(feature = "part1")]
+    {
+      let mut problem_data = parse(include_str!("../sample.txt")).unwrap();
+      let mut problem = problem_data.as_part1();
+      problem.buf = &mut [];
+      let err = part1(&mut problem).err().unwrap();
+      assert_eq!(
+        err,
+        Error::CapacityExceeded {
+          what: "queued beams",
+          limit: 0,
+          needed: cells,
+        }
+      );
+    }
+
+    #[cfg(feature = "part2")]
+    {
+      let mut problem_data = parse(include_str!("../sample.txt")).unwrap();
+      let mut counts = [0; 3];
+      let mut problem = problem_data.as_part2();
+      problem.counts = &mut counts;
+      let err = part2(&mut problem).err().unwrap();
+      assert_eq!(
+        err,
+        Error::CapacityExceeded {
+          what: "beam counts",
+          limit: 3,
+          needed: 2 * grid.width,
+        }
+      );
+    }
+  }
+
+  #[test]
+  fn test_parse_error() {
+    use crate::parse;
+    use aoc_core::{ParseError, Reason};
+
+    let err = parse("..S..\n.....\n..v..\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 3, column: 3, reason: Reason::Unexpected('v') }
+    );
+
+    let err = parse(".....\n..^..\n").err().unwrap();
+    assert_eq!(err.reason, Reason::Malformed("no start `S`"));
+
+    // the std parser agrees, rather than starting an empty grid at (0, 0)
+    let err = std_parse::parse("").err().unwrap();
+    assert_eq!(err.reason, Reason::Malformed("no start `S`"));
+  }
 }
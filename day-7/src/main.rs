@@ -1,28 +1,19 @@
+use aoc_core::Error;
 use day_7::prelude::*;
 mod std_parse;
 use std_parse::parse;
 
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+fn main() -> Result<(), Error> {
+  let input = aoc_core::input::from_arg(1);
+  let mut problem_data = parse(&input)?;
+  let mut problem = problem_data.as_part1();
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+  let p1 = part1(&mut problem)?;
+  println!("Part 1: {p1}");
 
+  let p2 = part2(&mut problem_data.as_part2())?;
+  println!("Part 2: {p2}");
 
-fn main() {
-  let mut problem_data = parse(INPUT);
-  let mut problem = problem_data.as_problem();
-
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&mut problem);
-    println!("Part 1: {p1}");
-  }
-
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&mut problem);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
@@ -3,7 +3,12 @@ extern crate alloc;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::Problem;
+use aoc_core::{ParseError, Reason};
+
+#[cfg(feature = "part1")]
+use crate::Part1Problem;
+#[cfg(feature = "part2")]
+use crate::Part2Problem;
 
 
 pub struct ProblemData {
@@ -12,9 +17,10 @@ pub struct ProblemData {
   width: usize,
   height: usize,
 
-  #[cfg(not(feature = "part2"))]
+  // only the parts built are allocated for
+  #[cfg(feature = "part1")]
   buf: Vec<usize>,
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   in_queue: Vec<bool>,
 
   #[cfg(feature = "part2")]
@@ -22,53 +28,73 @@ pub struct ProblemData {
 }
 
 impl ProblemData {
-  pub fn as_problem(&mut self) -> Problem<'_> {
-    Problem {
+  #[cfg(feature = "part1")]
+  pub fn as_part1(&mut self) -> Part1Problem<'_> {
+    Part1Problem {
       splitters: &self.splitters,
       start: self.start,
       width: self.width,
       height: self.height,
-
-      #[cfg(not(feature = "part2"))]
       buf: &mut self.buf,
-      #[cfg(not(feature = "part2"))]
       in_queue: &mut self.in_queue,
+    }
+  }
 
-      #[cfg(feature = "part2")]
+  #[cfg(feature = "part2")]
+  pub fn as_part2(&mut self) -> Part2Problem<'_> {
+    Part2Problem {
+      splitters: &self.splitters,
+      start: self.start,
+      width: self.width,
+      height: self.height,
       counts: &mut self.counts,
     }
   }
 }
 
-pub fn parse(input: &str) -> ProblemData {
+pub fn parse(input: &str) -> Result<ProblemData, ParseError> {
   let height = input.lines().count();
   let width = input.lines().next().map(|l| l.len()).unwrap_or(0);
   let size = width * height;
 
   let mut splitters = vec![false; size];
-  let mut start = (0, 0);
+  let mut start = None;
 
   for (y, line) in input.lines().enumerate() {
-    for (x, ch) in line.chars().enumerate() {
+    if line.len() != width {
+      let reason = Reason::Malformed("row width differs from the first row");
+      return Err(ParseError::at(input, line, reason));
+    }
+
+    for (x, ch) in line.char_indices() {
       match ch {
         '^' => splitters[y * width + x] = true,
-        'S' => start = (x, y),
-        _ => {}
+        'S' => start = Some((x, y)),
+        '.' => {}
+        _ => {
+          let at = &line[x..];
+          return Err(ParseError::at(input, at, Reason::Unexpected(ch)));
+        }
       }
     }
   }
 
-  ProblemData {
+  let Some(start) = start else {
+    let reason = Reason::Malformed("no start `S`");
+    return Err(ParseError::at_offset(input, input.len(), reason));
+  };
+
+  Ok(ProblemData {
     splitters,
     start,
     width,
     height,
-    #[cfg(not(feature = "part2"))]
+    #[cfg(feature = "part1")]
     buf: vec![0; size],
-    #[cfg(not(feature = "part2"))]
+    #[cfg(feature = "part1")]
     in_queue: vec![false; size],
 
     #[cfg(feature = "part2")]
     counts: vec![0; 2 * width],
-  }
+  })
 }
@@ -0,0 +1,44 @@
+// The manifold is walked in caller-owned memory: parsing and solving must
+// never reach the heap. The std-side owner is reported for comparison.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::audit::{Allocations, CountingAlloc, measure};
+use aoc_core::{Part, Solver, Workspace};
+use day_7::prelude::*;
+
+#[allow(dead_code)]
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+fn solve(part: Part) -> Allocations {
+  let needs = requirements(&parse(INPUT).unwrap());
+
+  // caller-owned block, allocated before measuring
+  let mut block = vec![0u8; needs.bytes];
+
+  let (_, seen) = measure(|| {
+    let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+    let p = Day7::parse(INPUT).unwrap();
+    Day7::solve(part, &p, scratch).unwrap()
+  });
+  seen
+}
+
+#[test]
+fn test_allocations() {
+  for part in [Part::One, Part::Two] {
+    let seen = solve(part);
+    println!("{part:?}: {seen}");
+
+    assert_eq!(seen, Allocations::default(), "{part:?}");
+  }
+
+  let (_, seen) = measure(|| std_parse::parse(INPUT).unwrap());
+  println!("std_parse::parse: {seen}");
+}
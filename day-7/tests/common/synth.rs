@@ -0,0 +1,27 @@
+use fastrand::Rng;
+
+/// A `width` by `height` manifold laid out like the puzzle's: `S` in the
+/// middle of the top row, splitters on every other row, never at an edge
+/// nor next to each other, and empty rows in between and at the bottom.
+pub fn manifold(seed: u64, width: usize, height: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let empty = ".".repeat(width);
+  let mut top = empty.clone();
+  top.replace_range(width / 2..=width / 2, "S");
+
+  let mut lines = vec![top];
+  for y in 1..height {
+    if y % 2 == 1 || y == height - 1 {
+      lines.push(empty.clone());
+      continue;
+    }
+    let mut row = vec!['.'; width];
+    for x in 1..width.saturating_sub(1) {
+      if row[x - 1] == '.' && rng.u8(..3) == 0 {
+        row[x] = '^';
+      }
+    }
+    lines.push(row.into_iter().collect());
+  }
+  lines.join("\n")
+}
@@ -0,0 +1,35 @@
+/* Day 7's C ABI on the sample, run by `tests/ffi.rs`. */
+#include "check.h"
+#include "day_7.h"
+
+int main(int argc, char **argv) {
+  CHECK(argc == 2);
+  size_t len;
+  char *sample = read_input(argv[1], &len);
+  const uint8_t *text = (const uint8_t *)sample;
+  uint64_t answer = 0;
+
+  size_t bytes;
+  CHECK(aoc_day7_scratch_size(text, len, &bytes) == AOC_OK);
+  uint8_t *scratch = malloc(bytes);
+
+  CHECK(aoc_day7_part1(text, len, scratch, bytes, &answer) == AOC_OK);
+  CHECK(answer == 21);
+  CHECK(aoc_day7_part2(text, len, scratch, bytes, &answer) == AOC_OK);
+  CHECK(answer == 40);
+
+  // failures leave the answer alone
+  CHECK(aoc_day7_part1(text, len, scratch, bytes - 1, &answer) ==
+        AOC_SCRATCH_TOO_SMALL);
+  CHECK(aoc_day7_part1((const uint8_t *)"x\n", 2, scratch, bytes,
+                       &answer) == AOC_PARSE);
+  CHECK(aoc_day7_part1((const uint8_t *)"\xff", 1, scratch, bytes,
+                       &answer) == AOC_INVALID_UTF8);
+  CHECK(aoc_day7_part1(NULL, 1, scratch, bytes, &answer) ==
+        AOC_NULL_POINTER);
+  CHECK(answer == 40);
+
+  free(scratch);
+  free(sample);
+  return 0;
+}
@@ -0,0 +1,36 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking, through both the `Solver` and the std parser
+// the binary uses. Crashing inputs are minimized and saved back into
+// `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver, Workspace};
+use day_7::prelude::*;
+
+#[allow(dead_code)]
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+
+fn target(input: &str) {
+  if let Ok(p) = Day7::parse(input) {
+    let needs = requirements(&p);
+    let mut block = vec![0u8; needs.bytes];
+
+    for part in [Part::One, Part::Two] {
+      let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+      let _ = Day7::solve(part, &p, scratch);
+    }
+  }
+
+  if let Ok(mut data) = std_parse::parse(input) {
+    let _ = part1(&mut data.as_part1());
+    let _ = part2(&mut data.as_part2());
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
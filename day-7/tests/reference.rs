@@ -0,0 +1,118 @@
+// Differential tests: random manifolds go through the `Solver` and through a
+// naive std reference that follows every beam, and every timeline, on its
+// own.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver, Workspace};
+use day_7::prelude::*;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  use std::collections::BTreeSet;
+
+  fn grid(input: &str) -> Vec<&[u8]> {
+    input.lines().map(str::as_bytes).collect()
+  }
+
+  fn start(grid: &[&[u8]]) -> usize {
+    grid[0].iter().position(|&b| b == b'S').unwrap()
+  }
+
+  /// Moves the beams down a row at a time; a splitter counts once, however
+  /// many beams reach it.
+  pub fn part1(input: &str) -> u64 {
+    let grid = grid(input);
+    let mut beams = BTreeSet::from([start(&grid)]);
+    let mut splits = 0;
+    for row in &grid[1..] {
+      let mut next = BTreeSet::new();
+      for &x in &beams {
+        if row[x] == b'^' {
+          splits += 1;
+          next.extend([x - 1, x + 1]);
+        } else {
+          next.insert(x);
+        }
+      }
+      beams = next;
+    }
+    splits
+  }
+
+  fn timelines(grid: &[&[u8]], x: usize, y: usize) -> u64 {
+    match grid.get(y + 1) {
+      None => 1,
+      Some(row) if row[x] == b'^' => {
+        timelines(grid, x - 1, y + 1) + timelines(grid, x + 1, y + 1)
+      }
+      Some(_) => timelines(grid, x, y + 1),
+    }
+  }
+
+  pub fn part2(input: &str) -> u64 {
+    let grid = grid(input);
+    timelines(&grid, start(&grid), 0)
+  }
+}
+
+/// Manifolds laid out like the puzzle's: `S` in the top row, splitters on
+/// every other row, never at an edge nor next to each other, and an empty
+/// last row.
+fn manifolds() -> impl Strategy<Value = String> {
+  (3..=15usize, 1..=7usize).prop_flat_map(|(width, levels)| {
+    let level =
+      prop::collection::vec(prop::bool::ANY, width).prop_map(move |wanted| {
+        let mut row = vec!['.'; width];
+        for x in 1..width - 1 {
+          if wanted[x] && row[x - 1] == '.' {
+            row[x] = '^';
+          }
+        }
+        row.into_iter().collect::<String>()
+      });
+    (0..width, prop::collection::vec(level, levels)).prop_map(
+      move |(start, levels)| {
+        let empty = ".".repeat(width);
+        let mut top = empty.clone();
+        top.replace_range(start..=start, "S");
+
+        let mut lines = vec![top, empty.clone()];
+        for level in levels {
+          lines.extend([level, empty.clone()]);
+        }
+        lines.join("\n")
+      },
+    )
+  })
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day7::parse(input).unwrap();
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+
+  Day7::solve(part, &p, scratch).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in manifolds()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in manifolds()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::manifold(seed, 15, 15);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
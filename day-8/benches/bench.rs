@@ -1,24 +1,34 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 use day_8::prelude::*;
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
 
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
+// the sample connects 10 pairs, which `AOC_K_EDGES` names; the real input
+// `K_EDGES`
+#[cfg(feature = "part1")]
+static K_EDGES: LazyLock<usize> = LazyLock::new(|| {
+  std::env::var("AOC_K_EDGES").map_or(day_8::K_EDGES, |k| {
+    k.parse().expect("AOC_K_EDGES is a number of pairs")
+  })
+});
 
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 #[divan::bench]
 fn bench_part1() {
-  let p = parse(black_box(INPUT));
+  let p = parse(black_box(INPUT.as_str()))
+    .unwrap()
+    .with_k_edges(*K_EDGES)
+    .unwrap();
   black_box(part1(&p));
 }
 
 #[cfg(feature = "part2")]
 #[divan::bench]
 fn bench_part2() {
-  let p = parse(black_box(INPUT));
-  black_box(part2(&p));
+  let p = parse(black_box(INPUT.as_str())).unwrap();
+  black_box(part2(&p).unwrap());
 }
 
 fn main() {
@@ -0,0 +1,41 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use day_8::prelude::*;
+use divan::{Bencher, black_box};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Junction boxes; part 1 always connects `K_EDGES` pairs.
+const SIZES: &[usize] = &[125, 250, 500, 1_000];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, n: usize) {
+  let input = synth::boxes(SEED, n);
+  bencher.bench(|| {
+    let p = parse(black_box(&input))
+      .unwrap()
+      .with_k_edges(day_8::K_EDGES)
+      .unwrap();
+    black_box(part1(&p));
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, n: usize) {
+  let input = synth::boxes(SEED, n);
+  bencher.bench(|| {
+    let p = parse(black_box(&input)).unwrap();
+    black_box(part2(&p).unwrap());
+  });
+}
+
+fn main() {
+  divan::main();
+}
@@ -0,0 +1,43 @@
+//! Day 8's C ABI, declared in `include/day_8.h`; see `aoc_core::ffi`.
+
+use aoc_core::ffi::{self, Status};
+use aoc_core::{Part, Solver};
+
+use crate::{Day8, parse};
+
+/// Part 1's answer after joining the `k_edges` closest pairs: 1000 for the
+/// puzzle, 10 for its sample.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `answer` to a writable
+/// `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day8_part1(
+  input: *const u8,
+  len: usize,
+  k_edges: usize,
+  answer: *mut u64,
+) -> Status {
+  let solved = (|| -> Result<u64, Status> {
+    let text = unsafe { ffi::input(input, len)? };
+    let problem = parse(text)?.with_k_edges(k_edges)?;
+    let answer = Day8::solve(Part::One, &problem, ())?;
+    u64::try_from(answer).map_err(|_| Status::CapacityExceeded)
+  })();
+
+  unsafe { ffi::write(answer, solved) }
+}
+
+/// Part 2's answer for the `len` bytes of puzzle text at `input`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `answer` to a writable
+/// `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_day8_part2(
+  input: *const u8,
+  len: usize,
+  answer: *mut u64,
+) -> Status {
+  unsafe { ffi::solve::<Day8>(Part::Two, input, len, answer) }
+}
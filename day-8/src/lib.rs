@@ -3,14 +3,27 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use core::fmt;
+
+use aoc_core::parse::{number, split_once};
+use aoc_core::work::Counters;
+use aoc_core::{Error, ParseError, Reason};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day8, Problem, Stats, parse};
 
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   pub use crate::part1_impl::part1;
+  #[cfg(all(feature = "part1", feature = "instrument"))]
+  pub use crate::part1_impl::part1_instrumented;
 
   #[cfg(feature = "part2")]
-  pub use crate::part2_impl::part2;
+  pub use crate::part2_impl::{Part2, part2};
 }
 
 // --------------------------
@@ -18,21 +31,22 @@ pub mod prelude {
 // --------------------------
 
 const MAX_POINTS: usize = 1000;
-#[cfg(not(feature = "part2"))]
+// part 1 keeps the K closest pairs in a heap of MAX_EDGES
 const MAX_EDGES: usize = 1000;
 
-#[cfg(all(feature = "sample", not(feature = "part2")))]
-pub const K_EDGES: usize = 10;
-
-#[cfg(all(not(feature = "sample"), not(feature = "part2")))]
+/// Pairs part 1 connects in the real puzzle; the sample uses 10.
 pub const K_EDGES: usize = 1000;
 
+// keeps every squared distance, and part 2's product, within 64 bits
+const MAX_COORDINATE: u32 = 1 << 30;
+
 // --------------------------
 // Data Model
 // --------------------------
 
 pub struct Problem<'a> {
   pub input: &'a str,
+  k_edges: usize,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -42,16 +56,29 @@ pub struct ThreeSpacePoint {
   z: i32,
 }
 
-impl From<&str> for ThreeSpacePoint {
-  fn from(value: &str) -> Self {
-    let mut positions = value.trim().split(',');
-    let x = positions.next().and_then(|s| s.parse().ok()).unwrap_or(0);
-    let y = positions.next().and_then(|s| s.parse().ok()).unwrap_or(0);
-    let z = positions.next().and_then(|s| s.parse().ok()).unwrap_or(0);
-    ThreeSpacePoint { x, y, z }
+impl ThreeSpacePoint {
+  /// Parses `x,y,z`; `line` is a sub-slice of `input`.
+  fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+    let (x, rest) = split_once(input, line, ',')?;
+    let (y, z) = split_once(input, rest, ',')?;
+
+    Ok(ThreeSpacePoint {
+      x: coordinate(input, x)?,
+      y: coordinate(input, y)?,
+      z: coordinate(input, z)?,
+    })
   }
 }
 
+/// A coordinate in `0..=MAX_COORDINATE`, as the puzzle's are.
+fn coordinate(input: &str, field: &str) -> Result<i32, ParseError> {
+  let value = number::<u32>(input, field)?;
+  if value > MAX_COORDINATE {
+    return Err(ParseError::at(input, field, Reason::OutOfRange));
+  }
+  Ok(value as i32)
+}
+
 // (squared_distance, index_from, index_to)
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct HeapItem((u64, usize, usize));
@@ -83,24 +110,73 @@ impl HeapItem {
   }
 }
 
+/// Work part 1's disjoint sets did joining the closest pairs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+  /// Root lookups, two per pair joined.
+  pub finds: u64,
+  /// Pairs that merged two separate circuits.
+  pub unions: u64,
+}
+
+impl Counters for Stats {}
+
+impl fmt::Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "finds: {}", self.finds)?;
+    writeln!(f, "unions: {}", self.unions)
+  }
+}
+
 // --------------------------
 // Parse
 // --------------------------
 
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+/// Checks every junction box up front; the solvers re-read them lazily.
+pub fn parse(input: &str) -> Result<Problem<'_>, Error> {
+  let mut count = 0;
+  for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+    ThreeSpacePoint::parse(input, line)?;
+    count += 1;
+  }
+  Error::check_capacity("junction boxes", MAX_POINTS, count)?;
+
+  Ok(Problem { input, k_edges: K_EDGES })
+}
+
+impl<'a> Problem<'a> {
+  /// Connects the `k` closest pairs in part 1 instead of `K_EDGES`.
+  pub fn with_k_edges(self, k: usize) -> Result<Self, Error> {
+    Error::check_capacity("closest pairs", MAX_EDGES, k)?;
+    Ok(Problem { k_edges: k, ..self })
+  }
+
+  pub fn k_edges(&self) -> usize {
+    self.k_edges
+  }
+
+  /// One point per non-blank line, already validated and counted by `parse`.
+  pub fn points(&self) -> impl Iterator<Item = ThreeSpacePoint> + use<'a> {
+    let input = self.input;
+    input
+      .lines()
+      .map(str::trim)
+      .filter(|l| !l.is_empty())
+      .filter_map(move |l| ThreeSpacePoint::parse(input, l).ok())
+  }
 }
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
+  use aoc_core::work::{NoTally, Tally};
   use heapless::Vec;
   use heapless::binary_heap::{BinaryHeap, Max};
 
   use super::{
-    HeapItem, K_EDGES, MAX_EDGES, MAX_POINTS, Problem, ThreeSpacePoint,
+    HeapItem, MAX_EDGES, MAX_POINTS, Problem, Stats, ThreeSpacePoint,
   };
 
 
@@ -119,7 +195,8 @@ mod part1_impl {
     }
 
     // Find with Path Compression
-    fn find(&mut self, i: usize) -> usize {
+    fn find(&mut self, i: usize, tally: &mut impl Tally<Stats>) -> usize {
+      tally.tally(|s| s.finds += 1);
       let mut root = i;
       while root != self.parent[root] {
         root = self.parent[root];
@@ -134,11 +211,12 @@ mod part1_impl {
     }
 
     // Union by Size
-    fn union(&mut self, i: usize, j: usize) {
-      let root_i = self.find(i);
-      let root_j = self.find(j);
+    fn union(&mut self, i: usize, j: usize, tally: &mut impl Tally<Stats>) {
+      let root_i = self.find(i, tally);
+      let root_j = self.find(j, tally);
 
       if root_i != root_j {
+        tally.tally(|s| s.unions += 1);
         if self.size[root_i] < self.size[root_j] {
           self.parent[root_i] = root_j;
           self.size[root_j] += self.size[root_i];
@@ -152,10 +230,18 @@ mod part1_impl {
 
 
   pub fn part1(p: &Problem) -> u64 {
-    let mut points: Vec<ThreeSpacePoint, MAX_POINTS> = Vec::new();
-    for line in p.input.lines() {
-      let _ = points.push(ThreeSpacePoint::from(line));
-    }
+    part1_tallied(p, &mut NoTally)
+  }
+
+  /// `part1`, with the work its disjoint sets did.
+  #[cfg(feature = "instrument")]
+  pub fn part1_instrumented(p: &Problem) -> (u64, Stats) {
+    let mut stats = Stats::default();
+    (part1_tallied(p, &mut stats), stats)
+  }
+
+  fn part1_tallied(p: &Problem, tally: &mut impl Tally<Stats>) -> u64 {
+    let points: Vec<ThreeSpacePoint, MAX_POINTS> = p.points().collect();
 
     let mut max_heap: BinaryHeap<HeapItem, Max, MAX_EDGES> = BinaryHeap::new();
 
@@ -163,13 +249,13 @@ mod part1_impl {
       for j in i + 1..points.len() {
         let item = HeapItem::from_points(&points[i], &points[j], i, j);
 
-        if max_heap.len() < K_EDGES {
+        if max_heap.len() < p.k_edges {
+          let _ = max_heap.push(item);
+        } else if let Some(top) = max_heap.peek()
+          && item.0.0 < top.0.0
+        {
+          let _ = max_heap.pop();
           let _ = max_heap.push(item);
-        } else if let Some(top) = max_heap.peek() {
-          if item.0.0 < top.0.0 {
-            let _ = max_heap.pop();
-            let _ = max_heap.push(item);
-          }
         }
       }
     }
@@ -179,7 +265,7 @@ mod part1_impl {
     let mut dsu = Dsu::new(&mut parent, &mut size, points.len());
 
     while let Some(HeapItem((_, u, v))) = max_heap.pop() {
-      dsu.union(u, v);
+      dsu.union(u, v, tally);
     }
 
     let mut size_heap: BinaryHeap<u16, Max, MAX_POINTS> = BinaryHeap::new();
@@ -206,73 +292,211 @@ mod part1_impl {
 mod part2_impl {
   use heapless::Vec;
 
-  use super::{MAX_POINTS, Problem, ThreeSpacePoint};
+  use aoc_core::resume::{Poll, Resumable};
+  use aoc_core::{Error, ParseError, Reason};
 
-  // Prim's algorithm to avoid allocating the full edge table on the stack.
-  // tracking each vertex the best connecting edge and its distance.
-  pub fn part2(p: &Problem) -> u64 {
-    let mut points: Vec<ThreeSpacePoint, MAX_POINTS> = Vec::new();
-    for line in p.input.lines() {
-      let _ = points.push(ThreeSpacePoint::from(line));
-    }
+  use super::{MAX_POINTS, Problem, ThreeSpacePoint};
 
-    let n = points.len();
-    if n < 2 {
-      panic!("Not enough points for part2");
+  // kept out of line so `Solver::solve`'s frame, which part 1 also pays
+  // for, does not hold part 2's buffers
+  #[inline(never)]
+  pub fn part2(p: &Problem) -> Result<u64, Error> {
+    // filled in place; `new` would copy the buffers out to return them
+    let mut steps = Part2::empty();
+    steps.load(p);
+    loop {
+      if let Poll::Ready(answer) = steps.step(u64::MAX) {
+        return answer;
+      }
     }
+  }
 
-    // Arrays sized to MAX_POINTS to avoid dynamic allocation; we only use indices < n.
-    let mut min_dist = [u64::MAX; MAX_POINTS];
-    let mut parent = [usize::MAX; MAX_POINTS];
-    let mut visited = [false; MAX_POINTS];
+  /// `part2` run a slice at a time. A unit is one junction box looked at,
+  /// either to pick the closest one to join next or to update its distance
+  /// to the tree.
+  pub struct Part2 {
+    points: Vec<ThreeSpacePoint, MAX_POINTS>,
+    // Arrays sized to MAX_POINTS to avoid dynamic allocation; we only use
+    // indices < n.
+    min_dist: [u64; MAX_POINTS],
+    parent: [usize; MAX_POINTS],
+    visited: [bool; MAX_POINTS],
+    phase: Phase,
+    joined: usize,
+    last_u: usize,
+    last_v: usize,
+    longest: u64,
+    lone: Option<ParseError>,
+  }
 
-    // Start from node 0
-    min_dist[0] = 0;
-    parent[0] = 0;
+  #[derive(Clone, Copy)]
+  enum Phase {
+    /// Looking from box `i` on for the closest box not yet joined.
+    Pick {
+      i: usize,
+      best: u64,
+      u: Option<usize>,
+    },
+    /// Updating distances from box `v` on through `u`, the box just joined.
+    Relax {
+      u: usize,
+      v: usize,
+    },
+    Done,
+  }
 
-    let mut last_u = 0usize;
-    let mut last_v = 0usize;
+  impl Part2 {
+    /// Reads `p`'s boxes; having fewer than two is an error, reported by the
+    /// first `step`.
+    pub fn new(p: &Problem) -> Self {
+      let mut part2 = Self::empty();
+      part2.load(p);
+      part2
+    }
 
-    for _ in 0..n {
-      let mut u = None;
-      let mut best = u64::MAX;
-      for i in 0..n {
-        if !visited[i] && min_dist[i] < best {
-          best = min_dist[i];
-          u = Some(i);
-        }
+    const fn empty() -> Self {
+      Part2 {
+        points: Vec::new(),
+        min_dist: [u64::MAX; MAX_POINTS],
+        parent: [usize::MAX; MAX_POINTS],
+        visited: [false; MAX_POINTS],
+        phase: Phase::Pick { i: 0, best: u64::MAX, u: None },
+        joined: 0,
+        last_u: 0,
+        last_v: 0,
+        longest: 0,
+        lone: None,
       }
+    }
 
-      let u = match u {
-        Some(idx) => idx,
-        None => break,
-      };
-
-      visited[u] = true;
+    fn load(&mut self, p: &Problem) {
+      // `parse` counted the boxes, so they all fit
+      for point in p.points() {
+        let _ = self.points.push(point);
+      }
 
-      if parent[u] != u {
-        last_u = parent[u];
-        last_v = u;
+      // a lone box is never connected to anything
+      if self.points.len() < 2 {
+        let reason = Reason::Malformed("part 2 needs two junction boxes");
+        let at = p.input.len();
+        self.lone = Some(ParseError::at_offset(p.input, at, reason));
       }
 
-      for v in 0..n {
-        if visited[v] || v == u {
-          continue;
-        }
+      // Start from node 0
+      self.min_dist[0] = 0;
+      self.parent[0] = 0;
+    }
+  }
+
+  // Prim's algorithm to avoid allocating the full edge table on the stack,
+  // tracking for each vertex the best connecting edge and its distance.
+  // Joining closest pairs first, the last connection made is the longest
+  // edge of the spanning tree, which need not be the last edge Prim adds.
+  impl Resumable for Part2 {
+    type Answer = Result<u64, Error>;
 
-        let dx = (points[u].x - points[v].x) as i64;
-        let dy = (points[u].y - points[v].y) as i64;
-        let dz = (points[u].z - points[v].z) as i64;
-        let dist = (dx * dx + dy * dy + dz * dz) as u64;
+    fn step(&mut self, budget: u64) -> Poll<Result<u64, Error>> {
+      if let Some(e) = self.lone {
+        return Poll::Ready(Err(e.into()));
+      }
 
-        if dist < min_dist[v] {
-          min_dist[v] = dist;
-          parent[v] = u;
+      let n = self.points.len();
+      let mut left = usize::try_from(budget.max(1)).unwrap_or(usize::MAX);
+
+      loop {
+        match self.phase {
+          Phase::Pick { i, mut best, mut u } if i < n => {
+            if left == 0 {
+              return Poll::Pending;
+            }
+            let end = n.min(i.saturating_add(left));
+            for i in i..end {
+              if !self.visited[i] && self.min_dist[i] < best {
+                best = self.min_dist[i];
+                u = Some(i);
+              }
+            }
+            left -= end - i;
+            self.phase = Phase::Pick { i: end, best, u };
+          }
+          Phase::Pick { u: None, .. } => self.phase = Phase::Done,
+          Phase::Pick { u: Some(u), best, .. } => {
+            self.visited[u] = true;
+
+            if self.parent[u] != u && best >= self.longest {
+              self.longest = best;
+              self.last_u = self.parent[u];
+              self.last_v = u;
+            }
+            self.phase = Phase::Relax { u, v: 0 };
+          }
+          Phase::Relax { u, v } if v < n => {
+            if left == 0 {
+              return Poll::Pending;
+            }
+            let end = n.min(v.saturating_add(left));
+            let from = self.points[u];
+            for v in v..end {
+              if self.visited[v] || v == u {
+                continue;
+              }
+
+              let to = self.points[v];
+              let dx = (from.x - to.x) as i64;
+              let dy = (from.y - to.y) as i64;
+              let dz = (from.z - to.z) as i64;
+              let dist = (dx * dx + dy * dy + dz * dz) as u64;
+
+              if dist < self.min_dist[v] {
+                self.min_dist[v] = dist;
+                self.parent[v] = u;
+              }
+            }
+            left -= end - v;
+            self.phase = Phase::Relax { u, v: end };
+          }
+          Phase::Relax { .. } => {
+            self.joined += 1;
+            self.phase = match self.joined {
+              joined if joined == n => Phase::Done,
+              _ => Phase::Pick { i: 0, best: u64::MAX, u: None },
+            };
+          }
+          Phase::Done => {
+            let (u, v) = (self.points[self.last_u], self.points[self.last_v]);
+            return Poll::Ready(Ok((u.x as u64) * (v.x as u64)));
+          }
         }
       }
     }
+  }
+}
 
-    (points[last_u].x as u64) * (points[last_v].x as u64)
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day8;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day8 {
+  const DAY: u8 = 8;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Problem<'_>, Error> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, Error> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p)?,
+    };
+
+    Ok(answer.into())
   }
 }
 
@@ -282,22 +506,112 @@ mod tests {
   use super::prelude::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap().with_k_edges(10).unwrap();
     let result = part1(&problem);
 
     assert_eq!(result, 40);
   }
 
+  #[test]
+  #[cfg(all(feature = "part1", feature = "instrument"))]
+  fn test_part1_instrumented() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap().with_k_edges(10).unwrap();
+    let (result, stats) = part1_instrumented(&problem);
+    assert_eq!(result, 40);
+
+    // one of the ten pairs is already in the same circuit
+    assert_eq!(stats, Stats { finds: 20, unions: 9 });
+  }
+
   #[test]
   #[cfg(feature = "part2")]
-  fn test_part1() {
+  fn test_part2() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
-    let result = part2(&problem);
+    let problem = parse(input).unwrap();
+    let result = part2(&problem).unwrap();
 
     assert_eq!(result, 25272);
   }
+
+  #[test]
+  #[cfg(feature = "part2")]
+  fn test_part2_longest_edge_added_early() {
+    // Prim adds the 0–2 edge first and 1–2 last, but 1–2 is the shorter
+    let input = "0,0,0\n46134,38253,7775\n2786,43499,4315\n";
+    let problem = parse(input).unwrap();
+    let result = part2(&problem).unwrap();
+
+    assert_eq!(result, 0);
+  }
+
+  #[test]
+  #[cfg(feature = "part2")]
+  fn test_part2_needs_two_boxes() {
+    use aoc_core::{Error, Reason};
+
+    let problem = parse("1,2,3\n").unwrap();
+    let Some(Error::Parse(err)) = part2(&problem).err() else {
+      panic!("expected a parse error");
+    };
+    assert_eq!(
+      err.reason,
+      Reason::Malformed("part 2 needs two junction boxes")
+    );
+  }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{Error, ParseError, Reason};
+
+    let err = parse("162,817,812\n57,618\n").err().unwrap();
+    let expected =
+      ParseError { line: 2, column: 7, reason: Reason::Expected(',') };
+    assert_eq!(err, Error::Parse(expected));
+
+    let Some(Error::Parse(err)) = parse("162,817,812\n57,6x8,57\n").err()
+    else {
+      panic!("expected a parse error");
+    };
+    assert_eq!((err.line, err.column), (2, 5));
+
+    // negative or huge coordinates would overflow the squared distances
+    let err = parse("1,2,3\n4,-5,6\n").err().unwrap();
+    let expected =
+      ParseError { line: 2, column: 3, reason: Reason::OutOfRange };
+    assert_eq!(err, Error::Parse(expected));
+    assert!(parse("1073741824,0,0\n").is_ok());
+    assert!(parse("1073741825,0,0\n").is_err());
+  }
+
+  #[test]
+  fn test_capacity_exceeded() {
+    extern crate std;
+    use aoc_core::Error;
+
+    let input = "1,2,3\n".repeat(super::MAX_POINTS + 1);
+    let err = parse(&input).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded {
+        what: "junction boxes",
+        limit: super::MAX_POINTS,
+        needed: super::MAX_POINTS + 1,
+      }
+    );
+
+    let k = super::MAX_EDGES + 1;
+    let err = parse("1,2,3\n").unwrap().with_k_edges(k).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded {
+        what: "closest pairs",
+        limit: super::MAX_EDGES,
+        needed: k,
+      }
+    );
+  }
 }
@@ -1,25 +1,28 @@
+use aoc_core::Error;
 use day_8::prelude::*;
 
+// the sample connects 10 pairs, the second argument; the real input
+// `K_EDGES`
+fn k_edges() -> usize {
+  let Some(k) = std::env::args().nth(2) else {
+    return day_8::K_EDGES;
+  };
+  k.parse().unwrap_or_else(|_| {
+    eprintln!("error: invalid number of pairs `{k}`");
+    std::process::exit(2)
+  })
+}
 
-#[cfg(feature = "sample")]
-const INPUT: &str = include_str!("../sample.txt");
-
-#[cfg(not(feature = "sample"))]
-const INPUT: &str = include_str!("../input.txt");
 
+fn main() -> Result<(), Error> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?.with_k_edges(k_edges())?;
 
-fn main() {
-  let problem = parse(INPUT);
+  let p1 = part1(&problem);
+  println!("Part 1: {p1}");
 
-  #[cfg(not(feature = "part2"))]
-  {
-    let p1 = part1(&problem);
-    println!("Part 1: {p1}");
-  }
+  let p2 = part2(&problem)?;
+  println!("Part 2: {p2}");
 
-  #[cfg(feature = "part2")]
-  {
-    let p2 = part2(&problem);
-    println!("Part 2: {p2}");
-  }
+  Ok(())
 }
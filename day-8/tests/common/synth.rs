@@ -0,0 +1,13 @@
+use fastrand::Rng;
+
+/// `n` junction boxes spread over a cube as large as the puzzle's.
+pub fn boxes(seed: u64, n: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let lines: Vec<String> = (0..n)
+    .map(|_| {
+      let [x, y, z] = [(); 3].map(|_| rng.u32(..100_000));
+      format!("{x},{y},{z}")
+    })
+    .collect();
+  lines.join("\n")
+}
@@ -0,0 +1,28 @@
+/* Day 8's C ABI on the sample, run by `tests/ffi.rs`. */
+#include "check.h"
+#include "day_8.h"
+
+int main(int argc, char **argv) {
+  CHECK(argc == 2);
+  size_t len;
+  char *sample = read_input(argv[1], &len);
+  const uint8_t *text = (const uint8_t *)sample;
+  uint64_t answer = 0;
+
+  // the sample joins its 10 closest pairs, the puzzle 1000
+  CHECK(aoc_day8_part1(text, len, 10, &answer) == AOC_OK);
+  CHECK(answer == 40);
+  CHECK(aoc_day8_part2(text, len, &answer) == AOC_OK);
+  CHECK(answer == 25272);
+
+  // failures leave the answer alone
+  CHECK(aoc_day8_part1(text, len, 1001, &answer) == AOC_CAPACITY_EXCEEDED);
+  CHECK(aoc_day8_part1((const uint8_t *)"x\n", 2, 10, &answer) == AOC_PARSE);
+  CHECK(aoc_day8_part1((const uint8_t *)"\xff", 1, 10, &answer) ==
+        AOC_INVALID_UTF8);
+  CHECK(aoc_day8_part1(NULL, 1, 10, &answer) == AOC_NULL_POINTER);
+  CHECK(answer == 25272);
+
+  free(sample);
+  return 0;
+}
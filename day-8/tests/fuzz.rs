@@ -0,0 +1,28 @@
+// Fuzz tests: the inputs under `corpus/`, and mutations of them, must parse
+// and solve without panicking. Crashing inputs are minimized and saved back
+// into `corpus/`, so they are replayed until fixed.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::fuzz::fuzz;
+use aoc_core::{Part, Solver};
+use day_8::Day8;
+
+fn target(input: &str) {
+  let Ok(p) = Day8::parse(input) else {
+    return;
+  };
+  for part in [Part::One, Part::Two] {
+    let _ = Day8::solve(part, &p, ());
+  }
+
+  // small inputs leave fewer pairs than part 1 connects by default
+  let k = input.lines().count();
+  if let Ok(p) = Day8::parse(input).and_then(|p| p.with_k_edges(k)) {
+    let _ = Day8::solve(Part::One, &p, ());
+  }
+}
+
+#[test]
+fn test_fuzz() {
+  fuzz(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus"), target);
+}
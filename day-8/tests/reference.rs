@@ -0,0 +1,124 @@
+// Differential tests: random junction boxes go through the `Solver` and
+// through a naive std reference that sorts every pair and merges circuits by
+// relabelling.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Part, Solver};
+use day_8::Day8;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  pub type Point = [i64; 3];
+
+  pub fn points(input: &str) -> Vec<Point> {
+    input
+      .lines()
+      .map(|l| {
+        let mut xyz = l.split(',').map(|v| v.parse().unwrap());
+        [(); 3].map(|_| xyz.next().unwrap())
+      })
+      .collect()
+  }
+
+  fn distance(a: &Point, b: &Point) -> i64 {
+    (0..3).map(|i| (a[i] - b[i]).pow(2)).sum()
+  }
+
+  /// Every pair, closest first.
+  pub fn pairs(points: &[Point]) -> Vec<(i64, usize, usize)> {
+    let mut pairs = Vec::new();
+    for i in 0..points.len() {
+      for j in i + 1..points.len() {
+        pairs.push((distance(&points[i], &points[j]), i, j));
+      }
+    }
+    pairs.sort();
+    pairs
+  }
+
+  /// Moves every box of `from`'s circuit into `into`'s.
+  fn connect(circuit: &mut [usize], into: usize, from: usize) {
+    let (into, from) = (circuit[into], circuit[from]);
+    for c in circuit.iter_mut().filter(|c| **c == from) {
+      *c = into;
+    }
+  }
+
+  pub fn part1(input: &str, k: usize) -> u64 {
+    let points = points(input);
+    let mut circuit: Vec<usize> = (0..points.len()).collect();
+    for &(_, i, j) in pairs(&points).iter().take(k) {
+      connect(&mut circuit, i, j);
+    }
+
+    let mut sizes: Vec<u64> = (0..points.len())
+      .map(|c| circuit.iter().filter(|&&o| o == c).count() as u64)
+      .collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes.resize(3, 0);
+    sizes[..3].iter().product()
+  }
+
+  /// The pair whose connection first leaves a single circuit.
+  pub fn part2(input: &str) -> u64 {
+    let points = points(input);
+    let mut circuit: Vec<usize> = (0..points.len()).collect();
+    for (_, i, j) in pairs(&points) {
+      connect(&mut circuit, i, j);
+      if circuit.iter().all(|&c| c == circuit[0]) {
+        return (points[i][0] * points[j][0]) as u64;
+      }
+    }
+    unreachable!("two or more boxes always end up in one circuit")
+  }
+}
+
+/// Boxes spread over a cube as large as the puzzle's. Ties between
+/// distances make "the k closest pairs" ambiguous, so those are rejected.
+fn boxes() -> impl Strategy<Value = String> {
+  let point = [0..100_000i64, 0..100_000i64, 0..100_000i64];
+  prop::collection::vec(point, 2..=40)
+    .prop_filter("two pairs are equally far apart", |points| {
+      let pairs = naive::pairs(points);
+      pairs.windows(2).all(|w| w[0].0 != w[1].0)
+    })
+    .prop_map(|points| {
+      let lines: Vec<String> = points
+        .iter()
+        .map(|[x, y, z]| format!("{x},{y},{z}"))
+        .collect();
+      lines.join("\n")
+    })
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in boxes(), k in 1..=40usize) {
+    let p = Day8::parse(&input).unwrap().with_k_edges(k).unwrap();
+    let answer = Day8::solve(Part::One, &p, ()).unwrap();
+    prop_assert_eq!(answer, naive::part1(&input, k).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in boxes()) {
+    let p = Day8::parse(&input).unwrap();
+    let answer = Day8::solve(Part::Two, &p, ()).unwrap();
+    prop_assert_eq!(answer, naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>()) {
+    let input = synth::boxes(seed, 40);
+    let pairs = naive::pairs(&naive::points(&input));
+    prop_assume!(pairs.windows(2).all(|w| w[0].0 != w[1].0));
+
+    let p = Day8::parse(&input).unwrap().with_k_edges(40).unwrap();
+    let answer = Day8::solve(Part::One, &p, ()).unwrap();
+    prop_assert_eq!(answer, naive::part1(&input, 40).into());
+    let answer = Day8::solve(Part::Two, &p, ()).unwrap();
+    prop_assert_eq!(answer, naive::part2(&input).into());
+  }
+}
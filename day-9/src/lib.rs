@@ -6,15 +6,25 @@ use core::fmt::Debug;
 #[cfg(feature = "std")]
 extern crate std;
 
+use aoc_core::parse::{number, split_once};
+#[cfg(feature = "part1")]
+use aoc_core::trace::{Event, NoTrace, TraceSink};
+use aoc_core::{Error, ParseError, Reason};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 
 pub mod prelude {
-  pub use crate::{Problem, parse};
+  pub use crate::{Day9, Problem, parse, parse_with_capacity};
 
-  #[cfg(not(feature = "part2"))]
-  pub use crate::part1_impl::part1;
+  #[cfg(feature = "part1")]
+  pub use crate::part1_impl::{part1, part1_traced};
 
   #[cfg(feature = "part2")]
-  pub use crate::part2_impl::part2;
+  pub use crate::part2_impl::{Part2, part2};
 }
 
 
@@ -22,13 +32,14 @@ pub mod prelude {
 // Data Model
 // --------------------------
 
-#[cfg(feature = "sample")]
-const N: usize = 20;
-#[cfg(not(feature = "sample"))]
-const N: usize = 512;
+/// Red tiles a `Problem` holds unless the caller picks its own `N`.
+pub const MAX_TILES: usize = 512;
 
+// keeps every rectangle's area within 64 bits
+const MAX_COORDINATE: u32 = 1 << 30;
 
-pub struct Problem<'a> {
+/// `N` bounds the red tiles, and so the stack the solvers use.
+pub struct Problem<'a, const N: usize = MAX_TILES> {
   pub input: &'a str,
 }
 
@@ -45,15 +56,23 @@ impl Coordinate {
   }
 }
 
-impl From<&str> for Coordinate {
-  fn from(value: &str) -> Self {
-    let mut positions = value.trim().split(',');
-    let x = positions.next().and_then(|s| s.parse().ok()).unwrap_or(0);
-    let y = positions.next().and_then(|s| s.parse().ok()).unwrap_or(0);
-    Coordinate { x, y }
+impl Coordinate {
+  /// Parses `x,y`; `line` is a sub-slice of `input`.
+  fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+    let (x, y) = split_once(input, line, ',')?;
+    Ok(Coordinate { x: coordinate(input, x)?, y: coordinate(input, y)? })
   }
 }
 
+/// A coordinate in `0..=MAX_COORDINATE`, as the puzzle's are.
+fn coordinate(input: &str, field: &str) -> Result<i32, ParseError> {
+  let value = number::<u32>(input, field)?;
+  if value > MAX_COORDINATE {
+    return Err(ParseError::at(input, field, Reason::OutOfRange));
+  }
+  Ok(value as i32)
+}
+
 #[cfg(feature = "std")]
 impl Debug for Coordinate {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -99,29 +118,61 @@ impl Iterator for Pairwise {
 // Parse
 // --------------------------
 
-pub fn parse(input: &str) -> Problem<'_> {
-  Problem { input }
+/// Checks every red tile up front; the solvers re-read them lazily.
+pub fn parse(input: &str) -> Result<Problem<'_>, Error> {
+  parse_with_capacity(input)
+}
+
+/// `parse`, for a `Problem` holding at most `N` red tiles.
+pub fn parse_with_capacity<const N: usize>(
+  input: &str,
+) -> Result<Problem<'_, N>, Error> {
+  let mut count = 0;
+  for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+    Coordinate::parse(input, line)?;
+    count += 1;
+  }
+  Error::check_capacity("red tiles", N, count)?;
+
+  Ok(Problem { input })
+}
+
+impl<'a, const N: usize> Problem<'a, N> {
+  /// One coordinate per non-blank line, already validated and counted by
+  /// `parse`.
+  pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + use<'a, N> {
+    let input = self.input;
+    input
+      .lines()
+      .map(str::trim)
+      .filter(|l| !l.is_empty())
+      .filter_map(move |l| Coordinate::parse(input, l).ok())
+  }
 }
 
 // --------------------------
 // Solver — Part 1
 // --------------------------
-#[cfg(not(feature = "part2"))]
+#[cfg(feature = "part1")]
 mod part1_impl {
   use heapless::Vec;
 
-  use super::{Coordinate, N, Pairwise, Problem};
+  use super::{Coordinate, Event, NoTrace, Pairwise, Problem, TraceSink};
 
 
-  pub fn part1(p: &Problem) -> u64 {
-    let coords: Vec<Coordinate, N> = p
-      .input
-      .lines()
-      .map(|line| Coordinate::from(line.trim()))
-      .collect();
+  pub fn part1<const N: usize>(p: &Problem<N>) -> u64 {
+    part1_traced(p, &mut NoTrace)
+  }
 
-    #[cfg(feature = "std")]
-    dbg!(&coords);
+  /// `part1`, reporting each red tile to `sink`.
+  pub fn part1_traced<const N: usize>(
+    p: &Problem<N>,
+    sink: &mut impl TraceSink,
+  ) -> u64 {
+    let coords: Vec<Coordinate, N> = p.coordinates().collect();
+    for (x, y) in coords.iter().map(Coordinate::to_tuple) {
+      sink.event(Event::Point { x: x.into(), y: y.into() });
+    }
 
     let mut acc: u64 = 0;
 
@@ -129,8 +180,8 @@ mod part1_impl {
       let (x1, y1) = coords[i].to_tuple();
       let (x2, y2) = coords[j].to_tuple();
 
-      let dx = (x1 - x2).abs() as u64 + 1;
-      let dy = (y1 - y2).abs() as u64 + 1;
+      let dx = (x1 - x2).unsigned_abs() as u64 + 1;
+      let dy = (y1 - y2).unsigned_abs() as u64 + 1;
       let area = dx * dy;
 
       if area > acc {
@@ -150,7 +201,9 @@ mod part1_impl {
 mod part2_impl {
   use heapless::Vec;
 
-  use super::{Coordinate, N, Pairwise, Problem};
+  use aoc_core::resume::{Poll, Resumable};
+
+  use super::{Coordinate, MAX_TILES, Pairwise, Problem};
 
 
   // (A, B_min, B_max)
@@ -229,62 +282,143 @@ mod part2_impl {
     true
   }
 
-  pub fn part2(p: &Problem) -> u64 {
-    let coords: Vec<Coordinate, N> = p
-      .input
-      .lines()
-      .map(|line| Coordinate::from(line.trim()))
-      .collect();
+  pub fn part2<const N: usize>(p: &Problem<N>) -> u64 {
+    // filled in place; `new` would copy the buffers out to return them
+    let mut steps = Part2::empty();
+    steps.load(p);
+    loop {
+      if let Poll::Ready(area) = steps.step(u64::MAX) {
+        return area;
+      }
+    }
+  }
 
-    let n = coords.len();
+  /// `part2` run a slice at a time. A unit is one pair of red tiles tried as
+  /// opposite corners.
+  pub struct Part2<const N: usize = MAX_TILES> {
+    coords: Vec<Coordinate, N>,
+    v_edges: Vec<VEdge, N>,
+    h_edges: Vec<HEdge, N>,
+    pairs: Pairwise,
+    max_area: u64,
+  }
 
-    let mut v_edges: Vec<VEdge, N> = Vec::new();
-    let mut h_edges: Vec<HEdge, N> = Vec::new();
+  impl<const N: usize> Part2<N> {
+    pub fn new(p: &Problem<N>) -> Self {
+      let mut part2 = Self::empty();
+      part2.load(p);
+      part2
+    }
 
-    for i in 0..n {
-      let c1 = coords[i];
-      let c2 = coords[(i + 1) % n];
-      if c1.x == c2.x {
-        let _ = v_edges.push((c1.x, c1.y.min(c2.y), c1.y.max(c2.y)));
-      } else {
-        let _ = h_edges.push((c1.y, c1.x.min(c2.x), c1.x.max(c2.x)));
+    const fn empty() -> Self {
+      Part2 {
+        coords: Vec::new(),
+        v_edges: Vec::new(),
+        h_edges: Vec::new(),
+        pairs: Pairwise::new(0),
+        max_area: 0,
       }
     }
 
-    let mut max_area: u64 = 0;
+    fn load(&mut self, p: &Problem<N>) {
+      // `parse` counted the tiles, so they all fit
+      for c in p.coordinates() {
+        let _ = self.coords.push(c);
+      }
+
+      let coords = &self.coords;
+      let n = coords.len();
+
+      // one edge per coordinate, so neither list can outgrow N
+      for i in 0..n {
+        let c1 = coords[i];
+        let c2 = coords[(i + 1) % n];
+        if c1.x == c2.x {
+          let _ = self.v_edges.push((c1.x, c1.y.min(c2.y), c1.y.max(c2.y)));
+        } else {
+          let _ = self.h_edges.push((c1.y, c1.x.min(c2.x), c1.x.max(c2.x)));
+        }
+      }
+
+      self.pairs = Pairwise::new(n);
+    }
 
-    for (i, j) in Pairwise::new(n) {
-      let c1 = coords[i];
-      let c2 = coords[j];
+    /// The area of the rectangle with corners `i` and `j`, if it lies inside
+    /// the loop.
+    fn area(&self, i: usize, j: usize) -> Option<u64> {
+      let c1 = self.coords[i];
+      let c2 = self.coords[j];
 
       let xa = c1.x.min(c2.x);
       let xb = c1.x.max(c2.x);
       let ya = c1.y.min(c2.y);
       let yb = c1.y.max(c2.y);
 
+      let (v_edges, h_edges) = (&self.v_edges, &self.h_edges);
+
       // Crossing test
-      if !rect_is_clear_of_edges(xa, xb, ya, yb, &v_edges, &h_edges) {
-        continue;
+      if !rect_is_clear_of_edges(xa, xb, ya, yb, v_edges, h_edges) {
+        return None;
       }
 
       // Midpoint test
       let mx2 = xa as i64 + xb as i64;
       let my2 = ya as i64 + yb as i64;
 
-      if !point_in_poly_doubled(mx2, my2, &v_edges, &h_edges) {
-        continue;
+      if !point_in_poly_doubled(mx2, my2, v_edges, h_edges) {
+        return None;
       }
 
       let dx = (xb - xa + 1) as u64;
       let dy = (yb - ya + 1) as u64;
-      let area = dx * dy;
+      Some(dx * dy)
+    }
+  }
 
-      if area > max_area {
-        max_area = area;
+  impl<const N: usize> Resumable for Part2<N> {
+    type Answer = u64;
+
+    fn step(&mut self, budget: u64) -> Poll<u64> {
+      for _ in 0..budget.max(1) {
+        let Some((i, j)) = self.pairs.next() else {
+          return Poll::Ready(self.max_area);
+        };
+
+        if let Some(area) = self.area(i, j) {
+          self.max_area = self.max_area.max(area);
+        }
       }
+
+      Poll::Pending
     }
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day9;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day9 {
+  const DAY: u8 = 9;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Problem<'_>, Error> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, Error> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p),
+    };
 
-    max_area
+    Ok(answer.into())
   }
 }
 
@@ -294,10 +428,10 @@ mod tests {
   use super::prelude::*;
 
   #[test]
-  #[cfg(not(feature = "part2"))]
+  #[cfg(feature = "part1")]
   fn test_part1() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part1(&problem);
 
     assert_eq!(result, 50);
@@ -307,8 +441,51 @@ mod tests {
   #[cfg(feature = "part2")]
   fn test_part2() {
     let input = include_str!("../sample.txt");
-    let problem = parse(input);
+    let problem = parse(input).unwrap();
     let result = part2(&problem);
     assert_eq!(result, 24);
   }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{Error, ParseError, Reason};
+
+    let err = parse("7,1\n11,1\n11;7\n").err().unwrap();
+    let expected =
+      ParseError { line: 3, column: 5, reason: Reason::Expected(',') };
+    assert_eq!(err, Error::Parse(expected));
+
+    // negative or huge coordinates would overflow the areas
+    let err = parse("0,-2147483648\n0,0\n").err().unwrap();
+    let expected =
+      ParseError { line: 1, column: 3, reason: Reason::OutOfRange };
+    assert_eq!(err, Error::Parse(expected));
+    assert!(parse("1073741825,0\n").is_err());
+  }
+
+  #[test]
+  fn test_capacity_exceeded() {
+    extern crate std;
+    use aoc_core::Error;
+
+    use super::MAX_TILES;
+
+    let input = "7,1\n".repeat(MAX_TILES + 1);
+    let err = parse(&input).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded {
+        what: "red tiles",
+        limit: MAX_TILES,
+        needed: MAX_TILES + 1,
+      }
+    );
+
+    let sample = include_str!("../sample.txt");
+    let err = parse_with_capacity::<4>(sample).err().unwrap();
+    assert_eq!(
+      err,
+      Error::CapacityExceeded { what: "red tiles", limit: 4, needed: 8 }
+    );
+  }
 }
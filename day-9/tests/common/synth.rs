@@ -0,0 +1,62 @@
+use fastrand::Rng;
+
+/// A loop of `k` red tiles, `k` a positive multiple of four, shaped like the
+/// puzzle's: strips of varying height side by side, each straddling a
+/// common line, with every red tile a corner. Rows and columns are spread
+/// out so that no two corners pinch the loop.
+pub fn tiles(seed: u64, k: usize) -> String {
+  assert!(
+    k >= 4 && k.is_multiple_of(4),
+    "{k} is not a positive multiple of four"
+  );
+  let mut rng = Rng::with_seed(seed);
+  let strips = k / 4;
+
+  // distinct heights for neighbours keep every corner a real turn
+  let mut heights: Vec<(i64, i64)> = Vec::with_capacity(strips);
+  for i in 0..strips {
+    let (top, bottom) = loop {
+      let h = (rng.i64(1..=k as i64), rng.i64(1..=k as i64));
+      match i.checked_sub(1).map(|p| heights[p]) {
+        Some((t, b)) if t == h.0 || b == h.1 => continue,
+        _ => break h,
+      }
+    };
+    heights.push((top, bottom));
+  }
+
+  let mut corners = Vec::with_capacity(k);
+  for (i, &(top, _)) in heights.iter().enumerate() {
+    corners.extend([(i, top), (i + 1, top)]);
+  }
+  for (i, &(_, bottom)) in heights.iter().enumerate().rev() {
+    corners.extend([(i + 1, -bottom), (i, -bottom)]);
+  }
+
+  let mut ys: Vec<i64> = corners.iter().map(|&(_, y)| y).collect();
+  ys.sort_unstable();
+  ys.dedup();
+  let xs = positions(&mut rng, strips + 1);
+  let ys_at = positions(&mut rng, ys.len());
+
+  let lines: Vec<String> = corners
+    .iter()
+    .map(|&(x, y)| {
+      let y = ys_at[ys.binary_search(&y).unwrap()];
+      format!("{},{y}", xs[x])
+    })
+    .collect();
+  lines.join("\n")
+}
+
+/// `n` increasing positions at least two apart, like the puzzle's spread.
+fn positions(rng: &mut Rng, n: usize) -> Vec<u64> {
+  let gap = (100_000 / n as u64).max(3);
+  let mut at = rng.u64(..gap);
+  (0..n)
+    .map(|_| {
+      at += rng.u64(2..gap);
+      at
+    })
+    .collect()
+}
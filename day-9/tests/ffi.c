@@ -0,0 +1,26 @@
+/* Day 9's C ABI on the sample, run by `tests/ffi.rs`. */
+#include "check.h"
+#include "day_9.h"
+
+int main(int argc, char **argv) {
+  CHECK(argc == 2);
+  size_t len;
+  char *sample = read_input(argv[1], &len);
+  const uint8_t *text = (const uint8_t *)sample;
+  uint64_t answer = 0;
+
+  CHECK(aoc_day9_part1(text, len, &answer) == AOC_OK);
+  CHECK(answer == 50u);
+  CHECK(aoc_day9_part2(text, len, &answer) == AOC_OK);
+  CHECK(answer == 24u);
+
+  // failures leave the answer alone
+  CHECK(aoc_day9_part1((const uint8_t *)"x\n", 2, &answer) == AOC_PARSE);
+  CHECK(aoc_day9_part1((const uint8_t *)"\xff", 1, &answer) ==
+        AOC_INVALID_UTF8);
+  CHECK(aoc_day9_part1(NULL, 1, &answer) == AOC_NULL_POINTER);
+  CHECK(answer == 24u);
+
+  free(sample);
+  return 0;
+}
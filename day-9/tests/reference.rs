@@ -0,0 +1,167 @@
+// Differential tests: random rectilinear loops go through the `Solver` and
+// through a naive std reference that colours every tile and checks every
+// rectangle tile by tile.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Part, Solver};
+use day_9::Day9;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  pub fn red_tiles(input: &str) -> Vec<(i64, i64)> {
+    input
+      .lines()
+      .map(|l| {
+        let (x, y) = l.split_once(',').unwrap();
+        (x.parse().unwrap(), y.parse().unwrap())
+      })
+      .collect()
+  }
+
+  fn area((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> u64 {
+    ((x1 - x2).abs() + 1) as u64 * ((y1 - y2).abs() + 1) as u64
+  }
+
+  pub fn part1(input: &str) -> u64 {
+    let red = red_tiles(input);
+    let mut best = 0;
+    for &a in &red {
+      for &b in &red {
+        best = best.max(area(a, b));
+      }
+    }
+    best
+  }
+
+  /// Whether the tile at `(x, y)` is red or green: on the loop, or inside it
+  /// by counting the loop's vertical edges to its right.
+  fn coloured(red: &[(i64, i64)], x: i64, y: i64) -> bool {
+    let edges = red.iter().zip(red.iter().cycle().skip(1));
+    let mut crossings = 0;
+    for (&(x1, y1), &(x2, y2)) in edges {
+      let (xs, ys) = (x1.min(x2)..=x1.max(x2), y1.min(y2)..=y1.max(y2));
+      if xs.contains(&x) && ys.contains(&y) {
+        return true;
+      }
+      if x1 == x2 && x1 > x && y1.min(y2) <= y && y < y1.max(y2) {
+        crossings += 1;
+      }
+    }
+    crossings % 2 == 1
+  }
+
+  pub fn part2(input: &str) -> u64 {
+    let red = red_tiles(input);
+    let mut best = 0;
+    for &(x1, y1) in &red {
+      for &(x2, y2) in &red {
+        let xs = x1.min(x2)..=x1.max(x2);
+        let ys = y1.min(y2)..=y1.max(y2);
+        let filled =
+          xs.clone().all(|x| ys.clone().all(|y| coloured(&red, x, y)));
+        if filled {
+          best = best.max(area((x1, y1), (x2, y2)));
+        }
+      }
+    }
+    best
+  }
+}
+
+/// Maps ranks `0, 1, 2, …` to increasing positions at least two apart.
+/// Adjacent rows or columns would pinch the loop without a tile between
+/// them, which the puzzle's well-spread corners never do.
+fn spread(gaps: &[i64], rank: usize) -> i64 {
+  gaps[..rank].iter().sum()
+}
+
+/// Loops monotone along one axis, as in the puzzle: strips of varying
+/// height side by side, each straddling a common line. Every red tile is a
+/// corner; the loop may start anywhere, run either way, and be transposed.
+fn loops() -> impl Strategy<Value = String> {
+  let strip = (1..=5i64, 1..=5i64);
+  (
+    prop::collection::vec(strip, 1..=6),
+    prop::collection::vec(2..=4i64, 16),
+    prop::collection::vec(2..=4i64, 16),
+    (
+      any::<prop::sample::Index>(),
+      prop::bool::ANY,
+      prop::bool::ANY,
+    ),
+  )
+    .prop_map(
+      |(mut strips, x_gaps, y_gaps, (start, reverse, transpose))| {
+        for i in 1..strips.len() {
+          let (top, bottom) = strips[i - 1];
+          if strips[i].0 == top {
+            strips[i].0 += 1;
+          }
+          if strips[i].1 == bottom {
+            strips[i].1 += 1;
+          }
+        }
+
+        let mut corners = Vec::new();
+        for (i, &(top, _)) in strips.iter().enumerate() {
+          corners.extend([(i, top), (i + 1, top)]);
+        }
+        for (i, &(_, bottom)) in strips.iter().enumerate().rev() {
+          corners.extend([(i + 1, -bottom), (i, -bottom)]);
+        }
+
+        let mut ys: Vec<i64> = corners.iter().map(|&(_, y)| y).collect();
+        ys.sort_unstable();
+        ys.dedup();
+        let mut red: Vec<(i64, i64)> = corners
+          .iter()
+          .map(|&(x, y)| {
+            let y = ys.binary_search(&y).unwrap();
+            (spread(&x_gaps, x), spread(&y_gaps, y))
+          })
+          .collect();
+
+        let start = start.index(red.len());
+        red.rotate_left(start);
+        if reverse {
+          red.reverse();
+        }
+        if transpose {
+          red.iter_mut().for_each(|(x, y)| std::mem::swap(x, y));
+        }
+        let lines: Vec<String> =
+          red.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        lines.join("\n")
+      },
+    )
+}
+
+fn solve(part: Part, input: &str) -> u64 {
+  let p = Day9::parse(input).unwrap();
+  u64::try_from(Day9::solve(part, &p, ()).unwrap()).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in loops()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input));
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in loops()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input));
+  }
+
+  // the reference's part 2 visits every tile, too many at the puzzle's
+  // spread; a loop's largest filled rectangle is at most its largest one
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>(), k in 1..=8usize) {
+    let input = synth::tiles(seed, 4 * k);
+    let part1 = solve(Part::One, &input);
+    prop_assert_eq!(part1, naive::part1(&input));
+    prop_assert!((1..=part1).contains(&solve(Part::Two, &input)));
+  }
+}
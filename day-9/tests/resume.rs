@@ -0,0 +1,43 @@
+// Part 2 stepped with small budgets must reach the one-shot answer, on the
+// sample and on seeded synth tiles.
+#![cfg(feature = "part2")]
+
+use aoc_core::resume::{Poll, Resumable};
+use day_9::prelude::{Part2, parse, part2};
+
+mod common;
+use common::synth;
+
+/// The answer, and how many steps of `budget` units it took.
+fn stepped(input: &str, budget: u64) -> (u64, usize) {
+  let mut steps = Part2::new(&parse(input).unwrap());
+  let mut calls = 1;
+  loop {
+    match steps.step(budget) {
+      Poll::Ready(answer) => return (answer, calls),
+      Poll::Pending => calls += 1,
+    }
+  }
+}
+
+#[test]
+fn test_matches_part2() {
+  let mut inputs = vec![include_str!("../sample.txt").to_string()];
+  inputs.extend((0..4).map(|seed| synth::tiles(seed, 8)));
+
+  for input in &inputs {
+    let whole = part2(&parse(input).unwrap());
+    for budget in [0, 1, 7, 64, u64::MAX] {
+      assert_eq!(stepped(input, budget).0, whole, "budget {budget}");
+    }
+  }
+}
+
+#[test]
+fn test_budget_bounds_work() {
+  let input = include_str!("../sample.txt");
+
+  // one call per pair of the 8 tiles, and one to find none are left
+  assert_eq!(stepped(input, 1).1, 8 * 7 / 2 + 1);
+  assert_eq!(stepped(input, u64::MAX).1, 1);
+}
@@ -0,0 +1,20 @@
+// Trace events tell the same story as the answers they lead to.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::trace::Collect;
+use day_9::prelude::*;
+
+const INPUT: &str = include_str!("../sample.txt");
+
+#[test]
+fn test_part1_trace() {
+  let p = parse(INPUT).unwrap();
+  let mut sink = Collect::default();
+
+  assert_eq!(part1_traced(&p, &mut sink), part1(&p));
+  let points: Vec<String> = INPUT
+    .lines()
+    .map(|l| format!("point ({})", l.replace(',', ", ")))
+    .collect();
+  assert_eq!(sink.events, points);
+}
@@ -0,0 +1,191 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use aoc_core::{Error, ParseError, Reason};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+use heapless::Vec;
+use nom::{
+  IResult, Parser,
+  character::complete::{digit1, line_ending},
+  combinator::{map_res, opt},
+  multi::fold_many0,
+  sequence::terminated,
+};
+
+pub mod prelude {
+  pub use crate::{Day__N__, MAX_VALUES, Problem, parse};
+
+  #[cfg(feature = "part1")]
+  pub use crate::part1_impl::part1;
+
+  #[cfg(feature = "part2")]
+  pub use crate::part2_impl::part2;
+}
+
+// --------------------------
+// Data Model
+// --------------------------
+
+/// Most values a `Problem` holds; the real input's size decides this.
+pub const MAX_VALUES: usize = 256;
+
+pub struct Problem {
+  pub values: Vec<u64, MAX_VALUES>,
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+// Replace with your Day __N__ grammar; this one reads a number per line.
+fn value(i: &str) -> IResult<&str, u64> {
+  map_res(digit1, str::parse).parse(i)
+}
+
+/// Keeps the first `MAX_VALUES` values, and counts every value so the
+/// caller can reject inputs with more.
+fn values(i: &str) -> IResult<&str, (Vec<u64, MAX_VALUES>, usize)> {
+  fold_many0(
+    terminated(value, opt(line_ending)),
+    || (Vec::<u64, MAX_VALUES>::new(), 0),
+    |(mut acc, count), v| {
+      let _ = acc.push(v);
+      (acc, count + 1)
+    },
+  )
+  .parse(i)
+}
+
+/// Points a nom error at the byte nom stopped on.
+fn located(input: &str, err: nom::Err<nom::error::Error<&str>>) -> ParseError {
+  use nom::error::ErrorKind;
+
+  let e = match err {
+    nom::Err::Error(e) | nom::Err::Failure(e) => e,
+    nom::Err::Incomplete(_) => {
+      return ParseError::at_offset(input, input.len(), Reason::UnexpectedEnd);
+    }
+  };
+
+  let reason = match e.code {
+    ErrorKind::Digit => Reason::ExpectedNumber,
+    ErrorKind::MapRes => Reason::OutOfRange,
+    _ => match e.input.chars().next() {
+      Some(c) => Reason::Unexpected(c),
+      None => Reason::UnexpectedEnd,
+    },
+  };
+
+  ParseError::at(input, e.input, reason)
+}
+
+pub fn parse(input: &str) -> Result<Problem, Error> {
+  let (rest, (values, count)) = values(input).map_err(|e| located(input, e))?;
+
+  // allow trailing whitespace; anything else is a line that failed
+  if !rest.trim().is_empty() {
+    let reason = Reason::Malformed("expected a number per line");
+    let err = match value(rest) {
+      Err(e) => located(input, e),
+      Ok(_) => ParseError::at(input, rest, reason),
+    };
+    return Err(err.into());
+  }
+  Error::check_capacity("values", MAX_VALUES, count)?;
+
+  Ok(Problem { values })
+}
+
+// --------------------------
+// Solver — Part 1
+// --------------------------
+#[cfg(feature = "part1")]
+mod part1_impl {
+  use super::Problem;
+
+  pub fn part1(p: &Problem) -> u64 {
+    // Replace with your Day __N__ logic
+    p.values.iter().sum()
+  }
+}
+
+// --------------------------
+// Solver — Part 2
+// --------------------------
+
+#[cfg(feature = "part2")]
+mod part2_impl {
+  use super::Problem;
+
+  pub fn part2(p: &Problem) -> u64 {
+    // Replace with your Day __N__ Part 2 logic
+    p.values.iter().copied().max().unwrap_or(0)
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day__N__;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day__N__ {
+  const DAY: u8 = __N__;
+
+  type Input<'a> = Problem;
+  type Scratch<'a> = ();
+  type Error = Error;
+
+  fn parse(input: &str) -> Result<Problem, Error> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, Error> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p),
+    };
+    Ok(answer.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::prelude::*;
+
+  #[test]
+  #[cfg(feature = "part1")]
+  #[ignore = "fill in the sample answer"]
+  fn test_part1() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap();
+    let result = part1(&problem);
+
+    assert_eq!(result, 0);
+  }
+
+  #[test]
+  #[cfg(feature = "part2")]
+  #[ignore = "fill in the sample answer"]
+  fn test_part2() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap();
+    let result = part2(&problem);
+
+    assert_eq!(result, 0);
+  }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{Error, ParseError, Reason};
+
+    let err = parse("12\n3x\n").err();
+    let expected =
+      ParseError { line: 2, column: 2, reason: Reason::ExpectedNumber };
+    assert_eq!(err, Some(Error::Parse(expected)));
+  }
+}
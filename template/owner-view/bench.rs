@@ -0,0 +1,30 @@
+use std::sync::LazyLock;
+
+use __DAY__::prelude::*;
+use divan::black_box;
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+
+static INPUT: LazyLock<String> = LazyLock::new(aoc_core::input::for_bench);
+
+#[cfg(feature = "part1")]
+#[divan::bench]
+fn bench_part1() {
+  let mut problem_data = std_parse::parse(black_box(INPUT.as_str())).unwrap();
+  let mut p = problem_data.as_problem();
+  black_box(part1(&mut p));
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench]
+fn bench_part2() {
+  let mut problem_data = std_parse::parse(black_box(INPUT.as_str())).unwrap();
+  let mut p = problem_data.as_problem();
+  black_box(part2(&mut p));
+}
+
+fn main() {
+  divan::main();
+}
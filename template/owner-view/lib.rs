@@ -0,0 +1,223 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use aoc_core::{Error, ParseError, Reason, Workspace};
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::workspace::Carve;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+pub mod prelude {
+  pub use crate::{
+    Day__N__, Problem, Puzzle, Requirements, Scratch, parse, requirements,
+  };
+
+  #[cfg(feature = "part1")]
+  pub use crate::part1_impl::part1;
+
+  #[cfg(feature = "part2")]
+  pub use crate::part2_impl::part2;
+}
+
+// --------------------------
+// Data Model
+// --------------------------
+
+/// The solver's view: nothing but the input and borrowed working memory.
+pub struct Problem<'a> {
+  pub input: &'a str,
+  pub values: &'a mut [u64],
+}
+
+/// The checked input, measured but not yet unpacked.
+pub struct Puzzle<'a> {
+  pub input: &'a str,
+  pub lines: usize,
+}
+
+/// Caller-owned memory backing the `Problem` view built from a `Puzzle`;
+/// `values` needs a slot per line.
+#[derive(Default)]
+pub struct Scratch<'a> {
+  pub values: &'a mut [u64],
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+/// Checks the input holds only printable ASCII lines and counts them.
+pub fn parse(input: &str) -> Result<Puzzle<'_>, ParseError> {
+  for line in input.lines() {
+    if let Some((i, c)) = line.char_indices().find(|&(_, c)| !c.is_ascii()) {
+      return Err(ParseError::at(input, &line[i..], Reason::Unexpected(c)));
+    }
+  }
+
+  Ok(Puzzle { input, lines: input.lines().count() })
+}
+
+impl<'a> Puzzle<'a> {
+  /// Lends `scratch` out as a `Problem` over this input.
+  pub fn as_problem<'s>(&self, scratch: Scratch<'s>) -> Problem<'s>
+  where
+    'a: 's,
+  {
+    Problem { input: self.input, values: &mut scratch.values[..self.lines] }
+  }
+}
+
+// --------------------------
+// Scratch
+// --------------------------
+
+/// Working memory for one input: a value per line, and `bytes` of workspace
+/// in all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Requirements {
+  pub lines: usize,
+  pub bytes: usize,
+}
+
+pub fn requirements(p: &Puzzle) -> Requirements {
+  let bytes = Workspace::size_of::<u64>(p.lines);
+  Requirements { lines: p.lines, bytes }
+}
+
+impl Requirements {
+  /// Cuts a `Scratch` out of `ws`, which needs `bytes` left.
+  pub fn carve<'a>(
+    &self,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    Ok(Scratch { values: ws.take(self.lines, || 0)? })
+  }
+}
+
+// --------------------------
+// Solver — Part 1
+// --------------------------
+#[cfg(feature = "part1")]
+mod part1_impl {
+  use super::Problem;
+
+  pub fn part1(p: &mut Problem) -> u64 {
+    // Replace with your Day __N__ logic
+    for (value, line) in p.values.iter_mut().zip(p.input.lines()) {
+      *value = line.len() as u64;
+    }
+    p.values.iter().sum()
+  }
+}
+
+// --------------------------
+// Solver — Part 2
+// --------------------------
+
+#[cfg(feature = "part2")]
+mod part2_impl {
+  use super::Problem;
+
+  pub fn part2(p: &mut Problem) -> u64 {
+    // Replace with your Day __N__ Part 2 logic
+    for (value, line) in p.values.iter_mut().zip(p.input.lines()) {
+      *value = line.len() as u64;
+    }
+    p.values.iter().copied().max().unwrap_or(0)
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day__N__;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day__N__ {
+  const DAY: u8 = __N__;
+
+  type Input<'a> = Puzzle<'a>;
+  type Scratch<'a> = Scratch<'a>;
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Puzzle<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve<'a>(
+    part: Part,
+    puzzle: &Puzzle<'a>,
+    scratch: Scratch<'a>,
+  ) -> Result<Answer, ParseError> {
+    let mut p = puzzle.as_problem(scratch);
+
+    let answer = match part {
+      Part::One => part1_impl::part1(&mut p),
+      Part::Two => part2_impl::part2(&mut p),
+    };
+    Ok(answer.into())
+  }
+}
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Carve for Day__N__ {
+  fn bytes(puzzle: &Puzzle) -> usize {
+    requirements(puzzle).bytes
+  }
+
+  fn carve<'a>(
+    puzzle: &Puzzle,
+    ws: &mut Workspace<'a>,
+  ) -> Result<Scratch<'a>, Error> {
+    requirements(puzzle).carve(ws)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  mod std_parse {
+    include!("../src/std_parse.rs");
+  }
+  use super::prelude::*;
+  use std_parse::parse;
+
+  #[test]
+  #[cfg(feature = "part1")]
+  #[ignore = "fill in the sample answer"]
+  fn test_part1() {
+    let input = include_str!("../sample.txt");
+    let mut problem_data = parse(input).unwrap();
+    let mut problem = problem_data.as_problem();
+
+    let result = part1(&mut problem);
+
+    assert_eq!(result, 0);
+  }
+
+  #[test]
+  #[cfg(feature = "part2")]
+  #[ignore = "fill in the sample answer"]
+  fn test_part2() {
+    let input = include_str!("../sample.txt");
+    let mut problem_data = parse(input).unwrap();
+    let mut problem = problem_data.as_problem();
+
+    let result = part2(&mut problem);
+
+    assert_eq!(result, 0);
+  }
+
+  #[test]
+  fn test_parse_error() {
+    use aoc_core::{ParseError, Reason};
+
+    let err = crate::parse("ab\ncé\n").err().unwrap();
+    assert_eq!(
+      err,
+      ParseError { line: 2, column: 2, reason: Reason::Unexpected('é') }
+    );
+  }
+}
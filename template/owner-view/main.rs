@@ -0,0 +1,19 @@
+use aoc_core::ParseError;
+use __DAY__::prelude::*;
+
+mod std_parse;
+
+
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
+  let mut problem_data = std_parse::parse(&input)?;
+  let mut problem = problem_data.as_problem();
+
+  let p1 = part1(&mut problem);
+  println!("Part 1: {p1}");
+
+  let p2 = part2(&mut problem);
+  println!("Part 2: {p2}");
+
+  Ok(())
+}
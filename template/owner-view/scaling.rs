@@ -0,0 +1,44 @@
+// Complexity curves: every size gets one seeded input from
+// `tests/common/synth.rs`, which each iteration parses and solves as
+// `bench.rs` does.
+use __DAY__::prelude::*;
+use divan::{Bencher, black_box};
+
+mod std_parse {
+  include!("../src/std_parse.rs");
+}
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::synth;
+
+const SEED: u64 = 2025;
+
+/// Lines; replace with the sizes the Day __N__ solution scales with.
+const SIZES: &[usize] = &[__SIZES__];
+
+#[cfg(feature = "part1")]
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, n: usize) {
+  let input = synth::lines(SEED, n);
+  bencher.bench(|| {
+    let mut problem_data = std_parse::parse(black_box(&input)).unwrap();
+    let mut p = problem_data.as_problem();
+    black_box(part1(&mut p));
+  });
+}
+
+#[cfg(feature = "part2")]
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, n: usize) {
+  let input = synth::lines(SEED, n);
+  bencher.bench(|| {
+    let mut problem_data = std_parse::parse(black_box(&input)).unwrap();
+    let mut p = problem_data.as_problem();
+    black_box(part2(&mut p));
+  });
+}
+
+fn main() {
+  divan::main();
+}
@@ -0,0 +1,28 @@
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use aoc_core::ParseError;
+
+use crate::{Problem, Puzzle, Scratch};
+
+
+/// Owns the buffers a `Problem` borrows; the std side of `Scratch`.
+pub struct ProblemData<'a> {
+  puzzle: Puzzle<'a>,
+  values: Vec<u64>,
+}
+
+impl ProblemData<'_> {
+  pub fn as_problem(&mut self) -> Problem<'_> {
+    self.puzzle.as_problem(Scratch { values: &mut self.values })
+  }
+}
+
+pub fn parse(input: &str) -> Result<ProblemData<'_>, ParseError> {
+  let puzzle = crate::parse(input)?;
+  let values = vec![0; puzzle.lines];
+
+  Ok(ProblemData { puzzle, values })
+}
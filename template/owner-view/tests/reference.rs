@@ -0,0 +1,59 @@
+// Differential tests: random inputs go through the `Solver` and through a
+// naive std reference that must agree with it.
+#![cfg(all(feature = "part1", feature = "part2"))]
+
+use aoc_core::{Answer, Part, Solver, Workspace};
+use __DAY__::prelude::*;
+use proptest::prelude::*;
+
+mod common;
+use common::synth;
+
+mod naive {
+  fn lengths(input: &str) -> impl Iterator<Item = u64> + '_ {
+    input.lines().map(|l| l.chars().count() as u64)
+  }
+
+  // Replace with the simplest Day __N__ solution you can trust
+  pub fn part1(input: &str) -> u64 {
+    lengths(input).sum()
+  }
+
+  pub fn part2(input: &str) -> u64 {
+    lengths(input).max().unwrap_or(0)
+  }
+}
+
+/// Random valid inputs; replace with the shape of the Day __N__ input.
+fn inputs() -> impl Strategy<Value = String> {
+  prop::collection::vec("[a-z0-9 ]{0,20}", 0..20)
+    .prop_map(|lines| lines.join("\n"))
+}
+
+fn solve(part: Part, input: &str) -> Answer {
+  let p = Day__N__::parse(input).unwrap();
+  let needs = requirements(&p);
+  let mut block = vec![0u8; needs.bytes];
+  let scratch = needs.carve(&mut Workspace::new(&mut block)).unwrap();
+
+  Day__N__::solve(part, &p, scratch).unwrap()
+}
+
+proptest! {
+  #[test]
+  fn test_part1_matches_reference(input in inputs()) {
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+  }
+
+  #[test]
+  fn test_part2_matches_reference(input in inputs()) {
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+
+  #[test]
+  fn test_synth_matches_reference(seed in any::<u64>(), n in 0..20usize) {
+    let input = synth::lines(seed, n);
+    prop_assert_eq!(solve(Part::One, &input), naive::part1(&input).into());
+    prop_assert_eq!(solve(Part::Two, &input), naive::part2(&input).into());
+  }
+}
@@ -0,0 +1,115 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use aoc_core::ParseError;
+#[cfg(all(feature = "part1", feature = "part2"))]
+use aoc_core::{Answer, Part, Solver};
+
+pub mod prelude {
+  pub use crate::{Day__N__, Problem, parse};
+
+  #[cfg(feature = "part1")]
+  pub use crate::part1_impl::part1;
+
+  #[cfg(feature = "part2")]
+  pub use crate::part2_impl::part2;
+}
+
+// --------------------------
+// Data Model
+// --------------------------
+
+pub struct Problem<'a> {
+  pub input: &'a str,
+}
+
+// --------------------------
+// Parse
+// --------------------------
+
+pub fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+  Ok(Problem { input })
+}
+
+// --------------------------
+// Solver — Part 1
+// --------------------------
+#[cfg(feature = "part1")]
+mod part1_impl {
+  use super::Problem;
+
+  pub fn part1(p: &Problem) -> u64 {
+    // Replace with your Day __N__ logic
+    p.input.lines().count() as u64
+  }
+}
+
+// --------------------------
+// Solver — Part 2
+// --------------------------
+
+#[cfg(feature = "part2")]
+mod part2_impl {
+  use super::Problem;
+
+  pub fn part2(p: &Problem) -> u64 {
+    // Replace with your Day __N__ Part 2 logic
+    p.input.len() as u64
+  }
+}
+
+// --------------------------
+// Solver
+// --------------------------
+
+pub struct Day__N__;
+
+#[cfg(all(feature = "part1", feature = "part2"))]
+impl Solver for Day__N__ {
+  const DAY: u8 = __N__;
+
+  type Input<'a> = Problem<'a>;
+  type Scratch<'a> = ();
+  type Error = ParseError;
+
+  fn parse(input: &str) -> Result<Problem<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn solve(part: Part, p: &Problem, _: ()) -> Result<Answer, ParseError> {
+    let answer = match part {
+      Part::One => part1_impl::part1(p),
+      Part::Two => part2_impl::part2(p),
+    };
+    Ok(answer.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::prelude::*;
+
+  #[test]
+  #[cfg(feature = "part1")]
+  #[ignore = "fill in the sample answer"]
+  fn test_part1() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap();
+    let result = part1(&problem);
+
+    assert_eq!(result, 0);
+  }
+
+  #[test]
+  #[cfg(feature = "part2")]
+  #[ignore = "fill in the sample answer"]
+  fn test_part2() {
+    let input = include_str!("../sample.txt");
+    let problem = parse(input).unwrap();
+    let result = part2(&problem);
+
+    assert_eq!(result, 0);
+  }
+}
@@ -0,0 +1,16 @@
+use aoc_core::ParseError;
+use __DAY__::prelude::*;
+
+
+fn main() -> Result<(), ParseError> {
+  let input = aoc_core::input::from_arg(1);
+  let problem = parse(&input)?;
+
+  let p1 = part1(&problem);
+  println!("Part 1: {p1}");
+
+  let p2 = part2(&problem);
+  println!("Part 2: {p2}");
+
+  Ok(())
+}
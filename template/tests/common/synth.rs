@@ -0,0 +1,10 @@
+use fastrand::Rng;
+
+/// `n` lines of one number each; replace with the shape of the Day __N__
+/// input, seeded and valid at every size.
+pub fn lines(seed: u64, n: usize) -> String {
+  let mut rng = Rng::with_seed(seed);
+  let lines: Vec<String> =
+    (0..n).map(|_| rng.u64(..1_000_000).to_string()).collect();
+  lines.join("\n")
+}
@@ -0,0 +1,342 @@
+//! Divan output as data: parses the table each `benches/bench.rs` prints,
+//! stores runs as TSV (the committed `benches/baseline.tsv` and
+//! `target/bench/day-N.tsv`), and compares two sets of runs.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const COLUMNS: &str =
+  "bench\trun\tfastest_ns\tslowest_ns\tmedian_ns\tmean_ns\tsamples\titers";
+
+/// One bench's row from one divan run. Times are in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+  pub bench: String,
+  pub run: u32,
+  pub fastest: f64,
+  pub slowest: f64,
+  pub median: f64,
+  pub mean: f64,
+  pub samples: u32,
+  pub iters: u32,
+}
+
+/// Every row of several runs of one day's benches, tied to the input they
+/// were measured on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Results {
+  /// `fnv1a:` hash of the day's `input.txt`, or `none`.
+  pub input: String,
+  pub rows: Vec<Row>,
+}
+
+/// How one bench's median moved between a baseline and a new set of runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verdict {
+  pub bench: String,
+  /// Median over runs of each run's median, in nanoseconds.
+  pub before: f64,
+  pub after: f64,
+  /// One-sided Mann-Whitney p-value for "after is slower".
+  pub p: f64,
+  pub regressed: bool,
+}
+
+/// Keeps divan's header and per-bench rows, dropping everything else
+/// `cargo bench` prints.
+pub fn bench_rows(output: &str) -> Vec<String> {
+  output
+    .lines()
+    .filter(|l| l.starts_with("bench ") || bench_name(l).is_some())
+    .map(|l| l.trim_end().to_string())
+    .collect()
+}
+
+/// The bench function named by a divan row or `--list` entry.
+pub fn bench_name(line: &str) -> Option<&str> {
+  let (_, rest) = line.split_once("─ ")?;
+  let name = rest.split_whitespace().next()?;
+  name.starts_with("bench_").then_some(name)
+}
+
+/// Parses the bench rows out of one `cargo bench` run.
+pub fn parse_run(output: &str, run: u32) -> Result<Vec<Row>, String> {
+  output
+    .lines()
+    .filter(|l| bench_name(l).is_some())
+    .map(|line| {
+      let bad = || format!("unexpected divan row `{line}`");
+      let cells: Vec<&str> = line.split('│').map(str::trim).collect();
+      let [first, slowest, median, mean, samples, iters] = cells[..] else {
+        return Err(bad());
+      };
+      let bench = bench_name(first).ok_or_else(bad)?;
+      let (_, fastest) = first.split_once(bench).ok_or_else(bad)?;
+
+      Ok(Row {
+        bench: bench.to_string(),
+        run,
+        fastest: nanos(fastest).ok_or_else(bad)?,
+        slowest: nanos(slowest).ok_or_else(bad)?,
+        median: nanos(median).ok_or_else(bad)?,
+        mean: nanos(mean).ok_or_else(bad)?,
+        samples: samples.parse().map_err(|_| bad())?,
+        iters: iters.parse().map_err(|_| bad())?,
+      })
+    })
+    .collect()
+}
+
+/// Converts a divan duration such as `1.161 µs` to nanoseconds, rounded to
+/// the tenth of a nanosecond divan prints at most.
+fn nanos(cell: &str) -> Option<f64> {
+  let (value, unit) = cell.trim().split_once(' ')?;
+  let scale = match unit {
+    "ns" => 1.0,
+    "µs" | "us" => 1e3,
+    "ms" => 1e6,
+    "s" => 1e9,
+    _ => return None,
+  };
+  Some((value.parse::<f64>().ok()? * scale * 10.0).round() / 10.0)
+}
+
+/// FNV-1a over the input bytes, so results from different inputs are
+/// never compared.
+pub fn input_hash(input: Option<&[u8]>) -> String {
+  let Some(bytes) = input else {
+    return "none".into();
+  };
+  let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |h, &b| {
+    (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+  });
+  format!("fnv1a:{hash:016x}")
+}
+
+impl Results {
+  pub fn to_tsv(&self) -> String {
+    let mut out = format!("# input: {}\n{COLUMNS}\n", self.input);
+    for r in &self.rows {
+      let _ = writeln!(
+        out,
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        r.bench,
+        r.run,
+        r.fastest,
+        r.slowest,
+        r.median,
+        r.mean,
+        r.samples,
+        r.iters
+      );
+    }
+    out
+  }
+
+  pub fn from_tsv(text: &str) -> Result<Self, String> {
+    let mut lines = text.lines();
+    let input = lines
+      .next()
+      .and_then(|l| l.strip_prefix("# input: "))
+      .ok_or("missing `# input:` line")?
+      .to_string();
+    if lines.next() != Some(COLUMNS) {
+      return Err("missing or unexpected column header".into());
+    }
+
+    let rows = lines
+      .enumerate()
+      .map(|(i, line)| {
+        let bad = || format!("line {}: malformed row", i + 3);
+        let cells: Vec<&str> = line.split('\t').collect();
+        let [bench, run, fastest, slowest, median, mean, samples, iters] =
+          cells[..]
+        else {
+          return Err(bad());
+        };
+        let time = |cell: &str| cell.parse::<f64>().map_err(|_| bad());
+        let count = |cell: &str| cell.parse::<u32>().map_err(|_| bad());
+
+        Ok(Row {
+          bench: bench.to_string(),
+          run: count(run)?,
+          fastest: time(fastest)?,
+          slowest: time(slowest)?,
+          median: time(median)?,
+          mean: time(mean)?,
+          samples: count(samples)?,
+          iters: count(iters)?,
+        })
+      })
+      .collect::<Result<_, _>>()?;
+
+    Ok(Results { input, rows })
+  }
+
+  /// Each bench's per-run medians.
+  fn medians(&self) -> BTreeMap<&str, Vec<f64>> {
+    let mut by_bench: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for r in &self.rows {
+      by_bench.entry(&r.bench).or_default().push(r.median);
+    }
+    by_bench
+  }
+}
+
+/// Compares the run medians of every bench present in both. A bench
+/// regressed when it is significantly slower (`p < alpha`) and its median
+/// grew by more than `threshold` (0.05 is 5%).
+pub fn compare(
+  baseline: &Results,
+  current: &Results,
+  alpha: f64,
+  threshold: f64,
+) -> Vec<Verdict> {
+  let before = baseline.medians();
+  current
+    .medians()
+    .into_iter()
+    .filter_map(|(bench, after)| {
+      let base = before.get(bench)?;
+      let (b, a) = (median(base), median(&after));
+      let p = mann_whitney_greater(&after, base);
+      Some(Verdict {
+        bench: bench.to_string(),
+        before: b,
+        after: a,
+        p,
+        regressed: p < alpha && a > b * (1.0 + threshold),
+      })
+    })
+    .collect()
+}
+
+fn median(values: &[f64]) -> f64 {
+  let mut v = values.to_vec();
+  v.sort_by(f64::total_cmp);
+  let mid = v.len() / 2;
+  if v.len().is_multiple_of(2) {
+    (v[mid - 1] + v[mid]) / 2.0
+  } else {
+    v[mid]
+  }
+}
+
+/// Exact one-sided Mann-Whitney U test: the probability, with both samples
+/// drawn from one distribution, that `xs` beats `ys` in at least as many
+/// pairs as observed. Ties count half and the statistic is rounded down,
+/// which only makes the test more conservative.
+fn mann_whitney_greater(xs: &[f64], ys: &[f64]) -> f64 {
+  let (n, m) = (xs.len(), ys.len());
+  if n == 0 || m == 0 {
+    return 1.0;
+  }
+
+  let twice_u: usize = xs
+    .iter()
+    .flat_map(|x| ys.iter().map(move |y| (x, y)))
+    .map(|(x, y)| match x.total_cmp(y) {
+      std::cmp::Ordering::Greater => 2,
+      std::cmp::Ordering::Equal => 1,
+      std::cmp::Ordering::Less => 0,
+    })
+    .sum();
+  let u = twice_u / 2;
+
+  // ways[i][j][k]: orderings of i xs and j ys where xs win k pairs. The
+  // largest element is either an x beating all j ys, or a y.
+  let mut ways = vec![vec![Vec::new(); m + 1]; n + 1];
+  for i in 0..=n {
+    for j in 0..=m {
+      ways[i][j] = if i == 0 || j == 0 {
+        vec![1.0]
+      } else {
+        (0..=i * j)
+          .map(|k| {
+            let x_top = k
+              .checked_sub(j)
+              .map_or(0.0, |k| ways[i - 1][j].get(k).copied().unwrap_or(0.0));
+            x_top + ways[i][j - 1].get(k).copied().unwrap_or(0.0)
+          })
+          .collect()
+      };
+    }
+  }
+
+  let counts = &ways[n][m];
+  let total: f64 = counts.iter().sum();
+  counts[u..].iter().sum::<f64>() / total
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const OUTPUT: &str = "\
+Timer precision: 20 ns
+bench           fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ bench_part1  603.4 ns      │ 21 µs         │ 919.9 ns      │ 1.093 µs      │ 100     │ 100
+╰─ bench_part2  262.5 ms      │ 1.2 s         │ 268.8 ms      │ 268.9 ms      │ 100     │ 100
+";
+
+  fn results(part2: &[f64]) -> Results {
+    let rows = part2
+      .iter()
+      .zip(1..)
+      .map(|(&median, run)| Row {
+        bench: "bench_part2".into(),
+        run,
+        fastest: median,
+        slowest: median,
+        median,
+        mean: median,
+        samples: 100,
+        iters: 100,
+      })
+      .collect();
+    Results { input: "none".into(), rows }
+  }
+
+  #[test]
+  fn test_parse_run() {
+    let rows = parse_run(OUTPUT, 3).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].bench, "bench_part1");
+    assert_eq!(rows[0].run, 3);
+    assert_eq!(rows[0].fastest, 603.4);
+    assert_eq!(rows[0].slowest, 21_000.0);
+    assert_eq!(rows[1].median, 268_800_000.0);
+    assert_eq!(rows[1].slowest, 1.2e9);
+    assert_eq!(bench_rows(OUTPUT).len(), 3);
+
+    assert!(parse_run("╰─ bench_part1  1 parsec │ 1 ns", 1).is_err());
+
+    let saved = Results { input: input_hash(Some(b"1\n")), rows };
+    assert_eq!(Results::from_tsv(&saved.to_tsv()).unwrap(), saved);
+    assert!(Results::from_tsv("bench\trun\n").is_err());
+  }
+
+  #[test]
+  fn test_compare() {
+    let base = results(&[100.0, 101.0, 99.0, 100.5, 100.2]);
+
+    let slower = results(&[110.0, 111.0, 109.0, 112.0, 110.5]);
+    let [v] = &compare(&base, &slower, 0.05, 0.05)[..] else {
+      panic!("expected one verdict");
+    };
+    assert!(v.regressed);
+    assert!((v.p - 1.0 / 252.0).abs() < 1e-12);
+    assert_eq!((v.before, v.after), (100.2, 110.5));
+
+    let noise = results(&[99.5, 102.0, 100.1, 98.9, 101.0]);
+    assert!(!compare(&base, &noise, 0.05, 0.05)[0].regressed);
+
+    // significant, but below the threshold
+    let nudged = results(&[101.5, 101.6, 101.7, 101.8, 101.9]);
+    let v = &compare(&base, &nudged, 0.05, 0.05)[0];
+    assert!(v.p < 0.05 && !v.regressed);
+
+    assert_eq!(mann_whitney_greater(&[1.0], &[1.0]), 1.0);
+    assert_eq!(mann_whitney_greater(&[2.0], &[1.0]), 0.5);
+  }
+}
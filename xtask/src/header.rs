@@ -0,0 +1,264 @@
+//! Writes the C headers for the `ffi` modules: `aoc-core/include/aoc.h`
+//! from the `Status` enum in `aoc-core/src/ffi.rs`, and
+//! `day-N/include/day_N.h` from each `pub unsafe extern "C" fn` in
+//! `day-N/src/ffi.rs`. Doc comments carry over as C comments.
+
+const CORE_SOURCE: &str = "aoc-core/src/ffi.rs";
+const EXPORT: &str = "pub unsafe extern \"C\" fn ";
+
+/// Where `day`'s exports are read from and its header written to, relative
+/// to the workspace root.
+pub fn day_paths(day: u8) -> (String, String) {
+  (
+    format!("day-{day}/src/ffi.rs"),
+    format!("day-{day}/include/day_{day}.h"),
+  )
+}
+
+/// The same for `aoc.h`.
+pub fn core_paths() -> (String, String) {
+  (CORE_SOURCE.into(), "aoc-core/include/aoc.h".into())
+}
+
+/// `aoc.h`, declaring `aoc_status` from the `Status` enum in `source`.
+pub fn core_header(source: &str) -> Result<String, String> {
+  let mut lines = source.lines().map(str::trim);
+  let mut doc = Vec::new();
+
+  for line in &mut lines {
+    if line == "pub enum Status {" {
+      break;
+    }
+    if !take_doc(&mut doc, line) && !line.starts_with("#[") {
+      doc.clear();
+    }
+  }
+  if doc.is_empty() {
+    return Err(format!("{CORE_SOURCE}: no documented `pub enum Status`"));
+  }
+
+  let mut out = preamble(CORE_SOURCE, "AOC_H");
+  out.push_str(&comment(&doc, ""));
+  out.push_str("typedef enum {\n");
+
+  let mut variant_doc = Vec::new();
+  for line in &mut lines {
+    if line == "}" {
+      out.push_str("} aoc_status;\n\n#endif\n");
+      return Ok(out);
+    }
+    if take_doc(&mut variant_doc, line) {
+      continue;
+    }
+    let (name, value) = line
+      .trim_end_matches(',')
+      .split_once(" = ")
+      .ok_or_else(|| format!("{CORE_SOURCE}: unexpected `{line}` in Status"))?;
+
+    out.push_str(&comment(&variant_doc, "  "));
+    out.push_str(&format!("  AOC_{} = {value},\n", screaming(name)));
+    variant_doc.clear();
+  }
+
+  Err(format!("{CORE_SOURCE}: unterminated `enum Status`"))
+}
+
+/// `day_N.h`, declaring every export in `source`, `day`'s `ffi.rs`.
+pub fn day_header(day: u8, source: &str) -> Result<String, String> {
+  let (path, _) = day_paths(day);
+  let mut out = preamble(&path, &format!("AOC_DAY_{day}_H"));
+  out.push_str("#include <stdbool.h>\n");
+  out.push_str("#include <stddef.h>\n");
+  out.push_str("#include <stdint.h>\n\n");
+  out.push_str("#include \"aoc.h\"\n");
+
+  let mut lines = source.lines().map(str::trim);
+  let mut doc = Vec::new();
+  let mut exports = 0;
+
+  while let Some(line) = lines.next() {
+    if take_doc(&mut doc, line) || line.starts_with("#[") {
+      continue;
+    }
+    let Some(rest) = line.strip_prefix(EXPORT) else {
+      doc.clear();
+      continue;
+    };
+
+    let name = rest.trim_end_matches('(');
+    let mut params = Vec::new();
+    for line in &mut lines {
+      if line.starts_with(')') {
+        if line != ") -> Status {" {
+          return Err(format!("{path}: `{name}` must return `Status`"));
+        }
+        break;
+      }
+      let (param, ty) = line
+        .trim_end_matches(',')
+        .split_once(": ")
+        .ok_or_else(|| format!("{path}: cannot read `{line}` in `{name}`"))?;
+      let ty = c_type(ty).ok_or_else(|| {
+        format!("{path}: `{name}` takes `{ty}`, which has no C spelling")
+      })?;
+      params.push(format!("{ty}{param}"));
+    }
+
+    out.push('\n');
+    out.push_str(&comment(&doc, ""));
+    out.push_str(&prototype(name, &params));
+    doc.clear();
+    exports += 1;
+  }
+
+  if exports == 0 {
+    return Err(format!("{path}: no `{}` functions", EXPORT.trim()));
+  }
+  out.push_str("\n#endif\n");
+  Ok(out)
+}
+
+// --------------------------
+// Helpers
+// --------------------------
+
+fn preamble(source: &str, guard: &str) -> String {
+  format!(
+    "/* Generated by `cargo xtask header` from {source}; do not edit. */\n\
+     #ifndef {guard}\n\
+     #define {guard}\n\n"
+  )
+}
+
+/// Adds `line` to `doc` if it is a `///` comment.
+fn take_doc(doc: &mut Vec<String>, line: &str) -> bool {
+  let Some(text) = line.strip_prefix("///") else {
+    return false;
+  };
+  let text = text.strip_prefix(' ').unwrap_or(text);
+  doc.push(match text {
+    "# Safety" => "Safety:".into(),
+    text => text.into(),
+  });
+  true
+}
+
+/// `doc` as a C comment at `indent`; one line stays one line.
+fn comment(doc: &[String], indent: &str) -> String {
+  match doc {
+    [] => String::new(),
+    [line] => format!("{indent}/* {line} */\n"),
+    lines => {
+      let mut out = format!("{indent}/*\n");
+      for line in lines {
+        match line.as_str() {
+          "" => out.push_str(&format!("{indent} *\n")),
+          line => out.push_str(&format!("{indent} * {line}\n")),
+        }
+      }
+      out.push_str(&format!("{indent} */\n"));
+      out
+    }
+  }
+}
+
+/// The C declaration, one parameter per line if one line is too wide.
+fn prototype(name: &str, params: &[String]) -> String {
+  let line = format!("aoc_status {name}({});\n", params.join(", "));
+  if line.len() <= 81 {
+    return line;
+  }
+  format!("aoc_status {name}(\n    {});\n", params.join(",\n    "))
+}
+
+/// The C type for a Rust parameter type, with any space before the name.
+fn c_type(ty: &str) -> Option<&'static str> {
+  Some(match ty {
+    "usize" => "size_t ",
+    "*const u8" => "const uint8_t *",
+    "*mut u8" => "uint8_t *",
+    "*mut u64" => "uint64_t *",
+    "*mut usize" => "size_t *",
+    "*mut bool" => "bool *",
+    _ => return None,
+  })
+}
+
+/// `NullPointer` as `NULL_POINTER`.
+fn screaming(name: &str) -> String {
+  let mut out = String::new();
+  for (i, c) in name.char_indices() {
+    if c.is_ascii_uppercase() && i > 0 {
+      out.push('_');
+    }
+    out.push(c.to_ascii_uppercase());
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_day_header() {
+    let source = "\
+use aoc_core::ffi::Status;
+
+/// The answer.
+///
+/// # Safety
+/// `input` must point to `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern \"C\" fn aoc_day1_part1(
+  input: *const u8,
+  len: usize,
+  answer: *mut u64,
+) -> Status {
+  todo!()
+}
+
+unsafe fn helper() {}
+";
+    let header = day_header(1, source).unwrap();
+    assert!(header.contains("#ifndef AOC_DAY_1_H\n"));
+    assert!(header.ends_with(
+      "\n/*\n * The answer.\n *\n * Safety:\n * `input` must point to `len` \
+       bytes.\n */\naoc_status aoc_day1_part1(const uint8_t *input, size_t \
+       len, uint64_t *answer);\n\n#endif\n"
+    ));
+
+    let wide = source.replace("len: usize", "len: f64");
+    let err = day_header(1, &wide).unwrap_err();
+    assert!(err.contains("`aoc_day1_part1` takes `f64`"), "{err}");
+    assert!(day_header(1, "fn main() {}").is_err());
+  }
+
+  #[test]
+  fn test_core_header() {
+    let source = "\
+/// What a call did.
+#[repr(C)]
+pub enum Status {
+  /// Done.
+  Ok = 0,
+  NullPointer = 1,
+}
+";
+    let header = core_header(source).unwrap();
+    assert!(header.ends_with(
+      "/* What a call did. */\ntypedef enum {\n  /* Done. */\n  AOC_OK = 0,\n  \
+       AOC_NULL_POINTER = 1,\n} aoc_status;\n\n#endif\n"
+    ));
+  }
+
+  #[test]
+  fn test_prototype_wraps() {
+    let params = vec!["size_t len".to_string(); 8];
+    assert_eq!(
+      prototype("f", &params[..2]),
+      "aoc_status f(size_t len, size_t len);\n"
+    );
+    assert!(prototype("f", &params).starts_with("aoc_status f(\n    size_t"));
+  }
+}
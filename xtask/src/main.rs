@@ -0,0 +1,602 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+
+use bench::{Results, Verdict};
+use readme::LibSize;
+use scaffold::Archetype;
+
+mod bench;
+mod header;
+mod readme;
+mod scaffold;
+
+
+const USAGE: &str = "\
+usage: cargo xtask readme [--day <1-12>] [--check]
+       cargo xtask bench [--day <1-12>] [--runs <n>] [--save]
+       cargo xtask compare [--day <1-12>] [--runs <n>] [--alpha <p>]
+                           [--threshold <fraction>]
+       cargo xtask new --day <1-25> [--archetype <name>] [--fetch <command>]
+       cargo xtask header [--day <1-12>] [--check]
+
+  --day        only this day; every day when omitted
+  --check      fail if README.md's library sizes or bench lists, or the C
+               headers, are stale, without writing anything
+  --runs       divan runs per day; defaults to 5
+  --save       also write the runs to `day-N/benches/baseline.tsv`
+  --alpha      significance level for a regression; defaults to 0.05
+  --threshold  smallest slowdown reported, as a fraction; defaults to 0.05
+  --archetype  plain, owner-view or nom; defaults to plain
+  --fetch      command that downloads the input, run after scaffolding with
+               `{day}` and `{input}` filled in; defaults to $AOC_FETCH, and
+               without either `input.txt` starts empty";
+
+// --------------------------
+// Args
+// --------------------------
+
+#[derive(Debug, PartialEq)]
+enum Task {
+  Readme {
+    days: Vec<u8>,
+    check: bool,
+  },
+  /// Record divan runs as TSV, optionally as the committed baseline.
+  Bench {
+    days: Vec<u8>,
+    runs: u32,
+    save: bool,
+  },
+  /// Record divan runs and test them against the baseline.
+  Compare {
+    days: Vec<u8>,
+    runs: u32,
+    alpha: f64,
+    threshold: f64,
+  },
+  /// Scaffold `day-N` from `template/`.
+  New {
+    day: Option<u8>,
+    archetype: Archetype,
+    fetch: Option<String>,
+  },
+  /// Generate the C headers from the `ffi` modules.
+  Header {
+    days: Vec<u8>,
+    check: bool,
+  },
+}
+
+fn parse_args<I>(args: I) -> Result<Task, String>
+where
+  I: IntoIterator<Item = String>,
+{
+  let mut args = args.into_iter();
+  let mut task = match args.next().as_deref() {
+    Some("readme") => Task::Readme { days: all_days(), check: false },
+    Some("bench") => Task::Bench { days: all_days(), runs: 5, save: false },
+    Some("compare") => {
+      Task::Compare { days: all_days(), runs: 5, alpha: 0.05, threshold: 0.05 }
+    }
+    Some("header") => Task::Header { days: all_days(), check: false },
+    Some("new") => {
+      Task::New { day: None, archetype: Archetype::Plain, fetch: None }
+    }
+    Some(other) => return Err(format!("unknown command `{other}`")),
+    None => return Err("missing command".into()),
+  };
+
+  while let Some(flag) = args.next() {
+    match (&mut task, flag.as_str()) {
+      (Task::Readme { check, .. } | Task::Header { check, .. }, "--check") => {
+        *check = true;
+      }
+      (Task::Bench { save, .. }, "--save") => *save = true,
+      (
+        Task::Readme { days, .. }
+        | Task::Bench { days, .. }
+        | Task::Compare { days, .. }
+        | Task::Header { days, .. },
+        "--day",
+      ) => *days = vec![day(&value(&mut args, &flag)?, 12)?],
+      (Task::New { day: new, .. }, "--day") => {
+        *new = Some(day(&value(&mut args, &flag)?, 25)?);
+      }
+      (Task::New { archetype, .. }, "--archetype") => {
+        let value = value(&mut args, &flag)?;
+        *archetype = Archetype::from_name(&value).ok_or_else(|| {
+          format!("unknown archetype `{value}`; one of {}", Archetype::NAMES)
+        })?;
+      }
+      (Task::New { fetch, .. }, "--fetch") => {
+        *fetch = Some(value(&mut args, &flag)?);
+      }
+      (Task::Bench { runs, .. } | Task::Compare { runs, .. }, "--runs") => {
+        let value = value(&mut args, &flag)?;
+        *runs = value
+          .parse()
+          .ok()
+          .filter(|&n| n > 0)
+          .ok_or_else(|| format!("invalid run count `{value}`"))?;
+      }
+      (Task::Compare { alpha, .. }, "--alpha") => {
+        *alpha = fraction(&value(&mut args, &flag)?)?;
+      }
+      (Task::Compare { threshold, .. }, "--threshold") => {
+        *threshold = fraction(&value(&mut args, &flag)?)?;
+      }
+      _ => return Err(format!("unknown flag `{flag}`")),
+    }
+  }
+
+  if let Task::New { day: None, .. } = task {
+    return Err("missing --day".into());
+  }
+
+  Ok(task)
+}
+
+fn all_days() -> Vec<u8> {
+  (1..=12).collect()
+}
+
+fn day(value: &str, last: u8) -> Result<u8, String> {
+  value
+    .parse::<u8>()
+    .ok()
+    .filter(|n| (1..=last).contains(n))
+    .ok_or_else(|| format!("invalid day `{value}`"))
+}
+
+fn value(
+  args: &mut impl Iterator<Item = String>,
+  flag: &str,
+) -> Result<String, String> {
+  args
+    .next()
+    .ok_or_else(|| format!("missing value for `{flag}`"))
+}
+
+fn fraction(value: &str) -> Result<f64, String> {
+  value
+    .parse::<f64>()
+    .ok()
+    .filter(|f| (0.0..1.0).contains(f))
+    .ok_or_else(|| format!("invalid fraction `{value}`"))
+}
+
+// --------------------------
+// Cargo
+// --------------------------
+
+fn workspace_root() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .parent()
+    .expect("xtask lives inside the workspace")
+    .to_path_buf()
+}
+
+fn cargo() -> Command {
+  Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+/// Runs `cmd` with stderr passed through and returns its stdout.
+fn output(mut cmd: Command) -> Result<String, String> {
+  let out = cmd
+    .stderr(Stdio::inherit())
+    .output()
+    .map_err(|e| format!("cannot run {cmd:?}: {e}"))?;
+  if !out.status.success() {
+    return Err(format!("{cmd:?} failed with {}", out.status));
+  }
+  String::from_utf8(out.stdout)
+    .map_err(|_| format!("{cmd:?} printed invalid UTF-8"))
+}
+
+/// The day's package name, read from its manifest.
+fn package(root: &Path, day: u8) -> Result<String, String> {
+  let path = root.join(format!("day-{day}/Cargo.toml"));
+  let manifest = std::fs::read_to_string(&path)
+    .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+  manifest
+    .lines()
+    .find_map(|l| l.strip_prefix("name = \""))
+    .and_then(|l| l.strip_suffix('"'))
+    .map(String::from)
+    .ok_or_else(|| format!("no package name in {}", path.display()))
+}
+
+/// Builds each part's library alone and reads the `.rlib` size.
+fn lib_sizes(
+  root: &Path,
+  day: u8,
+  package: &str,
+) -> Result<Vec<LibSize>, String> {
+  let dir = format!("day-{day}");
+  let rlib = format!("lib{}.rlib", package.replace('-', "_"));
+
+  [1, 2]
+    .into_iter()
+    .map(|part| {
+      let mut cmd = cargo();
+      cmd
+        .args(readme::build_args(part))
+        .current_dir(root.join(&dir));
+      output(cmd)?;
+
+      let path = format!("{dir}/target/lib-part{part}/release/{rlib}");
+      let bytes = std::fs::metadata(root.join(&path))
+        .map_err(|e| format!("cannot stat {path}: {e}"))?
+        .len();
+      Ok(LibSize { part, bytes, path })
+    })
+    .collect()
+}
+
+/// Runs the day's `bench` target on its `input.txt`.
+fn bench(
+  root: &Path,
+  day: u8,
+  package: &str,
+  extra: &[&str],
+) -> Result<String, String> {
+  let mut cmd = cargo();
+  cmd
+    .args(["bench", "-q", "-p", package, "--bench", "bench", "--"])
+    .args(extra)
+    .env("AOC_INPUT", root.join(format!("day-{day}/input.txt")))
+    .current_dir(root);
+  output(cmd)
+}
+
+/// Runs the day's benches `runs` times and collects every row.
+fn record(root: &Path, day: u8, runs: u32) -> Result<Results, String> {
+  let package = package(root, day)?;
+  let input = std::fs::read(root.join(format!("day-{day}/input.txt"))).ok();
+  let mut rows = Vec::new();
+
+  for run in 1..=runs {
+    eprintln!("day {day}: run {run}/{runs}");
+    rows.extend(bench::parse_run(&bench(root, day, &package, &[])?, run)?);
+  }
+
+  let results = Results { input: bench::input_hash(input.as_deref()), rows };
+  let path = root.join(format!("target/bench/day-{day}.tsv"));
+  write(&path, &results.to_tsv())?;
+  Ok(results)
+}
+
+fn baseline_path(root: &Path, day: u8) -> PathBuf {
+  root.join(format!("day-{day}/benches/baseline.tsv"))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)
+      .map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+  }
+  std::fs::write(path, text)
+    .map_err(|e| format!("cannot write {}: {e}", path.display()))
+}
+
+// --------------------------
+// Main
+// --------------------------
+
+fn main() -> ExitCode {
+  let task = match parse_args(std::env::args().skip(1)) {
+    Ok(task) => task,
+    Err(e) => {
+      eprintln!("error: {e}\n\n{USAGE}");
+      return ExitCode::from(2);
+    }
+  };
+
+  let outcome = match task {
+    Task::Readme { days, check } => readme(&days, check),
+    Task::Bench { days, runs, save } => record_all(&days, runs, save),
+    Task::Compare { days, runs, alpha, threshold } => {
+      compare(&days, runs, alpha, threshold)
+    }
+    Task::New { day: Some(day), archetype, fetch } => new(
+      day,
+      archetype,
+      fetch.or_else(|| std::env::var("AOC_FETCH").ok()),
+    ),
+    Task::New { day: None, .. } => Err("missing --day".into()),
+    Task::Header { days, check } => headers(&workspace_root(), &days, check),
+  };
+
+  match outcome {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::FAILURE,
+    Err(e) => {
+      eprintln!("error: {e}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+/// Refreshes or checks README.md; `Ok(false)` means `--check` found it
+/// stale.
+fn readme(days: &[u8], check: bool) -> Result<bool, String> {
+  let root = workspace_root();
+  let path = root.join("README.md");
+  let mut text = std::fs::read_to_string(&path)
+    .map_err(|e| format!("cannot read README.md: {e}"))?;
+  let mut fresh = true;
+
+  for &day in days {
+    let package = package(&root, day)?;
+    let sizes = lib_sizes(&root, day, &package)?;
+
+    if check {
+      let list = bench(&root, day, &package, &["--list"])?;
+      let benches: Vec<&str> =
+        list.lines().filter_map(bench::bench_name).collect();
+      for reason in readme::stale(&text, day, &benches, &sizes)? {
+        eprintln!("{reason}");
+        fresh = false;
+      }
+    } else {
+      let rows = bench::bench_rows(&bench(&root, day, &package, &[])?);
+      text = readme::update(&text, day, &rows, &sizes)?;
+      eprintln!("updated day {day}");
+    }
+  }
+
+  if check {
+    if !fresh {
+      eprintln!("README.md is stale; run `cargo xtask readme`");
+    }
+  } else {
+    write(&path, &text)?;
+  }
+
+  Ok(fresh)
+}
+
+/// Writes or checks `aoc.h` and each day's header; `Ok(false)` means
+/// `--check` found one stale.
+fn headers(root: &Path, days: &[u8], check: bool) -> Result<bool, String> {
+  let mut fresh = true;
+  let mut outputs = vec![(header::core_paths(), None)];
+  outputs.extend(days.iter().map(|&day| (header::day_paths(day), Some(day))));
+
+  for ((source, target), day) in outputs {
+    let text = std::fs::read_to_string(root.join(&source))
+      .map_err(|e| format!("cannot read {source}: {e}"))?;
+    let header = match day {
+      Some(day) => header::day_header(day, &text)?,
+      None => header::core_header(&text)?,
+    };
+
+    let current = std::fs::read_to_string(root.join(&target)).ok();
+    if current.as_deref() == Some(header.as_str()) {
+      continue;
+    }
+    if check {
+      eprintln!("{target} is stale; run `cargo xtask header`");
+      fresh = false;
+    } else {
+      write(&root.join(&target), &header)?;
+      eprintln!("wrote {target}");
+    }
+  }
+
+  Ok(fresh)
+}
+
+fn record_all(days: &[u8], runs: u32, save: bool) -> Result<bool, String> {
+  let root = workspace_root();
+
+  for &day in days {
+    let results = record(&root, day, runs)?;
+    if save {
+      write(&baseline_path(&root, day), &results.to_tsv())?;
+      eprintln!("saved day {day} baseline");
+    }
+  }
+
+  Ok(true)
+}
+
+/// Prints one line per bench; `Ok(false)` means something regressed.
+fn compare(
+  days: &[u8],
+  runs: u32,
+  alpha: f64,
+  threshold: f64,
+) -> Result<bool, String> {
+  let root = workspace_root();
+  let mut clean = true;
+
+  for &day in days {
+    let path = baseline_path(&root, day);
+    let Ok(text) = std::fs::read_to_string(&path) else {
+      eprintln!("skipping day {day}: no {}", path.display());
+      continue;
+    };
+    let baseline = Results::from_tsv(&text)
+      .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let current = record(&root, day, runs)?;
+    if current.input != baseline.input {
+      eprintln!(
+        "skipping day {day}: baseline was recorded on input {}, this is {}",
+        baseline.input, current.input
+      );
+      continue;
+    }
+
+    for v in bench::compare(&baseline, &current, alpha, threshold) {
+      println!("day {day} {}", verdict(&v));
+      clean &= !v.regressed;
+    }
+  }
+
+  Ok(clean)
+}
+
+/// Writes `day-N` from the templates, then fetches its input if a fetch
+/// command was given. Never touches an existing directory.
+fn new(
+  day: u8,
+  archetype: Archetype,
+  fetch: Option<String>,
+) -> Result<bool, String> {
+  let root = workspace_root();
+  let dir = root.join(format!("day-{day}"));
+  if dir.exists() {
+    return Err(format!("{} already exists", dir.display()));
+  }
+
+  for (path, text) in scaffold::render(day, archetype) {
+    write(&dir.join(path), &text)?;
+  }
+  eprintln!("created day-{day} ({archetype:?})");
+
+  let input = format!("day-{day}/input.txt");
+  match fetch {
+    Some(template) => {
+      let args = scaffold::fetch_command(&template, day, &input);
+      let Some((program, rest)) = args.split_first() else {
+        return Err("empty fetch command".into());
+      };
+      let status = Command::new(program)
+        .args(rest)
+        .current_dir(&root)
+        .status()
+        .map_err(|e| format!("cannot run `{program}`: {e}"))?;
+      if !status.success() {
+        return Err(format!("fetching the input failed with {status}"));
+      }
+    }
+    None => {
+      write(&root.join(&input), "")?;
+      eprintln!("no fetch command; {input} is empty");
+    }
+  }
+
+  eprintln!(
+    "add day-{day} to aoc/Cargo.toml and aoc/src/days.rs to run it with `aoc`"
+  );
+  Ok(true)
+}
+
+fn verdict(v: &Verdict) -> String {
+  let change = (v.after / v.before - 1.0) * 100.0;
+  format!(
+    "{}: {} → {} ({change:+.1}%, p = {:.3}){}",
+    v.bench,
+    duration(v.before),
+    duration(v.after),
+    v.p,
+    if v.regressed { "  REGRESSION" } else { "" }
+  )
+}
+
+fn duration(nanos: f64) -> String {
+  let (value, unit) = match nanos {
+    n if n >= 1e9 => (n / 1e9, "s"),
+    n if n >= 1e6 => (n / 1e6, "ms"),
+    n if n >= 1e3 => (n / 1e3, "µs"),
+    n => (n, "ns"),
+  };
+  // four significant digits, like divan
+  let decimals = match value {
+    v if v < 10.0 => 3,
+    v if v < 100.0 => 2,
+    _ => 1,
+  };
+  format!("{value:.decimals$} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn task(line: &str) -> Result<Task, String> {
+    parse_args(line.split_whitespace().map(String::from))
+  }
+
+  #[test]
+  fn test_parse_args() {
+    let Task::Readme { days, check } = task("readme").unwrap() else {
+      panic!("expected readme");
+    };
+    assert_eq!(days.len(), 12);
+    assert!(!check);
+
+    assert_eq!(
+      task("readme --check --day 3").unwrap(),
+      Task::Readme { days: vec![3], check: true }
+    );
+    assert_eq!(
+      task("bench --day 9 --runs 3 --save").unwrap(),
+      Task::Bench { days: vec![9], runs: 3, save: true }
+    );
+    assert_eq!(
+      task("compare --day 10 --alpha 0.01 --threshold 0.1").unwrap(),
+      Task::Compare { days: vec![10], runs: 5, alpha: 0.01, threshold: 0.1 }
+    );
+
+    assert!(task("readme --day 13").is_err());
+    assert!(task("readme --day").is_err());
+    assert!(task("readme --save").is_err());
+    assert!(task("bench --runs 0").is_err());
+    assert!(task("compare --alpha 2").is_err());
+    assert_eq!(
+      task("new --day 13 --archetype owner-view --fetch aoc").unwrap(),
+      Task::New {
+        day: Some(13),
+        archetype: Archetype::OwnerView,
+        fetch: Some("aoc".into()),
+      }
+    );
+    assert_eq!(
+      task("header --day 4 --check").unwrap(),
+      Task::Header { days: vec![4], check: true }
+    );
+    assert!(task("header --save").is_err());
+    assert!(task("new").is_err());
+    assert!(task("new --day 26").is_err());
+    assert!(task("new --day 13 --archetype view").is_err());
+    assert!(task("bench --archetype nom").is_err());
+    assert!(task("run").is_err());
+    assert!(task("").is_err());
+  }
+
+  #[test]
+  fn test_duration() {
+    assert_eq!(duration(603.4), "603.4 ns");
+    assert_eq!(duration(1_161.0), "1.161 µs");
+    assert_eq!(duration(268_800_000.0), "268.8 ms");
+    assert_eq!(duration(12_345_000_000.0), "12.35 s");
+  }
+
+  #[test]
+  fn test_package() {
+    let root = workspace_root();
+    assert_eq!(package(&root, 1).unwrap(), "day_1");
+    assert_eq!(package(&root, 12).unwrap(), "day-12");
+  }
+
+  #[test]
+  fn test_headers_fresh() {
+    let fresh = headers(&workspace_root(), &all_days(), true).unwrap();
+    assert!(fresh, "run `cargo xtask header`");
+  }
+
+  #[test]
+  fn test_baselines_parse() {
+    let root = workspace_root();
+    for day in all_days() {
+      let path = baseline_path(&root, day);
+      let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("day {day} baseline `{}`: {e}", path.display()));
+      let baseline = Results::from_tsv(&text).unwrap();
+      assert!(!baseline.rows.is_empty(), "day {day} baseline is empty");
+    }
+  }
+}
@@ -0,0 +1,341 @@
+//! Rewrites the generated parts of each `## day N` section in README.md:
+//! the fenced block under `### Benchmarks:` and the bullets under
+//! `### \`no_std\` library builds:`. Prose around them is left alone.
+
+use crate::bench::bench_name;
+
+const BENCHMARKS: &str = "### Benchmarks:";
+const LIB_BUILDS: &str = "### `no_std` library builds:";
+const FENCE: &str = "```";
+
+/// The size of one part's `no_std` library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibSize {
+  pub part: u8,
+  pub bytes: u64,
+  /// Path of the `.rlib`, relative to the workspace root.
+  pub path: String,
+}
+
+/// The exact build command a `LibSize` was measured with, run from the
+/// day's directory.
+pub fn build_args(part: u8) -> [String; 8] {
+  [
+    "build".into(),
+    "--release".into(),
+    "--lib".into(),
+    "--no-default-features".into(),
+    "--features".into(),
+    format!("part{part}"),
+    "--target-dir".into(),
+    format!("target/lib-part{part}"),
+  ]
+}
+
+/// Replaces the benchmark rows and library sizes in `day`'s section.
+pub fn update(
+  readme: &str,
+  day: u8,
+  rows: &[String],
+  sizes: &[LibSize],
+) -> Result<String, String> {
+  let (start, end) = section(readme, day)?;
+  let mut lines: Vec<String> =
+    readme[start..end].lines().map(String::from).collect();
+
+  replace_benchmarks(&mut lines, rows);
+  replace_sizes(&mut lines, sizes);
+
+  let mut out = String::with_capacity(readme.len());
+  out.push_str(&readme[..start]);
+  for line in &lines {
+    out.push_str(line);
+    out.push('\n');
+  }
+  out.push_str(&readme[end..]);
+  Ok(out)
+}
+
+/// Lists why `day`'s section no longer matches the measured sizes and
+/// the benches the day defines. Timings are too noisy to compare.
+pub fn stale(
+  readme: &str,
+  day: u8,
+  benches: &[&str],
+  sizes: &[LibSize],
+) -> Result<Vec<String>, String> {
+  let (start, end) = section(readme, day)?;
+  let lines: Vec<&str> = readme[start..end].lines().collect();
+  let mut reasons = Vec::new();
+
+  let listed: Vec<&str> = match fenced(&lines) {
+    Some((open, close)) => lines[open + 1..close]
+      .iter()
+      .filter_map(|l| bench_name(l))
+      .collect(),
+    None => Vec::new(),
+  };
+  if listed != benches {
+    reasons.push(format!(
+      "day {day}: benchmarks list {listed:?}, expected {benches:?}"
+    ));
+  }
+
+  for size in sizes {
+    let prefix = format!("* Part {}:", size.part);
+    let found = lines
+      .iter()
+      .find(|l| l.starts_with(&prefix))
+      .and_then(|l| recorded_bytes(l));
+    if found != Some(size.bytes) {
+      let found = found.map_or("nothing".into(), |b| format!("{b} bytes"));
+      reasons.push(format!(
+        "day {day}: part {} library is {} bytes, README says {found}",
+        size.part, size.bytes
+      ));
+    }
+  }
+
+  Ok(reasons)
+}
+
+/// Byte range of `## day N` up to the next `## ` heading.
+fn section(readme: &str, day: u8) -> Result<(usize, usize), String> {
+  let heading = format!("## day {day}\n");
+  let start = if readme.starts_with(&heading) {
+    0
+  } else {
+    readme
+      .find(&format!("\n{heading}"))
+      .map(|i| i + 1)
+      .ok_or_else(|| format!("README has no `## day {day}` section"))?
+  };
+  let body = start + heading.len();
+  let end = readme[body..]
+    .find("\n## ")
+    .map_or(readme.len(), |i| body + i + 1);
+  Ok((start, end))
+}
+
+/// Line indices of the opening and closing fence under `### Benchmarks:`.
+fn fenced<S: AsRef<str>>(lines: &[S]) -> Option<(usize, usize)> {
+  let heading = find(lines, BENCHMARKS)?;
+  let open = heading
+    + 1
+    + lines[heading + 1..]
+      .iter()
+      .take_while(|l| !l.as_ref().starts_with("###"))
+      .position(|l| l.as_ref().starts_with(FENCE))?;
+  let close =
+    open + 1 + lines[open + 1..].iter().position(|l| l.as_ref() == FENCE)?;
+  Some((open, close))
+}
+
+fn replace_benchmarks(lines: &mut Vec<String>, rows: &[String]) {
+  if let Some((open, close)) = fenced(lines) {
+    lines.splice(open + 1..close, rows.iter().cloned());
+    return;
+  }
+
+  let mut block = vec![BENCHMARKS.to_string(), String::new(), FENCE.into()];
+  block.extend(rows.iter().cloned());
+  block.push(FENCE.into());
+  let at = match find(lines, LIB_BUILDS) {
+    Some(at) => {
+      block.push(String::new());
+      at
+    }
+    None => {
+      block.insert(0, String::new());
+      content_end(lines)
+    }
+  };
+  lines.splice(at..at, block);
+}
+
+fn replace_sizes(lines: &mut Vec<String>, sizes: &[LibSize]) {
+  let Some(heading) = find(lines, LIB_BUILDS) else {
+    let at = content_end(lines);
+    let mut block = vec![String::new(), LIB_BUILDS.into(), String::new()];
+    block.extend(sizes.iter().map(|s| bullet(s, "")));
+    lines.splice(at..at, block);
+    return;
+  };
+
+  let first = heading
+    + 1
+    + lines[heading + 1..]
+      .iter()
+      .take_while(|l| l.is_empty())
+      .count();
+  let count = lines[first..]
+    .iter()
+    .take_while(|l| l.starts_with("* Part "))
+    .count();
+  let old = &lines[first..first + count];
+
+  let bullets: Vec<String> = sizes
+    .iter()
+    .map(|size| {
+      let prefix = format!("* Part {}:", size.part);
+      let remark = old
+        .iter()
+        .find(|l| l.starts_with(&prefix))
+        .map_or("", |l| remark(l));
+      bullet(size, remark)
+    })
+    .collect();
+  lines.splice(first..first + count, bullets);
+}
+
+fn bullet(size: &LibSize, remark: &str) -> String {
+  let args = build_args(size.part).join(" ");
+  let mut line = format!(
+    "* Part {}: `cargo {args}` → {} bytes (`{}`)",
+    size.part,
+    thousands(size.bytes),
+    size.path
+  );
+  if !remark.is_empty() {
+    line.push(' ');
+    line.push_str(remark);
+  }
+  line
+}
+
+/// The size a bullet records, with or without thousands separators.
+fn recorded_bytes(line: &str) -> Option<u64> {
+  let (_, after) = line.split_once("→ ")?;
+  let digits: String = after
+    .chars()
+    .take_while(|c| c.is_ascii_digit() || *c == ',')
+    .filter(char::is_ascii_digit)
+    .collect();
+  digits.parse().ok()
+}
+
+/// Hand-written text after a bullet's size and path, kept on rewrite.
+fn remark(line: &str) -> &str {
+  let Some((_, after)) = line.split_once("→ ") else {
+    return "";
+  };
+  let rest = after.trim_start_matches(|c: char| c.is_ascii_digit() || c == ',');
+  let rest = rest.trim_start().trim_start_matches("bytes").trim_start();
+  let rest = match rest.strip_prefix("(`") {
+    Some(path) => path.split_once("`)").map_or("", |(_, r)| r),
+    None => rest,
+  };
+  rest.trim()
+}
+
+fn find<S: AsRef<str>>(lines: &[S], heading: &str) -> Option<usize> {
+  lines.iter().position(|l| l.as_ref() == heading)
+}
+
+/// Index after the last line of prose, before any trailing blanks and
+/// `---` rule.
+fn content_end(lines: &[String]) -> usize {
+  lines
+    .iter()
+    .rposition(|l| !l.is_empty() && l != "---")
+    .map_or(lines.len(), |i| i + 1)
+}
+
+fn thousands(n: u64) -> String {
+  let digits = n.to_string();
+  let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+  for (i, c) in digits.chars().enumerate() {
+    if i > 0 && (digits.len() - i).is_multiple_of(3) {
+      out.push(',');
+    }
+    out.push(c);
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const README: &str = "\
+# title
+
+## day 5
+
+Intro.
+
+### Benchmarks:
+
+```
+╰─ bench_part1  1 µs │ 2 µs
+```
+
+Note: kept.
+
+### `no_std` library builds:
+
+* Part 1: `cargo build` → 43376 *size depends on SIMD choice*
+* Part 2: `cargo build` → 1,024 bytes (`day-5/old.rlib`)
+
+---
+
+## day 6
+
+Only prose.
+";
+
+  fn size(part: u8, bytes: u64) -> LibSize {
+    LibSize { part, bytes, path: format!("lib-part{part}.rlib") }
+  }
+
+  #[test]
+  fn test_update() {
+    let rows = vec![
+      "bench  fastest".to_string(),
+      "├─ bench_part1  3 µs".to_string(),
+      "╰─ bench_part2  4 µs".to_string(),
+    ];
+    let sizes = [size(1, 43_376), size(2, 2_048)];
+    let out = update(README, 5, &rows, &sizes).unwrap();
+
+    assert!(out.contains("```\nbench  fastest\n├─ bench_part1  3 µs\n"));
+    assert!(!out.contains("1 µs"));
+    assert!(out.contains("```\n\nNote: kept.\n"));
+    assert!(out.contains(
+      "→ 43,376 bytes (`lib-part1.rlib`) *size depends on SIMD choice*\n"
+    ));
+    assert!(out.contains("→ 2,048 bytes (`lib-part2.rlib`)\n\n---\n"));
+    assert!(out.ends_with("## day 6\n\nOnly prose.\n"));
+    assert_eq!(update(&out, 5, &rows, &sizes).unwrap(), out);
+
+    assert!(
+      stale(&out, 5, &["bench_part1", "bench_part2"], &sizes)
+        .unwrap()
+        .is_empty()
+    );
+    let reasons = stale(README, 5, &["bench_part1"], &sizes).unwrap();
+    assert_eq!(reasons.len(), 1);
+    assert!(reasons[0].contains("2048 bytes, README says 1024 bytes"));
+  }
+
+  #[test]
+  fn test_update_missing_sections() {
+    let rows = vec!["╰─ bench_part1  3 µs".to_string()];
+    let out = update(README, 6, &rows, &[size(1, 10)]).unwrap();
+    assert!(out.ends_with(
+      "## day 6\n\nOnly prose.\n\n### Benchmarks:\n\n```\n\
+       ╰─ bench_part1  3 µs\n```\n\n### `no_std` library builds:\n\n\
+       * Part 1: `cargo build --release --lib --no-default-features \
+       --features part1 --target-dir target/lib-part1` → 10 bytes \
+       (`lib-part1.rlib`)\n"
+    ));
+
+    assert!(update(README, 7, &rows, &[]).is_err());
+  }
+
+  #[test]
+  fn test_thousands() {
+    assert_eq!(thousands(1_234_567), "1,234,567");
+    assert_eq!(thousands(999), "999");
+  }
+}
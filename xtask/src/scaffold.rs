@@ -0,0 +1,197 @@
+//! Renders a new day crate from `template/`. The templates are compiled in,
+//! so scaffolding needs neither the network nor a shell.
+
+/// Destination path inside `day-N/`, and template text.
+type Files = &'static [(&'static str, &'static str)];
+
+macro_rules! template {
+  ($path:literal) => {
+    include_str!(concat!("../../template/", $path))
+  };
+}
+
+// the fuzz tests need at least one seed input; replace it with real ones
+const COMMON: Files = &[
+  ("Cargo.toml", template!("Cargo.toml")),
+  ("sample.txt", ""),
+  ("corpus/empty", ""),
+  ("tests/common/mod.rs", template!("tests/common/mod.rs")),
+  ("tests/common/synth.rs", template!("tests/common/synth.rs")),
+];
+
+const PLAIN: Files = &[
+  ("src/lib.rs", template!("plain/lib.rs")),
+  ("src/main.rs", template!("plain/main.rs")),
+  ("benches/bench.rs", template!("bench.rs")),
+  ("benches/scaling.rs", template!("scaling.rs")),
+  ("tests/alloc.rs", template!("tests/alloc.rs")),
+  ("tests/stack.rs", template!("tests/stack.rs")),
+  ("tests/reference.rs", template!("plain/reference.rs")),
+  ("tests/fuzz.rs", template!("tests/fuzz.rs")),
+];
+
+const OWNER_VIEW: Files = &[
+  ("src/lib.rs", template!("owner-view/lib.rs")),
+  ("src/main.rs", template!("owner-view/main.rs")),
+  ("src/std_parse.rs", template!("owner-view/std_parse.rs")),
+  ("benches/bench.rs", template!("owner-view/bench.rs")),
+  ("benches/scaling.rs", template!("owner-view/scaling.rs")),
+  ("tests/alloc.rs", template!("owner-view/tests/alloc.rs")),
+  ("tests/stack.rs", template!("owner-view/tests/stack.rs")),
+  ("tests/reference.rs", template!("owner-view/tests/reference.rs")),
+  ("tests/fuzz.rs", template!("owner-view/tests/fuzz.rs")),
+];
+
+const NOM: Files = &[
+  ("src/lib.rs", template!("nom/lib.rs")),
+  ("src/main.rs", template!("nom/main.rs")),
+  ("benches/bench.rs", template!("bench.rs")),
+  ("benches/scaling.rs", template!("scaling.rs")),
+  ("tests/alloc.rs", template!("tests/alloc.rs")),
+  ("tests/stack.rs", template!("tests/stack.rs")),
+  ("tests/reference.rs", template!("nom/reference.rs")),
+  ("tests/fuzz.rs", template!("tests/fuzz.rs")),
+];
+
+/// The shape of a new day's library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Archetype {
+  /// A `Problem` borrowing the input `&str`, as in days 1–3.
+  Plain,
+  /// A `Problem` view over caller buffers, owned on the std side by
+  /// `std_parse::ProblemData`, as in days 5–7.
+  OwnerView,
+  /// A nom grammar into `heapless` storage reporting `aoc_core::Error`, as
+  /// in day 12.
+  Nom,
+}
+
+impl Archetype {
+  pub const NAMES: &str = "plain, owner-view, nom";
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "plain" => Some(Archetype::Plain),
+      "owner-view" => Some(Archetype::OwnerView),
+      "nom" => Some(Archetype::Nom),
+      _ => None,
+    }
+  }
+
+  fn files(self) -> Files {
+    match self {
+      Archetype::Plain => PLAIN,
+      Archetype::OwnerView => OWNER_VIEW,
+      Archetype::Nom => NOM,
+    }
+  }
+
+  /// Dependencies beyond `aoc-core`, as `Cargo.toml` lines.
+  fn dependencies(self) -> &'static str {
+    match self {
+      Archetype::Plain | Archetype::OwnerView => "",
+      Archetype::Nom => {
+        "nom = { version = \"8\", default-features = false }\n\
+         heapless = \"^0.9\"\n"
+      }
+    }
+  }
+
+  /// Sizes for `benches/scaling.rs`; nom's `heapless` storage holds at most
+  /// `MAX_VALUES` values.
+  fn scaling_sizes(self) -> &'static str {
+    match self {
+      Archetype::Plain | Archetype::OwnerView => "100, 1_000, 10_000",
+      Archetype::Nom => "16, 64, 256",
+    }
+  }
+
+  /// Debug-build stack budget for `tests/stack.rs`; nom's combinators copy
+  /// the `heapless` accumulator around until optimised.
+  fn stack_budget(self) -> &'static str {
+    match self {
+      Archetype::Plain | Archetype::OwnerView => "8 << 10",
+      Archetype::Nom => "64 << 10",
+    }
+  }
+}
+
+/// Every file of `day-N`, relative to its directory, with the placeholders
+/// filled in: `__DAY__` is the package name in `Cargo.toml` and the crate
+/// name elsewhere, `__N__` the day number, `__BUDGET__` the stack budget and
+/// `__SIZES__` the scaling bench sizes.
+pub fn render(day: u8, archetype: Archetype) -> Vec<(&'static str, String)> {
+  COMMON
+    .iter()
+    .chain(archetype.files())
+    .map(|&(path, text)| {
+      let text = if path == "Cargo.toml" {
+        text
+          .replace("__DEPENDENCIES__\n", archetype.dependencies())
+          .replace("__DAY__", &format!("day-{day}"))
+      } else {
+        text.replace("__DAY__", &format!("day_{day}"))
+      };
+      let text = text
+        .replace("__BUDGET__", archetype.stack_budget())
+        .replace("__SIZES__", archetype.scaling_sizes());
+      (path, text.replace("__N__", &day.to_string()))
+    })
+    .collect()
+}
+
+/// Splits a fetch command on whitespace and fills in `{day}` and `{input}`.
+pub fn fetch_command(template: &str, day: u8, input: &str) -> Vec<String> {
+  template
+    .split_whitespace()
+    .map(|arg| {
+      arg
+        .replace("{day}", &day.to_string())
+        .replace("{input}", input)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_render() {
+    for archetype in [Archetype::Plain, Archetype::OwnerView, Archetype::Nom] {
+      let files = render(13, archetype);
+      for (path, text) in &files {
+        assert!(
+          !text.contains("__"),
+          "{archetype:?} {path}: placeholder left"
+        );
+      }
+
+      let manifest = &files[0].1;
+      assert!(manifest.contains("name = \"day-13\""));
+      assert_eq!(manifest.contains("nom = "), archetype == Archetype::Nom);
+
+      let lib = &files.iter().find(|(p, _)| *p == "src/lib.rs").unwrap().1;
+      assert!(lib.contains("pub struct Day13;"));
+      assert!(lib.contains("const DAY: u8 = 13;"));
+    }
+
+    let owner_view = render(13, Archetype::OwnerView);
+    assert!(owner_view.iter().any(|(p, _)| *p == "src/std_parse.rs"));
+    assert!(owner_view.iter().any(|(p, _)| *p == "corpus/empty"));
+    assert!(owner_view.iter().any(|(p, _)| *p == "benches/scaling.rs"));
+    assert_eq!(
+      Archetype::from_name("owner-view"),
+      Some(Archetype::OwnerView)
+    );
+    assert_eq!(Archetype::from_name("view"), None);
+  }
+
+  #[test]
+  fn test_fetch_command() {
+    assert_eq!(
+      fetch_command("aoc download --day {day} --input-file {input}", 3, "i"),
+      ["aoc", "download", "--day", "3", "--input-file", "i"]
+    );
+  }
+}